@@ -2,7 +2,13 @@
 
 use tauri::command;
 use crate::services::app_service::AppService;
-use crate::models::app::{InstalledApps, UninstallResult, AppRelatedFiles, DuplicateResult};
+use crate::services::macho_service::MachOService;
+use crate::services::bundle_size_service::BundleSizeService;
+use crate::services::association_service::AssociationService;
+use crate::models::app::{InstalledApps, AppFilter, UninstallResult, AppRelatedFiles, DuplicateResult};
+use crate::models::macho::AppArchInfo;
+use crate::models::bundle_size::AppSizeBreakdown;
+use crate::models::associations::{AppAssociations, AssociationIndex, AssociationLookup};
 
 /// 获取已安装的应用列表
 #[command]
@@ -11,6 +17,27 @@ pub fn get_installed_apps() -> Result<InstalledApps, String> {
     service.get_installed_apps()
 }
 
+/// 获取 Homebrew formula、未对应应用包的 cask 和 Linux 应用
+#[command]
+pub fn get_package_apps() -> Result<InstalledApps, String> {
+    let service = AppService::new();
+    service.get_package_apps()
+}
+
+/// 按条件筛选已安装的应用
+#[command]
+pub fn filter_installed_apps(filter: AppFilter) -> Result<InstalledApps, String> {
+    let service = AppService::new();
+    service.filter_installed_apps(&filter)
+}
+
+/// 获取应用架构信息（主程序、内嵌框架及转译状态）
+#[command]
+pub fn get_app_arch_info(app_path: &str) -> Result<AppArchInfo, String> {
+    let service = MachOService::new();
+    service.get_app_arch_info(app_path)
+}
+
 /// 获取支持双开的应用列表
 #[command]
 pub fn get_duplicatable_apps() -> Result<InstalledApps, String> {
@@ -25,6 +52,13 @@ pub fn get_app_size(app_path: &str) -> u64 {
     service.get_single_app_size(app_path)
 }
 
+/// 获取应用包大小构成
+#[command]
+pub fn get_app_size_breakdown(app_path: &str) -> Result<AppSizeBreakdown, String> {
+    let service = BundleSizeService::new();
+    service.get_size_breakdown(app_path)
+}
+
 /// 获取单个应用的图标
 #[command]
 pub fn get_app_icon(app_path: &str) -> String {
@@ -32,6 +66,41 @@ pub fn get_app_icon(app_path: &str) -> String {
     service.get_single_app_icon(app_path)
 }
 
+/// 获取应用声明的文件类型与 URL 协议
+#[command]
+pub fn get_app_associations(app_path: &str) -> Result<AppAssociations, String> {
+    let service = AssociationService::new();
+    service.get_app_associations(app_path)
+}
+
+/// 获取已安装应用的文件类型与 URL 协议关联索引
+#[command]
+pub fn get_association_index(rebuild: bool) -> Result<AssociationIndex, String> {
+    let service = AssociationService::new();
+    service.get_index(rebuild)
+}
+
+/// 查找声明了指定扩展名的应用（如 "pdf"）
+#[command]
+pub fn find_apps_for_extension(extension: &str) -> Result<AssociationLookup, String> {
+    let service = AssociationService::new();
+    service.find_apps_for_extension(extension)
+}
+
+/// 查找注册了指定 URL 协议的应用（如 "zoommtg"）
+#[command]
+pub fn find_apps_for_url_scheme(scheme: &str) -> Result<AssociationLookup, String> {
+    let service = AssociationService::new();
+    service.find_apps_for_url_scheme(scheme)
+}
+
+/// 获取被多个应用同时声明的文件类型与 URL 协议
+#[command]
+pub fn get_association_conflicts() -> Result<Vec<AssociationLookup>, String> {
+    let service = AssociationService::new();
+    service.get_conflicts()
+}
+
 /// 卸载应用
 #[command]
 pub fn uninstall_app(app_path: &str, remove_residuals: bool) -> Result<UninstallResult, String> {
@@ -2,7 +2,11 @@
 
 use tauri::command;
 use crate::services::cleaner_service::CleanerService;
+use crate::services::homebrew_service::HomebrewService;
+use crate::services::developer_storage_service::DeveloperStorageService;
 use crate::models::cleaner::{CleanPlanPreview, CleanResult, CleanItem};
+use crate::models::homebrew::HomebrewCleanupReport;
+use crate::models::developer::DeveloperStorageReport;
 
 /// 预览清理计划
 #[command]
@@ -16,4 +20,18 @@ pub fn preview_clean_plan(clean_types: Vec<String>) -> Result<CleanPlanPreview,
 pub fn execute_clean(items: Vec<CleanItem>) -> Result<CleanResult, String> {
     let service = CleanerService::new();
     service.execute_clean(items)
+}
+
+/// 分析 Homebrew 旧版本和下载缓存
+#[command]
+pub fn analyze_homebrew_cleanup() -> Result<HomebrewCleanupReport, String> {
+    let service = HomebrewService::new();
+    Ok(service.analyze())
+}
+
+/// 分析 Xcode / iOS 开发者存储占用
+#[command]
+pub fn analyze_developer_storage() -> Result<DeveloperStorageReport, String> {
+    let service = DeveloperStorageService::new();
+    Ok(service.analyze())
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+//! 代码签名校验相关命令
+
+use tauri::command;
+use crate::services::codesign_service::CodeSignService;
+use crate::models::codesign::SignatureVerification;
+
+/// 校验应用包资源是否与签名一致
+#[command]
+pub fn verify_app_signature(app_path: &str) -> Result<SignatureVerification, String> {
+    let service = CodeSignService::new();
+    service.verify_bundle(app_path)
+}
@@ -0,0 +1,63 @@
+//! 应用副本管理相关命令
+
+use tauri::command;
+use crate::services::duplicate_service::DuplicateService;
+use crate::services::duplicate_catalog_service::DuplicateCatalogService;
+use crate::models::app::{DuplicateResult, UninstallResult};
+use crate::models::duplicate::{DuplicateCatalogEntry, DuplicateStatus};
+
+/// 列出已创建的应用副本
+#[command]
+pub fn list_duplicates() -> Vec<DuplicateStatus> {
+    let service = DuplicateService::new();
+    service.list_duplicates()
+}
+
+/// 删除副本及其沙盒容器和偏好设置
+#[command]
+pub fn remove_duplicate(id: i64) -> Result<UninstallResult, String> {
+    let service = DuplicateService::new();
+    service.remove_duplicate(id)
+}
+
+/// 列出源应用已更新的过期副本
+#[command]
+pub fn get_stale_duplicates() -> Vec<DuplicateStatus> {
+    let service = DuplicateService::new();
+    service.get_stale_duplicates()
+}
+
+/// 用源应用的当前版本更新副本（保留标识符和数据）
+#[command]
+pub fn update_duplicate(id: i64) -> DuplicateResult {
+    let service = DuplicateService::new();
+    service.update_duplicate(id)
+}
+
+/// 更新所有过期副本
+#[command]
+pub fn update_stale_duplicates() -> Vec<DuplicateResult> {
+    let service = DuplicateService::new();
+    service.update_stale_duplicates()
+}
+
+/// 获取可多开应用目录（内置目录与用户目录合并后的结果）
+#[command]
+pub fn get_duplicate_catalog() -> Result<Vec<DuplicateCatalogEntry>, String> {
+    let service = DuplicateCatalogService::new();
+    service.load_catalog()
+}
+
+/// 获取应用在目录中的多开建议
+#[command]
+pub fn get_duplicate_catalog_entry(bundle_id: &str) -> Option<DuplicateCatalogEntry> {
+    let service = DuplicateCatalogService::new();
+    service.find(bundle_id)
+}
+
+/// 获取用户目录文件路径
+#[command]
+pub fn get_user_catalog_path() -> String {
+    let service = DuplicateCatalogService::new();
+    service.user_catalog_path()
+}
\ No newline at end of file
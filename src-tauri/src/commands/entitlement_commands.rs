@@ -0,0 +1,19 @@
+//! 应用权限审计相关命令
+
+use tauri::command;
+use crate::services::entitlement_service::EntitlementService;
+use crate::models::entitlements::{AppCapabilities, CapabilityReport};
+
+/// 获取单个应用的 entitlements 与能力汇总
+#[command]
+pub fn get_app_capabilities(app_path: &str) -> Result<AppCapabilities, String> {
+    let service = EntitlementService::new();
+    service.get_capabilities(app_path)
+}
+
+/// 审计所有已安装应用的权限
+#[command]
+pub fn audit_app_capabilities() -> Result<CapabilityReport, String> {
+    let service = EntitlementService::new();
+    service.analyze_installed_apps()
+}
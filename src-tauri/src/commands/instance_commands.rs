@@ -0,0 +1,33 @@
+//! Mole 启动实例相关命令
+
+use tauri::command;
+use crate::services::instance_tracker_service::InstanceTrackerService;
+use crate::models::instance::LaunchedInstance;
+
+/// 列出 Mole 启动过的实例（运行中的实例附带实时 CPU 和内存）
+#[command]
+pub fn list_launched_instances() -> Vec<LaunchedInstance> {
+    let service = InstanceTrackerService::new();
+    service.list_launched_instances()
+}
+
+/// 停止实例
+#[command]
+pub fn stop_instance(id: u64) -> Result<LaunchedInstance, String> {
+    let service = InstanceTrackerService::new();
+    service.stop_instance(id)
+}
+
+/// 以相同的命令重启实例
+#[command]
+pub fn restart_instance(id: u64) -> Result<LaunchedInstance, String> {
+    let service = InstanceTrackerService::new();
+    service.restart_instance(id)
+}
+
+/// 清除已退出的实例记录
+#[command]
+pub fn clear_exited_instances() -> u32 {
+    let service = InstanceTrackerService::new();
+    service.clear_exited_instances()
+}
\ No newline at end of file
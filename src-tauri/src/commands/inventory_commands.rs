@@ -0,0 +1,59 @@
+//! 应用清单导出与对比相关命令
+
+use tauri::command;
+use crate::services::inventory_service::{self, InventoryService};
+use crate::models::inventory::{Inventory, InventoryDiff, InventorySnapshot};
+
+/// 导出本机应用清单，格式为 "json" 或 "csv"
+#[command]
+pub fn export_inventory(format: &str) -> Result<String, String> {
+    let service = InventoryService::new();
+    service.export_inventory(format)
+}
+
+/// 读取导入的清单文件内容（JSON 或 CSV）
+#[command]
+pub fn import_inventory(content: &str) -> Result<Inventory, String> {
+    inventory_service::parse_inventory(content)
+}
+
+/// 对比两份清单：A 中缺少、B 中多出以及版本不同的应用
+#[command]
+pub fn diff_inventory(a: Inventory, b: Inventory) -> InventoryDiff {
+    inventory_service::diff_inventory(&a, &b)
+}
+
+/// 保存本机当前清单为快照
+#[command]
+pub fn save_inventory_snapshot() -> Result<InventorySnapshot, String> {
+    let service = InventoryService::new();
+    service.save_snapshot()
+}
+
+/// 列出已保存的清单快照
+#[command]
+pub fn list_inventory_snapshots() -> Vec<InventorySnapshot> {
+    let service = InventoryService::new();
+    service.list_snapshots()
+}
+
+/// 读取清单快照
+#[command]
+pub fn get_inventory_snapshot(id: i64) -> Result<Inventory, String> {
+    let service = InventoryService::new();
+    service.get_snapshot(id)
+}
+
+/// 对比指定天数前的快照与本机当前清单
+#[command]
+pub fn diff_inventory_since(days: u32) -> Result<InventoryDiff, String> {
+    let service = InventoryService::new();
+    service.diff_since(days)
+}
+
+/// 删除清单快照
+#[command]
+pub fn delete_inventory_snapshot(id: i64) -> Result<bool, String> {
+    let service = InventoryService::new();
+    service.delete_snapshot(id)
+}
@@ -0,0 +1,55 @@
+//! 应用多开启动配置相关命令
+
+use std::collections::BTreeMap;
+use tauri::command;
+use crate::services::launch_profile_service::LaunchProfileService;
+use crate::models::instance::LaunchedInstance;
+use crate::models::launch_profile::LaunchProfile;
+
+/// 列出启动配置，指定应用路径时只返回该应用的配置
+#[command]
+pub fn list_launch_profiles(app_path: Option<String>) -> Vec<LaunchProfile> {
+    let service = LaunchProfileService::new();
+    service.list_profiles(app_path.as_deref())
+}
+
+/// 为应用创建启动配置
+#[command]
+pub fn create_launch_profile(
+    app_path: &str,
+    name: &str,
+    strategy: Option<String>,
+    extra_args: Option<Vec<String>>,
+    env: Option<BTreeMap<String, String>>,
+) -> Result<LaunchProfile, String> {
+    let service = LaunchProfileService::new();
+    service.create_profile(app_path, name, strategy, extra_args.unwrap_or_default(), env.unwrap_or_default())
+}
+
+/// 删除启动配置
+#[command]
+pub fn delete_launch_profile(id: &str, remove_data: bool) -> Result<bool, String> {
+    let service = LaunchProfileService::new();
+    service.delete_profile(id, remove_data)
+}
+
+/// 以指定配置启动应用实例
+#[command]
+pub fn launch_profile(id: &str) -> Result<LaunchedInstance, String> {
+    let service = LaunchProfileService::new();
+    service.launch_profile(id)
+}
+
+/// 获取配置正在运行的实例
+#[command]
+pub fn get_profile_instances(id: &str) -> Vec<LaunchedInstance> {
+    let service = LaunchProfileService::new();
+    service.get_profile_instances(id)
+}
+
+/// 停止配置的所有实例
+#[command]
+pub fn stop_profile(id: &str) -> u32 {
+    let service = LaunchProfileService::new();
+    service.stop_profile(id)
+}
\ No newline at end of file
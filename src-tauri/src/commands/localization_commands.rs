@@ -0,0 +1,19 @@
+//! 应用本地化资源相关命令
+
+use tauri::command;
+use crate::services::localization_service::LocalizationService;
+use crate::models::localization::{LocalizationRemovalPlan, LocalizationReport};
+
+/// 分析应用的本地化目录
+#[command]
+pub fn analyze_app_localizations(app_paths: Vec<String>) -> LocalizationReport {
+    let service = LocalizationService::new();
+    service.analyze_apps(&app_paths)
+}
+
+/// 预览未使用本地化的移除计划（通过 execute_clean 执行）
+#[command]
+pub fn preview_localization_removal(app_paths: Vec<String>) -> LocalizationRemovalPlan {
+    let service = LocalizationService::new();
+    service.plan_removal(&app_paths)
+}
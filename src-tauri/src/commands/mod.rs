@@ -10,6 +10,18 @@ pub mod cleaner_commands;
 pub mod app_commands;
 pub mod settings_commands;
 pub mod search_history_commands;
+pub mod receipt_commands;
+pub mod thin_commands;
+pub mod localization_commands;
+pub mod runtime_commands;
+pub mod codesign_commands;
+pub mod entitlement_commands;
+pub mod usage_commands;
+pub mod update_commands;
+pub mod inventory_commands;
+pub mod duplicate_commands;
+pub mod launch_profile_commands;
+pub mod instance_commands;
 
 // 为了避免未使用导入警告，我们只在需要的地方使用这些模块
 pub use system_commands::*;
@@ -18,4 +30,16 @@ pub use disk_commands::*;
 pub use cleaner_commands::*;
 pub use app_commands::*;
 pub use settings_commands::*;
-pub use search_history_commands::*;
\ No newline at end of file
+pub use search_history_commands::*;
+pub use receipt_commands::*;
+pub use thin_commands::*;
+pub use localization_commands::*;
+pub use runtime_commands::*;
+pub use codesign_commands::*;
+pub use entitlement_commands::*;
+pub use usage_commands::*;
+pub use update_commands::*;
+pub use inventory_commands::*;
+pub use duplicate_commands::*;
+pub use launch_profile_commands::*;
+pub use instance_commands::*;
\ No newline at end of file
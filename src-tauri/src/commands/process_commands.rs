@@ -1,14 +1,20 @@
 //! 进程管理相关命令
 
-use tauri::command;
-use crate::services::process_service::ProcessService;
-use crate::models::process::ProcessList;
+use tauri::{command, State};
+use crate::services::process_service::{build_process_tree, ProcessService};
+use crate::services::sampler_service::SystemSampler;
+use crate::models::process::{AppProcessGroups, ProcessList, ProcessTree};
 
 /// 获取进程列表
 #[command]
-pub fn get_process_list() -> ProcessList {
-    let service = ProcessService::new();
-    service.get_process_list()
+pub fn get_process_list(sampler: State<'_, SystemSampler>) -> ProcessList {
+    ProcessList { processes: sampler.processes() }
+}
+
+/// 获取进程树
+#[command]
+pub fn get_process_tree(sampler: State<'_, SystemSampler>) -> ProcessTree {
+    build_process_tree(sampler.processes())
 }
 
 /// 结束进程
@@ -16,4 +22,22 @@ pub fn get_process_list() -> ProcessList {
 pub fn kill_process(pid: u32) -> bool {
     let service = ProcessService::new();
     service.kill_process(pid)
+}
+
+/// 按所属应用汇总进程
+#[command]
+pub fn get_app_process_groups(sampler: State<'_, SystemSampler>) -> AppProcessGroups {
+    let service = ProcessService::new();
+    service.group_by_app(&sampler.processes())
+}
+
+/// 获取进程所属应用的图标
+#[command]
+pub fn get_process_icon(pid: u32, sampler: State<'_, SystemSampler>) -> String {
+    let service = ProcessService::new();
+    sampler.processes()
+        .iter()
+        .find(|p| p.pid == pid)
+        .map(|p| service.get_process_icon(p))
+        .unwrap_or_default()
 }
\ No newline at end of file
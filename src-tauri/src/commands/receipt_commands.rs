@@ -0,0 +1,19 @@
+//! 安装包回执相关命令
+
+use tauri::command;
+use crate::services::receipt_service::ReceiptService;
+use crate::models::receipt::{PkgReceipt, PkgReceiptList};
+
+/// 获取安装包回执列表
+#[command]
+pub fn get_pkg_receipts(include_files: bool) -> Result<PkgReceiptList, String> {
+    let service = ReceiptService::new();
+    service.get_receipts(include_files)
+}
+
+/// 获取单个安装包回执（包含完整文件列表）
+#[command]
+pub fn get_pkg_receipt(package_id: &str) -> Result<PkgReceipt, String> {
+    let service = ReceiptService::new();
+    service.get_receipt(package_id)
+}
@@ -0,0 +1,12 @@
+//! 内嵌运行时检测相关命令
+
+use tauri::command;
+use crate::services::runtime_service::RuntimeService;
+use crate::models::runtime::RuntimeReport;
+
+/// 分析已安装应用中的内嵌运行时（Electron/Chromium/CEF/Qt）
+#[command]
+pub fn analyze_embedded_runtimes() -> Result<RuntimeReport, String> {
+    let service = RuntimeService::new();
+    service.analyze_installed_apps()
+}
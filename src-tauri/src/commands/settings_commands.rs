@@ -1,6 +1,7 @@
 //! 设置相关命令
 
-use tauri::command;
+use tauri::{command, State};
+use crate::services::sampler_service::SystemSampler;
 use crate::services::settings_service::SettingsService;
 use crate::models::settings::Settings;
 
@@ -13,7 +14,9 @@ pub fn get_settings() -> Result<Settings, String> {
 
 /// 更新设置
 #[command]
-pub fn update_settings(settings: Settings) -> Result<bool, String> {
+pub fn update_settings(settings: Settings, sampler: State<'_, SystemSampler>) -> Result<bool, String> {
     let service = SettingsService::new();
-    service.update_settings(&settings)
+    let saved = service.update_settings(&settings)?;
+    sampler.set_interval(settings.auto_refresh_interval);
+    Ok(saved)
 }
\ No newline at end of file
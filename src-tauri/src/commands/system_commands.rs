@@ -1,6 +1,7 @@
 //! 系统信息相关命令
 
-use tauri::command;
+use tauri::{command, State};
+use crate::services::sampler_service::SystemSampler;
 use crate::services::system_service::SystemService;
 use crate::models::system::{SystemInfo, CpuInfo, MemoryInfo, DiskInfo, BatteryInfo, NetworkSpeed, GpuInfo};
 
@@ -13,16 +14,14 @@ pub fn get_system_info() -> SystemInfo {
 
 /// 获取CPU信息
 #[command]
-pub fn get_cpu_info() -> CpuInfo {
-    let service = SystemService::new();
-    service.get_cpu_info()
+pub fn get_cpu_info(sampler: State<'_, SystemSampler>) -> CpuInfo {
+    sampler.cpu()
 }
 
 /// 获取内存信息
 #[command]
-pub fn get_memory_info() -> MemoryInfo {
-    let service = SystemService::new();
-    service.get_memory_info()
+pub fn get_memory_info(sampler: State<'_, SystemSampler>) -> MemoryInfo {
+    sampler.memory()
 }
 
 /// 获取磁盘信息
@@ -48,7 +47,9 @@ pub fn get_gpu_info() -> GpuInfo {
 
 /// 获取网络速度信息
 #[command]
-pub fn get_network_speed() -> NetworkSpeed {
+pub fn get_network_speed(sampler: State<'_, SystemSampler>) -> NetworkSpeed {
     let service = SystemService::new();
-    service.get_network_speed()
+    let mut speed = sampler.network();
+    service.add_network_details(&mut speed);
+    speed
 }
\ No newline at end of file
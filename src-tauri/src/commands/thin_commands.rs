@@ -0,0 +1,40 @@
+//! 通用二进制瘦身相关命令
+
+use tauri::command;
+use crate::services::thin_service::ThinService;
+use crate::models::thin::{ThinBackup, ThinPlan, ThinResult};
+
+/// 预览瘦身计划（allow_resign 为 true 时允许瘦身已签名的应用并临时重新签名）
+#[command]
+pub fn preview_thin_apps(app_paths: Vec<String>, target_arch: Option<String>, allow_resign: Option<bool>) -> ThinPlan {
+    let service = ThinService::new();
+    service.plan_thinning(&app_paths, target_arch.as_deref(), allow_resign.unwrap_or(false))
+}
+
+/// 执行瘦身
+#[command]
+pub fn thin_apps(app_paths: Vec<String>, target_arch: Option<String>, allow_resign: Option<bool>) -> Vec<ThinResult> {
+    let service = ThinService::new();
+    service.thin_apps(&app_paths, target_arch.as_deref(), allow_resign.unwrap_or(false))
+}
+
+/// 获取瘦身备份列表
+#[command]
+pub fn list_thin_backups() -> Vec<ThinBackup> {
+    let service = ThinService::new();
+    service.list_backups()
+}
+
+/// 从备份还原应用
+#[command]
+pub fn restore_thin_backup(backup_id: &str) -> Result<ThinResult, String> {
+    let service = ThinService::new();
+    service.restore_backup(backup_id)
+}
+
+/// 删除瘦身备份
+#[command]
+pub fn delete_thin_backup(backup_id: &str) -> Result<bool, String> {
+    let service = ThinService::new();
+    service.delete_backup(backup_id)
+}
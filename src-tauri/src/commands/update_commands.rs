@@ -0,0 +1,19 @@
+//! 应用更新检查相关命令
+
+use tauri::command;
+use crate::services::update_service::UpdateService;
+use crate::models::updates::{AppUpdateInfo, UpdateCheckReport};
+
+/// 检查所有声明了 Sparkle appcast 的已安装应用是否有更新
+#[command]
+pub fn check_app_updates(force: bool) -> Result<UpdateCheckReport, String> {
+    let service = UpdateService::new();
+    service.check_installed_apps(force)
+}
+
+/// 检查单个应用是否有更新
+#[command]
+pub fn check_app_update(app_path: &str) -> Result<AppUpdateInfo, String> {
+    let service = UpdateService::new();
+    service.check_app(app_path)
+}
@@ -0,0 +1,21 @@
+//! 应用使用记录相关命令
+
+use tauri::command;
+use crate::services::app_service::AppService;
+use crate::services::usage_service::UsageService;
+use crate::models::app::InstalledApps;
+use crate::models::usage::AppUsage;
+
+/// 获取超过指定天数未使用的应用
+#[command]
+pub fn get_unused_apps(days: u32) -> Result<InstalledApps, String> {
+    let service = AppService::new();
+    service.get_unused_apps(days)
+}
+
+/// 获取单个应用的使用记录
+#[command]
+pub fn get_app_usage(app_path: &str) -> Option<AppUsage> {
+    let service = UsageService::new();
+    service.get_usage(app_path)
+}
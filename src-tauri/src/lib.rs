@@ -8,6 +8,8 @@ mod commands;
 // 重导出所有命令，以便在前端调用
 pub use commands::*;
 
+use tauri::Manager;
+
 #[cfg(target_os = "macos")]
 use tauri::{TitleBarStyle, WebviewUrl, WebviewWindowBuilder};
 
@@ -92,6 +94,16 @@ pub fn run() {
                 // 窗口已由 tauri.conf.json 创建
             }
 
+            // 后台采样进程、CPU、内存和网络，间隔使用设置中的自动刷新间隔
+            let refresh_interval = services::settings_service::SettingsService::new()
+                .get_settings()
+                .map(|s| s.auto_refresh_interval)
+                .unwrap_or(500);
+            app.manage(services::sampler_service::SystemSampler::start(refresh_interval));
+
+            // 后台采样应用使用记录
+            services::usage_service::start_sampler(std::time::Duration::from_secs(60));
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -108,6 +120,8 @@ pub fn run() {
             
             // 进程管理命令
             get_process_list,
+            get_process_tree,
+            get_app_process_groups,
             kill_process,
             get_process_icon,
             
@@ -121,12 +135,23 @@ pub fn run() {
             // 系统清理命令
             preview_clean_plan,
             execute_clean,
+            analyze_homebrew_cleanup,
+            analyze_developer_storage,
             
             // 应用管理命令
             get_installed_apps,
+            get_package_apps,
+            filter_installed_apps,
+            get_app_arch_info,
             get_duplicatable_apps,
             get_app_size,
+            get_app_size_breakdown,
             get_app_icon,
+            get_app_associations,
+            get_association_index,
+            find_apps_for_extension,
+            find_apps_for_url_scheme,
+            get_association_conflicts,
             uninstall_app,
             get_app_related_files,
             force_uninstall_app,
@@ -134,6 +159,73 @@ pub fn run() {
             quick_duplicate_app,
             create_duplicate_app,
             
+            // 安装包回执命令
+            get_pkg_receipts,
+            get_pkg_receipt,
+            
+            // 应用瘦身命令
+            preview_thin_apps,
+            thin_apps,
+            list_thin_backups,
+            restore_thin_backup,
+            delete_thin_backup,
+            
+            // 本地化资源命令
+            analyze_app_localizations,
+            preview_localization_removal,
+            
+            // 内嵌运行时命令
+            analyze_embedded_runtimes,
+            
+            // 代码签名校验命令
+            verify_app_signature,
+            
+            // 权限审计命令
+            get_app_capabilities,
+            audit_app_capabilities,
+            
+            // 应用使用记录命令
+            get_unused_apps,
+            get_app_usage,
+            
+            // 应用更新检查命令
+            check_app_updates,
+            check_app_update,
+            
+            // 应用清单命令
+            export_inventory,
+            import_inventory,
+            diff_inventory,
+            save_inventory_snapshot,
+            list_inventory_snapshots,
+            get_inventory_snapshot,
+            diff_inventory_since,
+            delete_inventory_snapshot,
+            
+            // 应用副本管理命令
+            list_duplicates,
+            remove_duplicate,
+            get_stale_duplicates,
+            update_duplicate,
+            update_stale_duplicates,
+            get_duplicate_catalog,
+            get_duplicate_catalog_entry,
+            get_user_catalog_path,
+            
+            // 多开启动配置命令
+            list_launch_profiles,
+            create_launch_profile,
+            delete_launch_profile,
+            launch_profile,
+            get_profile_instances,
+            stop_profile,
+            
+            // 实例跟踪命令
+            list_launched_instances,
+            stop_instance,
+            restart_instance,
+            clear_exited_instances,
+            
             // 设置命令
             get_settings,
             update_settings,
@@ -19,6 +19,39 @@ pub struct AppInfo {
     pub size: u64,
     /// 是否为双开副本
     pub is_duplicate: bool,
+    /// 主程序支持的架构（如 arm64, x86_64）
+    pub architectures: Vec<String>,
+    /// 架构类型: "universal", "apple_silicon", "intel", "unknown"
+    pub arch_kind: String,
+    /// 最低系统版本
+    pub min_os_version: String,
+    /// 是否正在通过 Rosetta 转译运行
+    pub is_translated: bool,
+    /// 最后使用时间（秒），未知时为 None
+    pub last_used: Option<u64>,
+    /// 观察到的启动次数
+    pub launch_count: u32,
+    /// 安装来源: "manual", "app_store", "homebrew_cask", "homebrew_formula",
+    /// "flatpak", "snap", "desktop"（Linux 系统包管理器安装的桌面应用）
+    pub source: String,
+    /// 卸载方式: "trash", "brew_cask", "brew_formula", "flatpak", "snap", "package_manager"
+    pub uninstall_strategy: String,
+}
+
+/// 应用筛选条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppFilter {
+    /// 架构类型: "universal", "apple_silicon", "intel"
+    pub arch_kind: Option<String>,
+    /// 只显示正在转译运行的应用
+    pub translated_only: bool,
+    /// 只显示具备指定能力的应用，如 "camera", "full_disk_access", "unsandboxed_file_access"
+    pub capability: Option<String>,
+    /// 只显示超过指定天数未使用的应用（使用时间未知的应用不包含在内）
+    pub unused_days: Option<u32>,
+    /// 安装来源
+    pub source: Option<String>,
 }
 
 /// 已安装应用列表
@@ -71,6 +104,12 @@ pub struct AppRelatedFiles {
     pub sandbox_files: Vec<AppRelatedFile>,
     /// 其他文件
     pub other_files: Vec<AppRelatedFile>,
+    /// 安装包（.pkg）释放到应用包外的文件
+    pub pkg_files: Vec<AppRelatedFile>,
+    /// 应用包内本地化目录（*.lproj）总大小，已包含在应用本体大小中
+    pub localization_size: u64,
+    /// 其中未使用语言的大小
+    pub unused_localization_size: u64,
 }
 
 /// 双开结果
@@ -0,0 +1,85 @@
+//! 文件类型与 URL 协议关联数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 应用声明可打开的文档类型（CFBundleDocumentTypes）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentTypeClaim {
+    /// 类型名称
+    pub name: String,
+    /// 文件扩展名（小写，不含点）
+    pub extensions: Vec<String>,
+    /// 统一类型标识符（LSItemContentTypes）
+    pub content_types: Vec<String>,
+    /// 角色: "Editor", "Viewer", "Shell", "None"
+    pub role: String,
+    /// 处理优先级: "Owner", "Default", "Alternate", "None"
+    pub rank: String,
+}
+
+/// 应用导出的类型声明（UTExportedTypeDeclarations）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedTypeDeclaration {
+    /// 统一类型标识符
+    pub identifier: String,
+    /// 类型描述
+    pub description: String,
+    /// 文件扩展名（小写，不含点）
+    pub extensions: Vec<String>,
+    /// 父类型
+    pub conforms_to: Vec<String>,
+}
+
+/// 单个应用的文件类型与 URL 协议关联
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppAssociations {
+    /// 应用路径
+    pub app_path: String,
+    /// 应用名称
+    pub app_name: String,
+    /// 应用标识符
+    pub bundle_id: String,
+    /// Info.plist 修改时间（秒），用于判断缓存是否过期
+    pub info_modified: u64,
+    /// 可打开的文档类型
+    pub document_types: Vec<DocumentTypeClaim>,
+    /// 导出的类型声明
+    pub exported_types: Vec<ExportedTypeDeclaration>,
+    /// 注册的 URL 协议（小写）
+    pub url_schemes: Vec<String>,
+}
+
+/// 声明处理某个类型或协议的应用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssociationHandler {
+    /// 应用路径
+    pub app_path: String,
+    /// 应用名称
+    pub app_name: String,
+    /// 应用标识符
+    pub bundle_id: String,
+    /// 角色（URL 协议为空）
+    pub role: String,
+    /// 处理优先级（URL 协议为空）
+    pub rank: String,
+}
+
+/// 某个扩展名或 URL 协议的处理应用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssociationLookup {
+    /// 扩展名或协议（小写）
+    pub key: String,
+    /// 类型: "extension", "url_scheme"
+    pub kind: String,
+    /// 处理该类型的应用
+    pub handlers: Vec<AssociationHandler>,
+}
+
+/// 文件类型与 URL 协议关联索引
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssociationIndex {
+    /// 各应用的关联
+    pub apps: Vec<AppAssociations>,
+    /// 索引构建时间（秒）
+    pub built_at: u64,
+}
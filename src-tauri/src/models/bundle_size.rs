@@ -0,0 +1,64 @@
+//! 应用包大小构成数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 应用包中的单个组成部分（框架、插件或辅助应用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizeComponent {
+    /// 名称（如 Electron Framework.framework）
+    pub name: String,
+    /// 完整路径
+    pub path: String,
+    /// 大小(bytes)
+    pub size: u64,
+    /// 文件数量
+    pub file_count: u64,
+}
+
+/// 内容相同的一组文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateFileGroup {
+    /// 单个文件大小(bytes)
+    pub size: u64,
+    /// 文件路径
+    pub paths: Vec<String>,
+}
+
+/// 应用包大小构成
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSizeBreakdown {
+    /// 应用路径
+    pub app_path: String,
+    /// 实际占用大小(bytes)，硬链接只计算一次
+    pub total_size: u64,
+    /// 文件数量
+    pub file_count: u64,
+    /// 主程序（Contents/MacOS）大小
+    pub executables_size: u64,
+    /// 框架总大小
+    pub frameworks_size: u64,
+    /// 各框架大小
+    pub frameworks: Vec<SizeComponent>,
+    /// 资源文件大小（不含本地化）
+    pub resources_size: u64,
+    /// 本地化目录（*.lproj）大小
+    pub localizations_size: u64,
+    /// 其中未使用语言的大小
+    pub unused_localizations_size: u64,
+    /// 插件总大小（PlugIns、XPCServices、Library 等）
+    pub plugins_size: u64,
+    /// 各插件大小
+    pub plugins: Vec<SizeComponent>,
+    /// 辅助应用总大小
+    pub helper_apps_size: u64,
+    /// 各辅助应用大小
+    pub helper_apps: Vec<SizeComponent>,
+    /// 其他文件大小（签名、Info.plist 等）
+    pub other_size: u64,
+    /// 通过硬链接共享、未重复占用的大小(bytes)
+    pub hardlinked_size: u64,
+    /// 内容相同的文件可节省的大小(bytes)
+    pub duplicate_size: u64,
+    /// 内容相同的文件组
+    pub duplicate_groups: Vec<DuplicateFileGroup>,
+}
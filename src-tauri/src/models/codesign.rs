@@ -0,0 +1,26 @@
+//! 代码签名资源校验数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 应用包签名资源校验结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureVerification {
+    /// 应用包路径
+    pub bundle_path: String,
+    /// 是否存在签名资源清单（_CodeSignature/CodeResources）
+    pub signed: bool,
+    /// 资源是否与签名一致
+    pub valid: bool,
+    /// 已校验的资源数量
+    pub checked_count: u32,
+    /// 已校验的内嵌代码包数量
+    pub nested_count: u32,
+    /// 签名后被删除的资源（相对应用包的路径）
+    pub missing: Vec<String>,
+    /// 签名后新增的资源
+    pub added: Vec<String>,
+    /// 签名后内容被修改的资源
+    pub altered: Vec<String>,
+    /// 无法校验的内嵌代码包及原因
+    pub unverified: Vec<String>,
+}
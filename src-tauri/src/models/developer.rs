@@ -0,0 +1,69 @@
+//! Xcode / iOS 开发者存储分析数据模型
+
+use serde::{Deserialize, Serialize};
+use crate::models::cleaner::CleanItem;
+
+/// 开发者存储分类汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeveloperStorageCategory {
+    /// 分类标识: "derived_data", "archives", "device_support", "simulators",
+    /// "simulator_runtimes", "simulator_caches", "previews"
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 占用大小(bytes)
+    pub size: u64,
+    /// 条目数量
+    pub item_count: usize,
+}
+
+/// 模拟器设备
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatorDevice {
+    /// 设备 UDID
+    pub udid: String,
+    /// 设备名称
+    pub name: String,
+    /// 设备类型标识（如 com.apple.CoreSimulator.SimDeviceType.iPhone-15）
+    pub device_type: String,
+    /// 运行时标识（如 com.apple.CoreSimulator.SimRuntime.iOS-17-2）
+    pub runtime: String,
+    /// 运行时显示名称（如 iOS 17.2）
+    pub runtime_name: String,
+    /// 运行时是否仍已安装
+    pub runtime_installed: bool,
+    /// 是否正在运行
+    pub booted: bool,
+    /// 设备目录
+    pub path: String,
+    /// 占用大小(bytes)
+    pub size: u64,
+}
+
+/// 按系统版本分组的 DeviceSupport 目录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceSupportGroup {
+    /// 平台: "iOS", "watchOS", "tvOS", "visionOS"
+    pub platform: String,
+    /// 系统版本（如 17.2）
+    pub os_version: String,
+    /// 包含的目录（同一版本可能有多个构建号或设备型号）
+    pub folders: Vec<String>,
+    /// 占用大小(bytes)
+    pub size: u64,
+}
+
+/// 开发者存储分析报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeveloperStorageReport {
+    /// 分类汇总
+    pub categories: Vec<DeveloperStorageCategory>,
+    /// 模拟器设备
+    pub simulators: Vec<SimulatorDevice>,
+    /// DeviceSupport 分组
+    pub device_support: Vec<DeviceSupportGroup>,
+    /// 清理项
+    pub items: Vec<CleanItem>,
+    /// 可清理总大小(bytes)
+    pub total_size: u64,
+}
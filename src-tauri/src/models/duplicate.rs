@@ -0,0 +1,82 @@
+//! 应用副本登记数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 已登记的应用副本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateRecord {
+    /// 登记 ID
+    pub id: i64,
+    /// 源应用路径
+    pub source_path: String,
+    /// 源应用标识符
+    pub source_bundle_id: String,
+    /// 创建副本时源应用的显示版本
+    pub source_version: String,
+    /// 创建副本时源应用的构建版本
+    pub source_build: String,
+    /// 副本路径
+    pub copy_path: String,
+    /// 副本标识符
+    pub bundle_id: String,
+    /// 副本显示名称
+    pub app_name: String,
+    /// 副本图标 emoji
+    pub icon_emoji: Option<String>,
+    /// 副本编号
+    pub copy_number: u32,
+    /// 创建时间（秒）
+    pub created_at: u64,
+    /// 副本的数据目录（沙盒容器、偏好设置等）
+    pub data_containers: Vec<String>,
+}
+
+/// 副本当前状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateStatus {
+    /// 登记信息
+    pub duplicate: DuplicateRecord,
+    /// 副本是否仍存在
+    pub copy_exists: bool,
+    /// 源应用是否仍存在
+    pub source_exists: bool,
+    /// 源应用当前显示版本
+    pub current_source_version: Option<String>,
+    /// 源应用当前构建版本
+    pub current_source_build: Option<String>,
+    /// 源应用是否已更新（副本过期）
+    pub stale: bool,
+    /// 数据目录占用大小(bytes)
+    pub data_size: u64,
+}
+
+/// 可多开应用目录条目（按应用标识符索引）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCatalogEntry {
+    /// 应用标识符（目录文件中为键名）
+    #[serde(default)]
+    pub bundle_id: String,
+    /// 应用名称
+    pub name: String,
+    /// 推荐的多开方式: "bundle_copy"（复制应用包）, "profile"（独立数据目录的启动配置）
+    pub strategy: String,
+    /// 复制后是否需要重新签名
+    #[serde(default)]
+    pub requires_resign: bool,
+    /// 启动配置的隔离方式（profile 方式）: "user_data_dir", "firefox_profile", "home"，为空时按应用结构自动选择
+    #[serde(default)]
+    pub isolation: Option<String>,
+    /// 已知问题
+    #[serde(default)]
+    pub caveats: Vec<String>,
+    /// 是否启用（用户目录中设为 false 可屏蔽内置条目）
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 条目来源: "builtin", "user"
+    #[serde(default)]
+    pub source: String,
+}
+
+fn default_enabled() -> bool {
+    true
+}
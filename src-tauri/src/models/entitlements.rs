@@ -0,0 +1,49 @@
+//! 应用权限与沙盒能力数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 应用声明或获得的单项能力
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppCapability {
+    /// 能力标识: "camera", "microphone", "full_disk_access", "network_server",
+    /// "unsandboxed_file_access" 等
+    pub id: String,
+    /// 能力名称
+    pub name: String,
+    /// 来源: "entitlement", "usage_description", "tcc"
+    pub sources: Vec<String>,
+    /// Info.plist 中的用途说明
+    pub usage_description: Option<String>,
+}
+
+/// 应用权限汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppCapabilities {
+    /// 应用路径
+    pub app_path: String,
+    /// 应用名称
+    pub app_name: String,
+    /// 应用标识符
+    pub bundle_id: String,
+    /// 主程序是否带有代码签名
+    pub signed: bool,
+    /// 是否启用 App Sandbox
+    pub sandboxed: bool,
+    /// 是否启用 Hardened Runtime
+    pub hardened_runtime: bool,
+    /// 签名中嵌入的完整 entitlements
+    pub entitlements: serde_json::Value,
+    /// 能力汇总
+    pub capabilities: Vec<AppCapability>,
+}
+
+/// 已安装应用权限审计报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityReport {
+    /// 各应用权限汇总
+    pub apps: Vec<AppCapabilities>,
+    /// 未启用沙盒的应用数量
+    pub unsandboxed_count: u32,
+    /// 拥有完全磁盘访问权限的应用数量
+    pub full_disk_access_count: u32,
+}
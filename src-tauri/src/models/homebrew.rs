@@ -0,0 +1,38 @@
+//! Homebrew 清理分析数据模型
+
+use serde::{Deserialize, Serialize};
+use crate::models::cleaner::CleanItem;
+
+/// 单个 formula 或 cask 的可清理内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HomebrewPackageCleanup {
+    /// 名称（formula 名或 cask token）
+    pub name: String,
+    /// 类型: "formula", "cask"
+    pub kind: String,
+    /// Homebrew 前缀
+    pub prefix: String,
+    /// 当前使用的版本
+    pub current_version: Option<String>,
+    /// 可清理的旧版本
+    pub old_versions: Vec<String>,
+    /// 旧版本占用大小(bytes)
+    pub old_versions_size: u64,
+    /// 下载缓存占用大小(bytes)
+    pub cache_size: u64,
+}
+
+/// Homebrew 清理分析报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HomebrewCleanupReport {
+    /// 扫描到的 Homebrew 前缀
+    pub prefixes: Vec<String>,
+    /// 下载缓存目录
+    pub cache_dir: String,
+    /// 按 formula/cask 汇总（可清理大小降序）
+    pub packages: Vec<HomebrewPackageCleanup>,
+    /// 清理项
+    pub items: Vec<CleanItem>,
+    /// 可清理总大小(bytes)
+    pub total_size: u64,
+}
@@ -0,0 +1,34 @@
+//! Mole 启动的应用实例数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 由 Mole 启动的应用实例
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchedInstance {
+    /// 实例 ID（本次运行内唯一）
+    pub id: u64,
+    /// 进程 ID
+    pub pid: u32,
+    /// 应用路径
+    pub app_path: String,
+    /// 启动配置 ID（快速多开时为空）
+    pub profile_id: Option<String>,
+    /// 可执行文件
+    pub executable: String,
+    /// 启动参数
+    pub args: Vec<String>,
+    /// 启动时间（秒）
+    pub started_at: u64,
+    /// 状态: "running", "exited", "crashed", "stopped"
+    pub status: String,
+    /// 退出码
+    pub exit_code: Option<i32>,
+    /// 导致退出的信号
+    pub exit_signal: Option<i32>,
+    /// 退出时间（秒）
+    pub exited_at: Option<u64>,
+    /// CPU 使用率（0-1，运行中时有效）
+    pub cpu_usage: f32,
+    /// 内存占用(bytes)
+    pub memory: u64,
+}
@@ -0,0 +1,82 @@
+//! 应用清单导出与对比数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 清单中的单个应用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryEntry {
+    /// 应用名称
+    pub name: String,
+    /// 应用标识符
+    pub bundle_id: String,
+    /// 显示版本（CFBundleShortVersionString）
+    pub version: String,
+    /// 构建版本（CFBundleVersion）
+    pub build: String,
+    /// 安装路径
+    pub path: String,
+    /// 主程序支持的架构
+    pub architectures: Vec<String>,
+    /// 架构类型
+    pub arch_kind: String,
+    /// 应用大小(bytes)
+    pub size: u64,
+    /// 签名者（第一个 Authority）
+    pub signer: String,
+    /// 开发者团队 ID
+    pub team_id: String,
+    /// 安装来源: 与 AppInfo.source 相同，手动安装的应用包如来自 .pkg 则为 "pkg"
+    pub source: String,
+}
+
+/// 应用清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Inventory {
+    /// 主机名
+    pub hostname: String,
+    /// 生成时间（秒）
+    pub created_at: u64,
+    /// 应用列表
+    pub apps: Vec<InventoryEntry>,
+}
+
+/// 版本不同的应用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryVersionChange {
+    /// 应用名称
+    pub name: String,
+    /// 应用标识符
+    pub bundle_id: String,
+    /// 清单 A 中的版本
+    pub version_a: String,
+    /// 清单 B 中的版本
+    pub version_b: String,
+    /// 清单 A 中的构建版本
+    pub build_a: String,
+    /// 清单 B 中的构建版本
+    pub build_b: String,
+}
+
+/// 两份清单的差异
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryDiff {
+    /// 清单 A 中有、清单 B 中没有的应用
+    pub missing: Vec<InventoryEntry>,
+    /// 清单 B 中有、清单 A 中没有的应用
+    pub extra: Vec<InventoryEntry>,
+    /// 两份清单中版本不同的应用
+    pub version_changed: Vec<InventoryVersionChange>,
+}
+
+/// 已保存的清单快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventorySnapshot {
+    /// 快照 ID
+    pub id: i64,
+    /// 主机名
+    pub hostname: String,
+    /// 创建时间（秒）
+    pub created_at: u64,
+    /// 应用数量
+    pub app_count: u32,
+}
@@ -0,0 +1,41 @@
+//! 应用多开启动配置数据模型
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+/// 启动配置（每个配置使用独立的数据目录）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchProfile {
+    /// 配置 ID
+    pub id: String,
+    /// 配置名称
+    pub name: String,
+    /// 应用路径
+    pub app_path: String,
+    /// 应用标识符
+    pub bundle_id: String,
+    /// 数据目录
+    pub data_dir: String,
+    /// 隔离方式: "auto"（按应用自动选择）, "user_data_dir", "firefox_profile", "home"
+    pub strategy: String,
+    /// 额外的启动参数
+    pub extra_args: Vec<String>,
+    /// 额外的环境变量
+    pub env: BTreeMap<String, String>,
+    /// 创建时间（秒）
+    pub created_at: u64,
+}
+
+/// 解析后的启动命令
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchCommand {
+    /// 可执行文件
+    pub executable: String,
+    /// 实际使用的隔离方式
+    pub strategy: String,
+    /// 启动参数
+    pub args: Vec<String>,
+    /// 环境变量
+    pub env: BTreeMap<String, String>,
+}
+
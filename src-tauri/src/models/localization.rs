@@ -0,0 +1,62 @@
+//! 应用本地化资源数据模型
+
+use serde::{Deserialize, Serialize};
+use crate::models::cleaner::CleanItem;
+
+/// 单个本地化目录（*.lproj）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LprojInfo {
+    /// 目录名（如 fr.lproj）
+    pub name: String,
+    /// 语言代码（如 fr, zh-Hans）
+    pub language: String,
+    /// 完整路径
+    pub path: String,
+    /// 大小(bytes)
+    pub size: u64,
+    /// 是否为用户使用的语言（或 Base）
+    pub is_used: bool,
+    /// 是否可以移除（应用和所在的嵌套代码包都未签名）
+    pub removable: bool,
+}
+
+/// 单个应用的本地化分析结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppLocalizations {
+    /// 应用路径
+    pub app_path: String,
+    /// 应用名称
+    pub app_name: String,
+    /// 本地化目录列表
+    pub localizations: Vec<LprojInfo>,
+    /// 本地化总大小(bytes)
+    pub total_size: u64,
+    /// 未使用语言的大小(bytes)
+    pub unused_size: u64,
+    /// 应用是否已签名
+    pub is_signed: bool,
+    /// 是否允许移除（已签名的应用移除后会破坏签名）
+    pub removal_allowed: bool,
+}
+
+/// 本地化分析汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizationReport {
+    /// 用户首选语言
+    pub preferred_languages: Vec<String>,
+    /// 各应用分析结果
+    pub apps: Vec<AppLocalizations>,
+    /// 未使用语言总大小(bytes)
+    pub total_unused_size: u64,
+}
+
+/// 本地化移除计划
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizationRemovalPlan {
+    /// 可移除项（可直接交给 execute_clean 执行）
+    pub items: Vec<CleanItem>,
+    /// 可回收空间(bytes)
+    pub total_size: u64,
+    /// 因签名保护被跳过的应用路径
+    pub skipped_apps: Vec<String>,
+}
@@ -0,0 +1,35 @@
+//! Mach-O 架构信息数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 内嵌框架的架构信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameworkArch {
+    /// 框架名称
+    pub name: String,
+    /// 框架可执行文件路径
+    pub path: String,
+    /// 支持的架构
+    pub architectures: Vec<String>,
+}
+
+/// 应用架构信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppArchInfo {
+    /// 应用路径
+    pub app_path: String,
+    /// 主可执行文件路径
+    pub executable_path: String,
+    /// 主可执行文件支持的架构
+    pub architectures: Vec<String>,
+    /// 架构类型: "universal", "apple_silicon", "intel", "unknown"
+    pub arch_kind: String,
+    /// 最低系统版本
+    pub min_os_version: String,
+    /// 内嵌框架架构
+    pub frameworks: Vec<FrameworkArch>,
+    /// 是否有进程正在通过 Rosetta 转译运行
+    pub is_translated: bool,
+    /// 正在转译运行的进程 ID
+    pub translated_pids: Vec<u32>,
+}
@@ -10,6 +10,23 @@ pub mod cleaner;
 pub mod app;
 pub mod settings;
 pub mod search_history;
+pub mod receipt;
+pub mod macho;
+pub mod thin;
+pub mod localization;
+pub mod bundle_size;
+pub mod runtime;
+pub mod codesign;
+pub mod entitlements;
+pub mod associations;
+pub mod usage;
+pub mod updates;
+pub mod inventory;
+pub mod homebrew;
+pub mod developer;
+pub mod duplicate;
+pub mod launch_profile;
+pub mod instance;
 
 // 为了避免未使用导入警告，我们只在需要的地方使用这些模块
 // pub use system::*;
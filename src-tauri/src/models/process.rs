@@ -17,6 +17,22 @@ pub struct Process {
     pub status: String,
     /// 启动时间(unix timestamp)
     pub start_time: u64,
+    /// 父进程ID
+    pub parent_pid: Option<u32>,
+    /// 所属用户
+    pub user: String,
+    /// 完整命令行
+    pub command_line: String,
+    /// 可执行文件路径
+    pub exe_path: String,
+    /// 工作目录
+    pub cwd: String,
+    /// 线程数（系统不提供时为空）
+    pub thread_count: Option<u32>,
+    /// 常驻内存(bytes)
+    pub memory: u64,
+    /// 虚拟内存(bytes)
+    pub virtual_memory: u64,
 }
 
 /// 进程列表
@@ -24,4 +40,64 @@ pub struct Process {
 pub struct ProcessList {
     /// 进程列表
     pub processes: Vec<Process>,
-}
\ No newline at end of file
+}
+
+/// 进程树节点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessTreeNode {
+    /// 进程信息
+    pub process: Process,
+    /// 子进程（按子树内存从大到小排序）
+    pub children: Vec<ProcessTreeNode>,
+    /// 子树进程数（包含自身）
+    pub subtree_count: u32,
+    /// 子树 CPU 使用率合计
+    pub subtree_cpu_usage: f32,
+    /// 子树常驻内存合计(bytes)
+    pub subtree_memory: u64,
+}
+
+/// 进程树
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessTree {
+    /// 顶层进程（没有父进程或父进程不可见）
+    pub roots: Vec<ProcessTreeNode>,
+}
+
+/// 按应用包汇总的进程
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppProcessGroup {
+    /// 应用包路径
+    pub app_path: String,
+    /// 应用名称
+    pub app_name: String,
+    /// 应用标识符
+    pub bundle_id: String,
+    /// 应用图标
+    pub icon: String,
+    /// 包含的进程ID（主程序、辅助程序、XPC 服务等）
+    pub pids: Vec<u32>,
+    /// 进程数
+    pub process_count: u32,
+    /// 线程数合计
+    pub thread_count: u32,
+    /// CPU 使用率合计
+    pub cpu_usage: f32,
+    /// 内存使用率合计(0-1)
+    pub memory_usage: f32,
+    /// 常驻内存合计(bytes)
+    pub memory: u64,
+}
+
+/// 按应用包汇总的进程列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppProcessGroups {
+    /// 应用（按内存从大到小排序）
+    pub apps: Vec<AppProcessGroup>,
+    /// 不属于任何应用包的进程数
+    pub other_process_count: u32,
+    /// 不属于任何应用包的进程 CPU 使用率合计
+    pub other_cpu_usage: f32,
+    /// 不属于任何应用包的进程内存合计(bytes)
+    pub other_memory: u64,
+}
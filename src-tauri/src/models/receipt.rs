@@ -0,0 +1,46 @@
+//! 安装包回执（pkg receipt）数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 回执中记录的单个文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptFile {
+    /// 绝对路径
+    pub path: String,
+    /// 文件大小(bytes)，取自 BOM 记录
+    pub size: u64,
+    /// 文件权限位
+    pub mode: u32,
+    /// 文件类型: "file", "directory", "symlink", "device"
+    pub file_type: String,
+}
+
+/// 安装包回执
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PkgReceipt {
+    /// 安装包标识符（如 com.example.pkg.App）
+    pub package_id: String,
+    /// 安装包版本
+    pub version: String,
+    /// 安装时间(unix timestamp)
+    pub install_date: u64,
+    /// 安装前缀路径
+    pub install_prefix: String,
+    /// BOM 文件路径
+    pub bom_path: String,
+    /// 文件数量（不含目录）
+    pub file_count: u64,
+    /// 文件总大小(bytes)
+    pub total_size: u64,
+    /// 该安装包安装的 .app 路径
+    pub apps: Vec<String>,
+    /// 完整文件列表（列表接口中可省略）
+    pub files: Vec<ReceiptFile>,
+}
+
+/// 安装包回执列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PkgReceiptList {
+    /// 回执列表
+    pub receipts: Vec<PkgReceipt>,
+}
@@ -0,0 +1,54 @@
+//! 内嵌运行时（Electron/Chromium/CEF/Qt）数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 应用中内嵌的运行时框架
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddedRuntime {
+    /// 应用路径
+    pub app_path: String,
+    /// 应用名称
+    pub app_name: String,
+    /// 运行时类型: "electron", "chromium", "cef", "nwjs", "qt"
+    pub runtime: String,
+    /// 框架名称
+    pub framework_name: String,
+    /// 框架路径
+    pub framework_path: String,
+    /// 运行时版本
+    pub version: String,
+    /// 框架大小(bytes)
+    pub size: u64,
+    /// 是否为已停止安全更新的版本
+    pub is_outdated: bool,
+    /// 过期说明
+    pub advisory: Option<String>,
+}
+
+/// 同类运行时的汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeGroup {
+    /// 运行时类型
+    pub runtime: String,
+    /// 副本数量
+    pub copies: u32,
+    /// 所有副本总大小(bytes)
+    pub total_size: u64,
+    /// 重复占用的大小(bytes)，即总大小减去最大的一份
+    pub duplicated_size: u64,
+    /// 出现的版本
+    pub versions: Vec<String>,
+}
+
+/// 内嵌运行时分析报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeReport {
+    /// 所有内嵌运行时
+    pub runtimes: Vec<EmbeddedRuntime>,
+    /// 按运行时类型汇总
+    pub groups: Vec<RuntimeGroup>,
+    /// 重复运行时占用的总大小(bytes)
+    pub total_duplicated_size: u64,
+    /// 过期运行时数量
+    pub outdated_count: u32,
+}
@@ -0,0 +1,89 @@
+//! 通用二进制瘦身数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 可瘦身的单个二进制文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThinBinary {
+    /// 文件路径
+    pub path: String,
+    /// 当前包含的架构
+    pub architectures: Vec<String>,
+    /// 保留的架构
+    pub keep_arch: String,
+    /// 原始大小(bytes)
+    pub original_size: u64,
+    /// 瘦身后大小(bytes)
+    pub thinned_size: u64,
+}
+
+/// 单个应用的瘦身计划
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppThinPlan {
+    /// 应用路径
+    pub app_path: String,
+    /// 应用名称
+    pub app_name: String,
+    /// 应用标识符
+    pub identifier: String,
+    /// 可瘦身的二进制文件
+    pub binaries: Vec<ThinBinary>,
+    /// 可回收空间(bytes)
+    pub reclaim_bytes: u64,
+    /// 瘦身后是否需要重新签名
+    pub needs_resign: bool,
+    /// 拒绝瘦身的原因（为空表示可以瘦身）
+    pub refused_reason: Option<String>,
+}
+
+/// 瘦身计划
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThinPlan {
+    /// 保留的目标架构
+    pub target_arch: String,
+    /// 各应用计划
+    pub apps: Vec<AppThinPlan>,
+    /// 可回收总空间(bytes)
+    pub total_reclaim_bytes: u64,
+}
+
+/// 单个应用的瘦身结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThinResult {
+    /// 应用路径
+    pub app_path: String,
+    /// 是否成功
+    pub success: bool,
+    /// 结果消息
+    pub message: String,
+    /// 备份 ID（用于还原）
+    pub backup_id: String,
+    /// 已回收空间(bytes)
+    pub reclaimed_bytes: u64,
+    /// 已瘦身的文件
+    pub thinned_files: Vec<String>,
+    /// 失败项列表
+    pub failed_items: Vec<String>,
+    /// 是否需要重新签名
+    pub needs_resign: bool,
+}
+
+/// 瘦身备份
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThinBackup {
+    /// 备份 ID
+    pub id: String,
+    /// 应用路径
+    pub app_path: String,
+    /// 瘦身时保留的架构
+    pub target_arch: String,
+    /// 备份时间(unix timestamp)
+    pub created_at: u64,
+    /// 备份的文件（相对应用包的路径）
+    pub files: Vec<String>,
+    /// 备份总大小(bytes)
+    pub total_size: u64,
+    /// 是否备份了整个应用包（需要重新签名的应用整体备份和还原）
+    #[serde(default)]
+    pub full_bundle: bool,
+}
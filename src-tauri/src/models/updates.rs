@@ -0,0 +1,79 @@
+//! 应用更新检查数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 增量更新包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppcastDelta {
+    /// 适用的旧版本（CFBundleVersion）
+    pub from_version: String,
+    /// 下载地址
+    pub url: String,
+    /// 大小(bytes)
+    pub length: u64,
+}
+
+/// Sparkle appcast 中的一个版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppcastItem {
+    /// 标题
+    pub title: String,
+    /// 构建版本（sparkle:version，对应 CFBundleVersion）
+    pub version: String,
+    /// 显示版本（sparkle:shortVersionString）
+    pub short_version: String,
+    /// 发布日期
+    pub pub_date: String,
+    /// 最低系统版本
+    pub minimum_system_version: Option<String>,
+    /// 发行说明链接
+    pub release_notes_url: Option<String>,
+    /// 内嵌的发行说明（HTML）
+    pub description: Option<String>,
+    /// 完整安装包下载地址
+    pub download_url: String,
+    /// 完整安装包大小(bytes)
+    pub length: u64,
+    /// 是否为关键更新
+    pub critical: bool,
+    /// 增量更新包
+    pub deltas: Vec<AppcastDelta>,
+}
+
+/// 单个应用的更新检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppUpdateInfo {
+    /// 应用路径
+    pub app_path: String,
+    /// 应用名称
+    pub app_name: String,
+    /// 应用标识符
+    pub bundle_id: String,
+    /// appcast 地址（SUFeedURL）
+    pub feed_url: String,
+    /// 当前显示版本（CFBundleShortVersionString）
+    pub current_version: String,
+    /// 当前构建版本（CFBundleVersion）
+    pub current_build: String,
+    /// appcast 中的最新版本
+    pub latest: Option<AppcastItem>,
+    /// 是否有可用更新
+    pub update_available: bool,
+    /// 最新版本是否兼容当前系统
+    pub compatible: bool,
+    /// 可用于当前版本的增量更新包
+    pub applicable_delta: Option<AppcastDelta>,
+    /// 检查失败原因
+    pub error: Option<String>,
+}
+
+/// 已安装应用更新检查报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCheckReport {
+    /// 声明了 SUFeedURL 的应用
+    pub apps: Vec<AppUpdateInfo>,
+    /// 有可用更新的应用数量
+    pub outdated_count: u32,
+    /// 检查失败的应用数量
+    pub failed_count: u32,
+}
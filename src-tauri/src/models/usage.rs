@@ -0,0 +1,18 @@
+//! 应用使用记录数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 应用使用记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppUsage {
+    /// 应用路径
+    pub app_path: String,
+    /// 最后使用时间（秒），未知时为 None
+    pub last_used: Option<u64>,
+    /// 最近一次启动时间（秒）
+    pub last_launch: Option<u64>,
+    /// 采样期间观察到的启动次数
+    pub launch_count: u32,
+    /// 记录来源: "sampler"（进程采样）, "atime"（主程序访问时间）
+    pub source: String,
+}
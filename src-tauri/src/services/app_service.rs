@@ -1,10 +1,23 @@
 //! 应用管理服务实现
 
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use base64::{Engine as _, engine::general_purpose};
-use crate::models::app::{AppInfo, InstalledApps, UninstallResult, AppRelatedFile, AppRelatedFiles, DuplicateResult};
+use crate::models::app::{AppInfo, AppFilter, InstalledApps, UninstallResult, AppRelatedFile, AppRelatedFiles, DuplicateResult};
+use crate::models::duplicate::DuplicateCatalogEntry;
+use crate::services::receipt_service::ReceiptService;
+use crate::services::macho_service::{self, MachOService};
+use crate::services::localization_service::LocalizationService;
+use crate::services::entitlement_service::EntitlementService;
+use crate::services::usage_service::UsageService;
+use crate::services::duplicate_service::{read_bundle_identifier, DuplicateService};
+use crate::services::duplicate_catalog_service::{strip_copy_suffix, DuplicateCatalogService};
+use crate::services::bundle_rewrite_service::{BundleRewriteService, DuplicateIdentity};
+use crate::services::icon_badge_service::IconBadge;
+use crate::services::launch_profile_service::LaunchProfileService;
+use crate::services::app_source_service::{self, AppSourceService, UninstallTarget};
 
 /// 应用管理服务
 pub struct AppService;
@@ -15,52 +28,101 @@ impl AppService {
         AppService
     }
 
+    /// 扫描主要应用目录中的应用包（跳过系统应用以加快速度）
+    fn scan_app_bundles(&self) -> Vec<AppInfo> {
+        app_bundle_paths()
+            .iter()
+            .filter_map(|path| self.get_app_info_fast(path))
+            .collect()
+    }
+
     /// 获取已安装的应用列表（快速版，不计算大小）
     pub fn get_installed_apps(&self) -> Result<InstalledApps, String> {
-        let mut apps: Vec<AppInfo> = Vec::new();
-        
-        // 只扫描主要应用目录（跳过系统应用以加快速度）
-        let app_directories = vec![
-            "/Applications".to_string(),
-            dirs::home_dir()
-                .map(|h| h.join("Applications").to_string_lossy().to_string())
-                .unwrap_or_default(),
-        ];
+        let mut apps = self.scan_app_bundles();
 
-        for dir in app_directories {
-            if dir.is_empty() {
-                continue;
-            }
-            self.scan_apps_in_directory_fast(&dir, &mut apps);
-        }
+        // 标记由 Homebrew cask 安装的应用包
+        AppSourceService::new().apply_sources(&mut apps);
 
         // 按名称排序
         apps.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
 
+        self.mark_translated_apps(&mut apps);
+        UsageService::new().apply_usage(&mut apps);
+
         Ok(InstalledApps { apps })
     }
 
-    /// 快速扫描目录中的应用（不计算大小）
-    fn scan_apps_in_directory_fast(&self, dir: &str, apps: &mut Vec<AppInfo>) {
-        let path = Path::new(dir);
-        if !path.exists() || !path.is_dir() {
-            return;
-        }
+    /// 获取 Homebrew formula、未对应应用包的 cask 和 Linux 应用（不计算大小）
+    pub fn get_package_apps(&self) -> Result<InstalledApps, String> {
+        let bundles = self.scan_app_bundles();
+        let mut apps = AppSourceService::new().get_package_apps(&bundles);
+        apps.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        Ok(InstalledApps { apps })
+    }
 
-        if let Ok(entries) = fs::read_dir(path) {
-            for entry in entries.flatten() {
-                let entry_path = entry.path();
-                
-                if entry_path.is_dir() {
-                    if let Some(ext) = entry_path.extension() {
-                        if ext == "app" {
-                            if let Some(app_info) = self.get_app_info_fast(&entry_path) {
-                                apps.push(app_info);
-                            }
-                        }
-                    }
+    /// 按条件筛选已安装的应用
+    pub fn filter_installed_apps(&self, filter: &AppFilter) -> Result<InstalledApps, String> {
+        let mut installed = self.get_installed_apps()?;
+        let entitlements = filter.capability.as_ref().map(|_| EntitlementService::new());
+        let unused_cutoff = filter.unused_days.map(|days| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            now.saturating_sub(days as u64 * 86_400)
+        });
+
+        installed.apps.retain(|app| {
+            if let Some(kind) = &filter.arch_kind {
+                if &app.arch_kind != kind {
+                    return false;
+                }
+            }
+            if filter.translated_only && !app.is_translated {
+                return false;
+            }
+            if let (Some(capability), Some(service)) = (&filter.capability, &entitlements) {
+                if !service.has_capability(&app.path, capability) {
+                    return false;
                 }
             }
+            if let Some(source) = &filter.source {
+                if &app.source != source {
+                    return false;
+                }
+            }
+            if let Some(cutoff) = unused_cutoff {
+                if !app.last_used.map(|t| t < cutoff).unwrap_or(false) {
+                    return false;
+                }
+            }
+            true
+        });
+
+        Ok(installed)
+    }
+
+    /// 获取超过指定天数未使用的应用（按最后使用时间升序）
+    pub fn get_unused_apps(&self, days: u32) -> Result<InstalledApps, String> {
+        let usage = UsageService::new();
+        let installed = self.get_installed_apps()?;
+        // 尚无记录的应用先用主程序访问时间补齐
+        usage.seed_from_access_times(&installed.apps);
+
+        let mut apps = installed.apps;
+        usage.apply_usage(&mut apps);
+        Ok(InstalledApps { apps: usage.filter_unused(apps, days) })
+    }
+
+    /// 标记正在通过 Rosetta 转译运行的应用
+    fn mark_translated_apps(&self, apps: &mut [AppInfo]) {
+        let translated = macho_service::get_translated_processes();
+        if translated.is_empty() {
+            return;
+        }
+        for app in apps.iter_mut() {
+            let bundle_prefix = format!("{}/", app.path);
+            app.is_translated = translated.iter().any(|(_, exe)| exe.starts_with(&bundle_prefix));
         }
     }
 
@@ -83,42 +145,38 @@ impl AppService {
 
     /// 获取支持双开的应用列表（支持已双开的应用）
     pub fn get_duplicatable_apps(&self) -> Result<InstalledApps, String> {
-        // 支持双开的应用名称关键词（不区分大小写）
-        let keywords = [
-            "qq", "wechat", "微信", "企业微信", "wecom",
-            "dingtalk", "钉钉", "feishu", "飞书", "lark",
-        ];
-        
-        // 排除的应用（不显示）
-        let excluded = ["qqmusic", "qqlive", "qqbrowser"];
+        // 按应用标识符匹配可多开应用目录
+        let mut catalog: HashSet<String> = DuplicateCatalogService::new()
+            .enabled_entries()
+            .into_iter()
+            .map(|e| e.bundle_id)
+            .collect();
+
+        // 已登记副本的源应用在目录中时，副本同样可再次多开
+        let copies: Vec<String> = DuplicateService::new()
+            .list_duplicates()
+            .into_iter()
+            .filter(|s| catalog.contains(&s.duplicate.source_bundle_id))
+            .map(|s| s.duplicate.bundle_id)
+            .collect();
+        catalog.extend(copies);
 
         let mut apps: Vec<AppInfo> = Vec::new();
         
         // 扫描 /Applications 目录
-        self.scan_duplicatable_apps("/Applications", &keywords, &excluded, &mut apps);
+        self.scan_duplicatable_apps("/Applications", &catalog, &mut apps);
         
         // 扫描用户应用目录
         if let Some(home) = dirs::home_dir() {
             let user_apps_dir = home.join("Applications");
-            self.scan_duplicatable_apps(
-                &user_apps_dir.to_string_lossy(),
-                &keywords,
-                &excluded,
-                &mut apps
-            );
+            self.scan_duplicatable_apps(&user_apps_dir.to_string_lossy(), &catalog, &mut apps);
         }
 
         Ok(InstalledApps { apps })
     }
 
     /// 扫描目录中支持双开的应用
-    fn scan_duplicatable_apps(
-        &self,
-        dir: &str,
-        keywords: &[&str],
-        excluded: &[&str],
-        apps: &mut Vec<AppInfo>
-    ) {
+    fn scan_duplicatable_apps(&self, dir: &str, catalog: &HashSet<String>, apps: &mut Vec<AppInfo>) {
         let path = Path::new(dir);
         if !path.exists() || !path.is_dir() {
             return;
@@ -128,29 +186,22 @@ impl AppService {
             for entry in entries.flatten() {
                 let entry_path = entry.path();
                 
-                if entry_path.is_dir() {
-                    if let Some(ext) = entry_path.extension() {
-                        if ext == "app" {
-                            // 获取应用名称（小写）
-                            let app_name = entry_path.file_stem()
-                                .map(|s| s.to_string_lossy().to_lowercase())
-                                .unwrap_or_default();
-                            
-                            // 检查是否在排除列表中
-                            if excluded.iter().any(|e| app_name.contains(e)) {
-                                continue;
-                            }
-                            
-                            // 检查是否包含关键词
-                            if keywords.iter().any(|k| app_name.contains(k)) {
-                                if let Some(app_info) = self.get_app_info_with_icon(&entry_path) {
-                                    // 避免重复
-                                    if !apps.iter().any(|a| a.path == app_info.path) {
-                                        apps.push(app_info);
-                                    }
-                                }
-                            }
-                        }
+                if !entry_path.is_dir() || entry_path.extension().map(|e| e != "app").unwrap_or(true) {
+                    continue;
+                }
+
+                // 检查标识符是否在目录中，副本按去掉编号后缀的源标识符匹配
+                let in_catalog = read_bundle_identifier(&entry_path)
+                    .map(|id| catalog.contains(&id) || catalog.contains(strip_copy_suffix(&id)))
+                    .unwrap_or(false);
+                if !in_catalog {
+                    continue;
+                }
+
+                if let Some(app_info) = self.get_app_info_with_icon(&entry_path) {
+                    // 避免重复
+                    if !apps.iter().any(|a| a.path == app_info.path) {
+                        apps.push(app_info);
                     }
                 }
             }
@@ -189,6 +240,9 @@ impl AppService {
             .unwrap_or_default();
         let is_duplicate = app_file_name.chars().any(|c| c.is_ascii_digit());
 
+        // 读取主程序 Mach-O 头部获取架构
+        let (architectures, min_os_version) = MachOService::new().get_executable_arch(app_path);
+
         Some(AppInfo {
             name,
             identifier,
@@ -197,11 +251,19 @@ impl AppService {
             icon_path,
             size: 0, // 不计算大小，加快速度
             is_duplicate,
+            arch_kind: macho_service::arch_kind(&architectures),
+            architectures,
+            min_os_version,
+            is_translated: false,
+            last_used: None,
+            launch_count: 0,
+            source: app_source_service::bundle_source(app_path).to_string(),
+            uninstall_strategy: "trash".to_string(),
         })
     }
 
     /// 获取应用信息（包含图标，用于双开应用）
-    fn get_app_info_with_icon(&self, app_path: &Path) -> Option<AppInfo> {
+    pub fn get_app_info_with_icon(&self, app_path: &Path) -> Option<AppInfo> {
         let info_plist = app_path.join("Contents/Info.plist");
         
         if !info_plist.exists() {
@@ -232,6 +294,8 @@ impl AppService {
             .unwrap_or_default();
         let is_duplicate = app_file_name.chars().any(|c| c.is_ascii_digit());
 
+        let (architectures, min_os_version) = MachOService::new().get_executable_arch(app_path);
+
         Some(AppInfo {
             name,
             identifier,
@@ -240,6 +304,14 @@ impl AppService {
             icon_path,
             size: 0,
             is_duplicate,
+            arch_kind: macho_service::arch_kind(&architectures),
+            architectures,
+            min_os_version,
+            is_translated: false,
+            last_used: None,
+            launch_count: 0,
+            source: app_source_service::bundle_source(app_path).to_string(),
+            uninstall_strategy: "trash".to_string(),
         })
     }
 
@@ -420,6 +492,12 @@ impl AppService {
             return Err("应用不存在".to_string());
         }
 
+        // Homebrew、Flatpak、Snap 等通过对应的包管理器卸载
+        let target = AppSourceService::new().resolve_uninstall(app_path);
+        if target != UninstallTarget::Trash {
+            return AppSourceService::new().uninstall_with_package_manager(&target);
+        }
+
         let mut removed_paths = Vec::new();
 
         // 移动应用到废纸篓
@@ -585,10 +663,17 @@ impl AppService {
             }
         }
         
+        // 扫描 .pkg 安装包释放到应用包外的文件
+        let pkg_files = self.get_pkg_payload_files(app_path);
+        
+        // 统计本地化目录大小
+        let localizations = LocalizationService::new().analyze_app(app_path);
+        
         let total_size = binary_files.iter().map(|f| f.size).sum::<u64>()
             + sandbox_files.iter().map(|f| f.size).sum::<u64>()
-            + other_files.iter().map(|f| f.size).sum::<u64>();
-        let total_files = (binary_files.len() + sandbox_files.len() + other_files.len()) as u32;
+            + other_files.iter().map(|f| f.size).sum::<u64>()
+            + pkg_files.iter().map(|f| f.size).sum::<u64>();
+        let total_files = (binary_files.len() + sandbox_files.len() + other_files.len() + pkg_files.len()) as u32;
         
         AppRelatedFiles {
             app_name,
@@ -599,7 +684,40 @@ impl AppService {
             binary_files,
             sandbox_files,
             other_files,
+            pkg_files,
+            localization_size: localizations.total_size,
+            unused_localization_size: localizations.unused_size,
+        }
+    }
+
+    /// 获取安装包回执中属于该应用、但位于应用包之外的文件
+    fn get_pkg_payload_files(&self, app_path: &str) -> Vec<AppRelatedFile> {
+        let bundle_prefix = format!("{}/", app_path.trim_end_matches('/'));
+        let mut files: Vec<AppRelatedFile> = Vec::new();
+
+        for receipt in ReceiptService::new().find_receipts_for_app(app_path) {
+            for file in receipt.files {
+                // 目录可能被多个软件共享，只收集文件和链接
+                if file.file_type == "directory" || file.path.starts_with(&bundle_prefix) {
+                    continue;
+                }
+                let path = Path::new(&file.path);
+                let Ok(metadata) = fs::symlink_metadata(path) else {
+                    continue;
+                };
+                if files.iter().any(|f| f.path == file.path) {
+                    continue;
+                }
+                files.push(AppRelatedFile {
+                    name: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+                    path: file.path.clone(),
+                    size: metadata.len(),
+                    file_type: format!("pkg:{}", receipt.package_id),
+                });
+            }
         }
+
+        files
     }
     
     /// 获取目录大小
@@ -724,7 +842,7 @@ impl AppService {
         })
     }
 
-    /// 快速双开应用 - 直接运行应用内部可执行文件
+    /// 快速双开应用 - 以独立数据目录的快速启动配置运行新实例
     pub fn quick_duplicate_app(&self, app_path: &str) -> DuplicateResult {
         let path = Path::new(app_path);
         
@@ -741,76 +859,43 @@ impl AppService {
             .and_then(|s| s.to_str())
             .unwrap_or("App");
 
-        // 构建可执行文件路径
-        let macos_path = path.join("Contents/MacOS");
-        
-        if !macos_path.exists() {
-            return DuplicateResult {
-                success: false,
-                message: "找不到应用的可执行文件".to_string(),
-                steps: vec![],
-            };
-        }
-
-        // 查找可执行文件
-        let executable = if let Ok(entries) = fs::read_dir(&macos_path) {
-            entries
-                .flatten()
-                .find(|e| {
-                    let path = e.path();
-                    path.is_file() && !path.file_name()
-                        .and_then(|n| n.to_str())
-                        .map(|n| n.starts_with('.'))
-                        .unwrap_or(true)
-                })
-                .map(|e| e.path())
-        } else {
-            None
-        };
-
-        let exec_path = match executable {
-            Some(p) => p,
-            None => {
-                // 尝试使用应用名称作为可执行文件名
-                macos_path.join(app_name)
+        // 使用独立数据目录的启动配置，避免新实例与原应用争用同一份数据
+        let profiles = LaunchProfileService::new();
+        let profile = match profiles.quick_profile(app_path) {
+            Ok(profile) => profile,
+            Err(e) => {
+                return DuplicateResult {
+                    success: false,
+                    message: e,
+                    steps: vec![],
+                };
             }
         };
+        let mut steps = vec![
+            format!("使用启动配置: {}（隔离方式: {}）", profile.name, profile.strategy),
+            format!("数据目录: {}", profile.data_dir),
+            "正在启动新实例...".to_string(),
+        ];
 
-        if !exec_path.exists() {
-            return DuplicateResult {
-                success: false,
-                message: format!("找不到可执行文件: {:?}", exec_path),
-                steps: vec![],
-            };
-        }
-
-        // 使用 nohup 运行，使其在后台运行
-        let result = Command::new("nohup")
-            .arg(&exec_path)
-            .arg("/dev/null")
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .spawn();
-
-        match result {
-            Ok(_) => DuplicateResult {
-                success: true,
-                message: format!("{} 已启动新实例", app_name),
-                steps: vec![
-                    format!("找到可执行文件: {:?}", exec_path),
-                    "正在启动新实例...".to_string(),
-                    "启动成功!".to_string(),
-                ],
-            },
+        // 由实例跟踪服务启动，记录 PID 并检测崩溃
+        match profiles.launch_profile(&profile.id) {
+            Ok(instance) => {
+                steps.push(format!("启动成功! PID: {}", instance.pid));
+                DuplicateResult {
+                    success: true,
+                    message: format!("{} 已启动新实例", app_name),
+                    steps,
+                }
+            }
             Err(e) => DuplicateResult {
                 success: false,
-                message: format!("启动失败: {}", e),
-                steps: vec![format!("尝试运行: {:?}", exec_path)],
+                message: e,
+                steps,
             },
         }
     }
 
-    /// 创建应用副本 - 复制应用并修改标识符
+    /// 创建应用副本 - 复制应用并修改标识符（目录中多开方式为 profile 的应用改为创建启动配置）
     pub fn create_duplicate_app(&self, app_path: &str, app_name: &str, identifier: &str, icon_emoji: Option<String>) -> DuplicateResult {
         let source_path = Path::new(app_path);
         let mut steps = Vec::new();
@@ -823,6 +908,15 @@ impl AppService {
             };
         }
 
+        // 按可多开应用目录选择多开方式: profile 方式改为创建独立数据目录的启动配置，不复制应用
+        let source_bundle_id = self.get_app_identifier_from_path(app_path).unwrap_or_default();
+        let entry = DuplicateCatalogService::new().find(&source_bundle_id);
+        if let Some(entry) = entry.as_ref().filter(|e| e.strategy == "profile") {
+            return self.create_duplicate_profile(app_path, app_name, entry, icon_emoji);
+        }
+        // 目录未收录的应用默认重新签名
+        let resign = entry.map(|e| e.requires_resign).unwrap_or(true);
+
         // 确定副本名称和路径
         let parent_dir = source_path.parent().unwrap_or(Path::new("/Applications"));
         let mut copy_number = 2;
@@ -849,88 +943,106 @@ impl AppService {
 
         steps.push(format!("副本将创建为: {:?}", dest_path));
 
-        // 复制应用
-        steps.push("正在复制应用文件...".to_string());
-        let copy_result = Command::new("cp")
-            .args(["-R", app_path, dest_path.to_str().unwrap()])
-            .output();
+        let new_identifier = format!("{}_{}", identifier, copy_number);
+        let identity = DuplicateIdentity {
+            source_bundle_id,
+            bundle_id: new_identifier.clone(),
+            display_name: dest_path.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| app_name.to_string()),
+            url_scheme_suffix: copy_number.to_string(),
+            icon_badge: Some(IconBadge::for_duplicate(copy_number, icon_emoji.as_deref())),
+            resign,
+        };
+        if let Err(e) = BundleRewriteService::new().create_copy(source_path, &dest_path, &identity, &mut steps) {
+            return DuplicateResult {
+                success: false,
+                message: e.to_string(),
+                steps,
+            };
+        }
 
-        match copy_result {
-            Ok(output) if output.status.success() => {
-                steps.push("复制完成".to_string());
-            }
-            Ok(output) => {
-                return DuplicateResult {
-                    success: false,
-                    message: format!("复制失败: {}", String::from_utf8_lossy(&output.stderr)),
-                    steps,
-                };
-            }
+        // 登记副本，便于之后列出、更新和删除；数据库可能被其他操作短暂占用，失败时重试一次
+        let duplicates = DuplicateService::new();
+        let dest = dest_path.to_string_lossy().to_string();
+        let register = || duplicates.register(app_path, &dest, &new_identifier, app_name, icon_emoji.clone(), copy_number);
+        match register().or_else(|_| register()) {
+            Ok(record) => steps.push(format!("已登记副本 (ID {})", record.id)),
             Err(e) => {
+                // 未登记的副本无法在副本列表中管理，删除后报告失败
+                steps.push(format!("副本登记失败: {}", e));
+                if fs::remove_dir_all(&dest_path).is_ok() {
+                    steps.push("已删除未登记的副本".to_string());
+                }
                 return DuplicateResult {
                     success: false,
-                    message: format!("复制失败: {}", e),
+                    message: format!("副本登记失败: {}", e),
                     steps,
                 };
             }
         }
 
-        // 修改 Bundle Identifier
-        let info_plist = dest_path.join("Contents/Info.plist");
-        let new_identifier = format!("{}_{}", identifier, copy_number);
-        
-        steps.push(format!("修改标识符为: {}", new_identifier));
-        
-        let plistbuddy_result = Command::new("/usr/libexec/PlistBuddy")
-            .args([
-                "-c",
-                &format!("Set :CFBundleIdentifier {}", new_identifier),
-                info_plist.to_str().unwrap(),
-            ])
-            .output();
-
-        if let Ok(output) = plistbuddy_result {
-            if output.status.success() {
-                steps.push("标识符修改成功".to_string());
-            } else {
-                steps.push(format!("标识符修改失败: {}", String::from_utf8_lossy(&output.stderr)));
-            }
+        DuplicateResult {
+            success: true,
+            message: format!("{} 副本创建成功", app_name),
+            steps,
         }
+    }
 
-        // 删除扩展属性
-        steps.push("删除扩展属性...".to_string());
-        let _ = Command::new("xattr")
-            .args(["-cr", dest_path.to_str().unwrap()])
-            .output();
-
-        // 重新签名
-        steps.push("重新签名应用...".to_string());
-        let codesign_result = Command::new("codesign")
-            .args([
-                "--force",
-                "--deep",
-                "--sign",
-                "-",
-                dest_path.to_str().unwrap(),
-            ])
-            .output();
+    /// 以启动配置多开应用（目录中多开方式为 profile 的应用），不复制应用包
+    fn create_duplicate_profile(&self, app_path: &str, app_name: &str, entry: &DuplicateCatalogEntry, icon_emoji: Option<String>) -> DuplicateResult {
+        let profiles = LaunchProfileService::new();
+        let existing = profiles.list_profiles(Some(app_path));
+        let name_prefix = icon_emoji.map(|e| format!("{} ", e)).unwrap_or_default();
+        let Some(name) = (2..100)
+            .map(|n| format!("{}{} {}", name_prefix, app_name, n))
+            .find(|name| !existing.iter().any(|p| &p.name == name))
+        else {
+            return DuplicateResult {
+                success: false,
+                message: "副本数量超出限制".to_string(),
+                steps: vec![],
+            };
+        };
 
-        match codesign_result {
-            Ok(output) if output.status.success() => {
-                steps.push("签名成功".to_string());
-            }
-            Ok(output) => {
-                steps.push(format!("签名失败 (可能不影响使用): {}", String::from_utf8_lossy(&output.stderr)));
-            }
-            Err(e) => {
-                steps.push(format!("签名失败: {}", e));
+        let mut steps = vec![format!("{} 使用独立数据目录多开，无需复制应用", entry.name)];
+        match profiles.create_profile(app_path, &name, entry.isolation.clone(), Vec::new(), BTreeMap::new()) {
+            Ok(profile) => {
+                steps.push(format!("已创建启动配置: {}（隔离方式: {}）", profile.name, profile.strategy));
+                steps.push(format!("数据目录: {}", profile.data_dir));
+                DuplicateResult {
+                    success: true,
+                    message: format!("{} 启动配置创建成功", app_name),
+                    steps,
+                }
             }
+            Err(e) => DuplicateResult {
+                success: false,
+                message: e,
+                steps,
+            },
         }
+    }
+}
 
-        DuplicateResult {
-            success: true,
-            message: format!("{} 副本创建成功", app_name),
-            steps,
+/// 主要应用目录（/Applications 和 ~/Applications）中的应用包路径，不读取应用信息
+pub fn app_bundle_paths() -> Vec<PathBuf> {
+    let mut app_directories = vec![PathBuf::from("/Applications")];
+    if let Some(home) = dirs::home_dir() {
+        app_directories.push(home.join("Applications"));
+    }
+
+    let mut paths = Vec::new();
+    for dir in app_directories {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            if entry_path.is_dir() && entry_path.extension().is_some_and(|ext| ext == "app") {
+                paths.push(entry_path);
+            }
         }
     }
+    paths
 }
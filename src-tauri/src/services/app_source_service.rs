@@ -0,0 +1,495 @@
+//! 应用来源识别服务实现
+//!
+//! 识别 /Applications 中应用包的来源（Homebrew cask、带 `_MASReceipt` 的 App Store 应用），
+//! 并单独列出 Homebrew 的 formula 与未对应应用包的 cask（Cellar/Caskroom 目录结构）、
+//! Linux 上的 XDG `.desktop` 应用、Flatpak 和 Snap，为每种来源提供对应的卸载方式。
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+use crate::models::app::{AppInfo, UninstallResult};
+use crate::services::update_service::compare_versions;
+
+/// Snap 中不属于应用的类型
+const NON_APP_SNAP_TYPES: [&str; 5] = ["base", "core", "os", "snapd", "kernel"];
+
+/// 应用包的来源（仅区分 App Store 与手动安装）
+pub fn bundle_source(app_path: &Path) -> &'static str {
+    if app_path.join("Contents/_MASReceipt/receipt").exists() {
+        "app_store"
+    } else {
+        "manual"
+    }
+}
+
+/// 卸载目标
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UninstallTarget {
+    /// 移动到废纸篓
+    Trash,
+    /// brew uninstall <formula>
+    BrewFormula { brew: PathBuf, name: String },
+    /// brew uninstall --cask <token>
+    BrewCask { brew: PathBuf, token: String },
+    /// flatpak uninstall <id>
+    Flatpak { id: String, user: bool },
+    /// snap remove <name>
+    Snap { name: String },
+    /// Linux 桌面应用（系统包管理器或手动安装），需要用户自行卸载
+    PackageManager,
+}
+
+/// 列出目录下的非隐藏子目录名
+fn visible_subdirs(path: &Path) -> Vec<String> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries.flatten()
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|n| !n.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 选出最新的版本目录
+fn latest_version(versions: &[String]) -> Option<String> {
+    versions.iter()
+        .max_by(|a, b| compare_versions(a, b))
+        .cloned()
+}
+
+/// 构造非应用包形式的应用信息（大小按需通过 get_app_size 获取）
+fn package_app(name: String, identifier: String, version: String, path: &Path, source: &str, strategy: &str) -> AppInfo {
+    AppInfo {
+        name,
+        identifier,
+        version,
+        path: path.to_string_lossy().to_string(),
+        icon_path: String::new(),
+        size: 0,
+        is_duplicate: false,
+        architectures: Vec::new(),
+        arch_kind: "unknown".to_string(),
+        min_os_version: String::new(),
+        is_translated: false,
+        last_used: None,
+        launch_count: 0,
+        source: source.to_string(),
+        uninstall_strategy: strategy.to_string(),
+    }
+}
+
+/// 应用包是否为 cask 的 app 产物
+fn is_cask_artifact(app: &AppInfo, artifacts: &[String]) -> bool {
+    app.path.ends_with(".app")
+        && Path::new(&app.path).file_name()
+            .map(|n| artifacts.contains(&n.to_string_lossy().to_string()))
+            .unwrap_or(false)
+}
+
+/// 从 cask 元数据（.json 或 .rb）中读取 app 产物的文件名
+fn cask_app_artifacts(cask_dir: &Path) -> Vec<String> {
+    let metadata = cask_dir.join(".metadata");
+    let mut artifacts = Vec::new();
+
+    for entry in WalkDir::new(&metadata).max_depth(4).into_iter().flatten() {
+        let path = entry.path();
+        let Some(ext) = path.extension().map(|e| e.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if !path.parent().map(|p| p.ends_with("Casks")).unwrap_or(false) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+
+        if ext == "json" {
+            let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+                continue;
+            };
+            let items = json.get("artifacts").and_then(|a| a.as_array()).cloned().unwrap_or_default();
+            for item in items {
+                let Some(apps) = item.get("app").and_then(|a| a.as_array()) else {
+                    continue;
+                };
+                // ["Foo.app"] 或 ["Foo.app", {"target": "Bar.app"}]
+                let target = apps.iter()
+                    .find_map(|a| a.get("target").and_then(|t| t.as_str()))
+                    .or_else(|| apps.first().and_then(|a| a.as_str()));
+                if let Some(target) = target {
+                    artifacts.push(target.to_string());
+                }
+            }
+        } else if ext == "rb" {
+            for line in content.lines() {
+                let line = line.trim();
+                let Some(rest) = line.strip_prefix("app ") else {
+                    continue;
+                };
+                // app "Foo.app" 或 app "Foo.app", target: "Bar.app"
+                let quoted: Vec<&str> = rest.split('"').skip(1).step_by(2).collect();
+                if let Some(target) = quoted.last() {
+                    artifacts.push(target.to_string());
+                }
+            }
+        }
+    }
+
+    artifacts.iter()
+        .filter_map(|a| Path::new(a).file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// 解析 .desktop 文件中 [Desktop Entry] 段的键值
+fn parse_desktop_entry(path: &Path) -> Option<HashMap<String, String>> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut in_entry = false;
+    let mut values = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_entry || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            values.entry(key.trim().to_string()).or_insert_with(|| value.trim().to_string());
+        }
+    }
+    (!values.is_empty()).then_some(values)
+}
+
+/// 读取简单 YAML 中的顶层字段
+fn yaml_field(content: &str, key: &str) -> Option<String> {
+    content.lines()
+        .filter(|l| !l.starts_with(' ') && !l.starts_with('\t'))
+        .find_map(|l| l.strip_prefix(key)?.strip_prefix(':').map(|v| v.trim().trim_matches(['\'', '"']).to_string()))
+        .filter(|v| !v.is_empty())
+}
+
+/// 应用来源识别服务
+pub struct AppSourceService {
+    homebrew_prefixes: Vec<PathBuf>,
+    flatpak_roots: Vec<(PathBuf, bool)>,
+    snap_root: PathBuf,
+    desktop_dirs: Vec<PathBuf>,
+}
+
+impl AppSourceService {
+    /// 创建新的应用来源识别服务实例
+    pub fn new() -> Self {
+        let home = dirs::home_dir().unwrap_or_default();
+
+        let mut homebrew_prefixes: Vec<PathBuf> = std::env::var_os("HOMEBREW_PREFIX")
+            .map(PathBuf::from)
+            .into_iter()
+            .collect();
+        for prefix in [
+            PathBuf::from("/opt/homebrew"),
+            PathBuf::from("/usr/local"),
+            PathBuf::from("/home/linuxbrew/.linuxbrew"),
+            home.join(".linuxbrew"),
+        ] {
+            if !homebrew_prefixes.contains(&prefix) {
+                homebrew_prefixes.push(prefix);
+            }
+        }
+
+        // XDG 数据目录：用户目录优先
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".local/share"));
+        let data_dirs = std::env::var("XDG_DATA_DIRS")
+            .unwrap_or_else(|_| "/usr/local/share:/usr/share".to_string());
+        let desktop_dirs = std::iter::once(data_home.clone())
+            .chain(data_dirs.split(':').filter(|d| !d.is_empty()).map(PathBuf::from))
+            .map(|d| d.join("applications"))
+            .collect();
+
+        AppSourceService {
+            flatpak_roots: vec![
+                (data_home.join("flatpak"), true),
+                (PathBuf::from("/var/lib/flatpak"), false),
+            ],
+            snap_root: PathBuf::from("/snap"),
+            desktop_dirs,
+            ..Self::with_homebrew_prefixes(homebrew_prefixes)
+        }
+    }
+
+    /// 使用指定的 Homebrew 前缀创建实例（不扫描 Linux 来源）
+    pub fn with_homebrew_prefixes(homebrew_prefixes: Vec<PathBuf>) -> Self {
+        AppSourceService {
+            homebrew_prefixes,
+            flatpak_roots: Vec::new(),
+            snap_root: PathBuf::new(),
+            desktop_dirs: Vec::new(),
+        }
+    }
+
+    /// 存在 Cellar 或 Caskroom 的 Homebrew 前缀
+    fn active_homebrew_prefixes(&self) -> impl Iterator<Item = &PathBuf> {
+        self.homebrew_prefixes.iter()
+            .filter(|p| p.join("Cellar").is_dir() || p.join("Caskroom").is_dir())
+    }
+
+    /// 为由 Homebrew cask 安装的应用包标记来源
+    pub fn apply_sources(&self, apps: &mut [AppInfo]) {
+        for prefix in self.active_homebrew_prefixes() {
+            let caskroom = prefix.join("Caskroom");
+            for token in visible_subdirs(&caskroom) {
+                let artifacts = cask_app_artifacts(&caskroom.join(&token));
+                for app in apps.iter_mut().filter(|app| is_cask_artifact(app, &artifacts)) {
+                    app.source = "homebrew_cask".to_string();
+                    app.uninstall_strategy = "brew_cask".to_string();
+                }
+            }
+        }
+    }
+
+    /// 获取非应用包形式的软件: Homebrew formula、未对应任何应用包的 cask 和 Linux 应用
+    pub fn get_package_apps(&self, bundles: &[AppInfo]) -> Vec<AppInfo> {
+        let mut apps = Vec::new();
+        for prefix in self.active_homebrew_prefixes() {
+            self.scan_homebrew(prefix, bundles, &mut apps);
+        }
+        if cfg!(target_os = "linux") {
+            apps.extend(self.scan_linux_apps());
+        }
+        apps
+    }
+
+    /// 扫描单个 Homebrew 前缀
+    fn scan_homebrew(&self, prefix: &Path, bundles: &[AppInfo], apps: &mut Vec<AppInfo>) {
+        let cellar = prefix.join("Cellar");
+        for name in visible_subdirs(&cellar) {
+            let formula_dir = cellar.join(&name);
+            let Some(version) = latest_version(&visible_subdirs(&formula_dir)) else {
+                continue;
+            };
+            apps.push(package_app(
+                name.clone(),
+                format!("homebrew.formula.{}", name),
+                version.clone(),
+                &formula_dir.join(&version),
+                "homebrew_formula",
+                "brew_formula",
+            ));
+        }
+
+        let caskroom = prefix.join("Caskroom");
+        for token in visible_subdirs(&caskroom) {
+            let cask_dir = caskroom.join(&token);
+            let artifacts = cask_app_artifacts(&cask_dir);
+            if bundles.iter().any(|app| is_cask_artifact(app, &artifacts)) {
+                continue;
+            }
+            let version = latest_version(&visible_subdirs(&cask_dir)).unwrap_or_default();
+            apps.push(package_app(
+                token.clone(),
+                format!("homebrew.cask.{}", token),
+                version,
+                &cask_dir,
+                "homebrew_cask",
+                "brew_cask",
+            ));
+        }
+    }
+
+    /// 扫描 Linux 上的 Flatpak、Snap 和 XDG 桌面应用
+    pub fn scan_linux_apps(&self) -> Vec<AppInfo> {
+        let mut apps = Vec::new();
+        let mut covered_ids: HashSet<String> = HashSet::new();
+
+        for (root, _) in &self.flatpak_roots {
+            let app_root = root.join("app");
+            for id in visible_subdirs(&app_root) {
+                let app_dir = app_root.join(&id);
+                let active = app_dir.join("current/active");
+                let name = parse_desktop_entry(&active.join(format!("export/share/applications/{}.desktop", id)))
+                    .and_then(|e| e.get("Name").cloned())
+                    .unwrap_or_else(|| id.clone());
+                let version = flatpak_version(&active, &id)
+                    .or_else(|| fs::read_link(app_dir.join("current")).ok().and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string())))
+                    .unwrap_or_default();
+                covered_ids.insert(id.clone());
+                apps.push(package_app(name, id, version, &app_dir, "flatpak", "flatpak"));
+            }
+        }
+
+        for name in visible_subdirs(&self.snap_root) {
+            let snap_dir = self.snap_root.join(&name);
+            let Ok(meta) = fs::read_to_string(snap_dir.join("current/meta/snap.yaml")) else {
+                continue;
+            };
+            let snap_type = yaml_field(&meta, "type").unwrap_or_else(|| "app".to_string());
+            if NON_APP_SNAP_TYPES.contains(&snap_type.as_str()) {
+                continue;
+            }
+            let title = yaml_field(&meta, "title").unwrap_or_else(|| name.clone());
+            let version = yaml_field(&meta, "version").unwrap_or_default();
+            covered_ids.insert(name.clone());
+            apps.push(package_app(title, name.clone(), version, &snap_dir, "snap", "snap"));
+        }
+
+        // 同名 .desktop 文件以先出现的目录（用户目录）为准
+        let mut seen_files: HashSet<String> = HashSet::new();
+        for dir in &self.desktop_dirs {
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().map(|e| e != "desktop").unwrap_or(true) {
+                    continue;
+                }
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if !seen_files.insert(file_name.clone()) {
+                    continue;
+                }
+                let Some(desktop) = parse_desktop_entry(&path) else {
+                    continue;
+                };
+                let is_true = |key: &str| desktop.get(key).map(|v| v == "true").unwrap_or(false);
+                if desktop.get("Type").map(|t| t != "Application").unwrap_or(true) || is_true("NoDisplay") || is_true("Hidden") {
+                    continue;
+                }
+                // Flatpak 和 Snap 导出的桌面文件已由上面的扫描覆盖
+                let id = file_name.trim_end_matches(".desktop").to_string();
+                let path_str = path.to_string_lossy();
+                if desktop.contains_key("X-Flatpak") || desktop.contains_key("X-SnapInstanceName")
+                    || path_str.contains("/flatpak/exports/") || path_str.contains("/snapd/desktop/")
+                    || covered_ids.contains(&id)
+                {
+                    continue;
+                }
+
+                let name = desktop.get("Name").cloned().unwrap_or_else(|| id.clone());
+                let version = desktop.get("X-AppImage-Version").cloned().unwrap_or_default();
+                apps.push(package_app(name, id, version, &path, "desktop", "package_manager"));
+            }
+        }
+
+        apps.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        apps
+    }
+
+    /// 根据应用路径确定卸载方式
+    pub fn resolve_uninstall(&self, app_path: &str) -> UninstallTarget {
+        let path = Path::new(app_path);
+
+        for prefix in self.active_homebrew_prefixes() {
+            let brew = prefix.join("bin/brew");
+            if let Ok(relative) = path.strip_prefix(prefix.join("Cellar")) {
+                if let Some(name) = relative.iter().next() {
+                    return UninstallTarget::BrewFormula { brew, name: name.to_string_lossy().to_string() };
+                }
+            }
+            if let Ok(relative) = path.strip_prefix(prefix.join("Caskroom")) {
+                if let Some(token) = relative.iter().next() {
+                    return UninstallTarget::BrewCask { brew, token: token.to_string_lossy().to_string() };
+                }
+            }
+            if app_path.ends_with(".app") {
+                let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let caskroom = prefix.join("Caskroom");
+                for token in visible_subdirs(&caskroom) {
+                    if cask_app_artifacts(&caskroom.join(&token)).contains(&file_name) {
+                        return UninstallTarget::BrewCask { brew, token };
+                    }
+                }
+            }
+        }
+
+        for (root, user) in &self.flatpak_roots {
+            if let Ok(relative) = path.strip_prefix(root.join("app")) {
+                if let Some(id) = relative.iter().next() {
+                    return UninstallTarget::Flatpak { id: id.to_string_lossy().to_string(), user: *user };
+                }
+            }
+        }
+        if !self.snap_root.as_os_str().is_empty() {
+            if let Ok(relative) = path.strip_prefix(&self.snap_root) {
+                if let Some(name) = relative.iter().next() {
+                    return UninstallTarget::Snap { name: name.to_string_lossy().to_string() };
+                }
+            }
+        }
+        if app_path.ends_with(".desktop") {
+            return UninstallTarget::PackageManager;
+        }
+
+        UninstallTarget::Trash
+    }
+
+    /// 使用包管理器卸载（废纸篓方式由调用方处理）
+    pub fn uninstall_with_package_manager(&self, target: &UninstallTarget) -> Result<UninstallResult, String> {
+        let (program, args, removed): (String, Vec<String>, String) = match target {
+            UninstallTarget::BrewFormula { brew, name } => (
+                brew.to_string_lossy().to_string(),
+                vec!["uninstall".to_string(), name.clone()],
+                name.clone(),
+            ),
+            UninstallTarget::BrewCask { brew, token } => (
+                brew.to_string_lossy().to_string(),
+                vec!["uninstall".to_string(), "--cask".to_string(), token.clone()],
+                token.clone(),
+            ),
+            UninstallTarget::Flatpak { id, user } => {
+                let scope = if *user { "--user" } else { "--system" };
+                (
+                    "flatpak".to_string(),
+                    vec!["uninstall".to_string(), "-y".to_string(), "--noninteractive".to_string(), scope.to_string(), id.clone()],
+                    id.clone(),
+                )
+            }
+            UninstallTarget::Snap { name } => (
+                "pkexec".to_string(),
+                vec!["snap".to_string(), "remove".to_string(), name.clone()],
+                name.clone(),
+            ),
+            UninstallTarget::PackageManager => {
+                return Err("该应用由系统包管理器安装，请使用系统包管理器或手动卸载".to_string());
+            }
+            UninstallTarget::Trash => {
+                return Err("该应用不是通过包管理器安装的".to_string());
+            }
+        };
+
+        let output = Command::new(&program)
+            .args(&args)
+            .output()
+            .map_err(|e| format!("无法执行 {}: {}", program, e))?;
+        if !output.status.success() {
+            return Err(format!("卸载失败: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+
+        Ok(UninstallResult {
+            success: true,
+            message: format!("已通过 {} 卸载", program.rsplit('/').next().unwrap_or(&program)),
+            removed_paths: vec![removed],
+        })
+    }
+}
+
+/// 读取 Flatpak 应用 metainfo 中最新的 release 版本
+fn flatpak_version(active: &Path, id: &str) -> Option<String> {
+    let candidates = [
+        active.join(format!("files/share/metainfo/{}.metainfo.xml", id)),
+        active.join(format!("files/share/metainfo/{}.appdata.xml", id)),
+        active.join(format!("files/share/appdata/{}.appdata.xml", id)),
+    ];
+    let content = candidates.iter().find_map(|p| fs::read_to_string(p).ok())?;
+    let release = &content[content.find("<release ")?..];
+    let start = release.find("version=\"")? + "version=\"".len();
+    let end = release[start..].find('"')?;
+    Some(release[start..start + end].to_string())
+}
@@ -0,0 +1,525 @@
+//! 文件类型与 URL 协议关联服务实现
+//!
+//! 从各应用 Info.plist 读取 CFBundleDocumentTypes、UTExportedTypeDeclarations
+//! 和 CFBundleURLTypes，建立"哪些应用声明了 .pdf / zoommtg://"的索引。
+//! 索引缓存为 JSON，按 Info.plist 修改时间增量更新。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::models::associations::{
+    AppAssociations, AssociationHandler, AssociationIndex, AssociationLookup,
+    DocumentTypeClaim, ExportedTypeDeclaration,
+};
+use crate::services::app_service::app_bundle_paths;
+
+/// 常见系统类型标识符对应的扩展名（系统类型不会出现在应用的导出声明中）
+const SYSTEM_TYPES: [(&str, &[&str]); 26] = [
+    ("com.adobe.pdf", &["pdf"]),
+    ("public.plain-text", &["txt", "text"]),
+    ("public.utf8-plain-text", &["txt"]),
+    ("public.rtf", &["rtf"]),
+    ("public.html", &["html", "htm"]),
+    ("public.xml", &["xml"]),
+    ("public.json", &["json"]),
+    ("public.comma-separated-values-text", &["csv"]),
+    ("net.daringfireball.markdown", &["md", "markdown"]),
+    ("public.jpeg", &["jpg", "jpeg"]),
+    ("public.png", &["png"]),
+    ("com.compuserve.gif", &["gif"]),
+    ("public.tiff", &["tif", "tiff"]),
+    ("public.heic", &["heic"]),
+    ("public.svg-image", &["svg"]),
+    ("public.mpeg-4", &["mp4"]),
+    ("com.apple.quicktime-movie", &["mov"]),
+    ("public.mp3", &["mp3"]),
+    ("com.microsoft.word.doc", &["doc"]),
+    ("org.openxmlformats.wordprocessingml.document", &["docx"]),
+    ("org.openxmlformats.spreadsheetml.sheet", &["xlsx"]),
+    ("org.openxmlformats.presentationml.presentation", &["pptx"]),
+    ("public.zip-archive", &["zip"]),
+    ("com.apple.disk-image-udif", &["dmg"]),
+    ("public.python-script", &["py"]),
+    ("public.shell-script", &["sh"]),
+];
+
+/// 泛型类型：声明它们表示"可打开任意文件"，不计入具体类型的处理者和冲突
+const WILDCARD_TYPES: [&str; 5] = ["public.data", "public.item", "public.content", "public.text", "public.folder"];
+
+/// 规范化扩展名（去掉前导点并转为小写）
+fn normalize_extension(extension: &str) -> String {
+    extension.trim().trim_start_matches('.').to_lowercase()
+}
+
+/// 规范化 URL 协议（去掉 :// 后缀并转为小写）
+fn normalize_scheme(scheme: &str) -> String {
+    scheme.trim().trim_end_matches("://").trim_end_matches(':').to_lowercase()
+}
+
+/// 处理优先级排序权重
+fn rank_order(rank: &str) -> u8 {
+    match rank {
+        "Owner" => 0,
+        "Default" => 1,
+        "Alternate" => 2,
+        _ => 3,
+    }
+}
+
+/// 读取 plist 字典中的字符串数组（兼容单个字符串）
+fn string_list(dict: &plist::Dictionary, key: &str) -> Vec<String> {
+    match dict.get(key) {
+        Some(plist::Value::Array(items)) => items.iter()
+            .filter_map(|v| v.as_string())
+            .map(|s| s.to_string())
+            .collect(),
+        Some(plist::Value::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// 文件修改时间（秒）
+fn modified_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 读取应用 Info.plist 中的文件类型与 URL 协议声明
+pub fn read_associations(app_path: &Path) -> Option<AppAssociations> {
+    let info_plist = app_path.join("Contents/Info.plist");
+    let value = plist::Value::from_file(&info_plist).ok()?;
+    let info = value.as_dictionary()?;
+    let info_string = |key: &str| info.get(key).and_then(|v| v.as_string()).map(|s| s.to_string());
+
+    let document_types = info.get("CFBundleDocumentTypes")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items.iter()
+                .filter_map(|v| v.as_dictionary())
+                .map(|doc| DocumentTypeClaim {
+                    name: doc.get("CFBundleTypeName").and_then(|v| v.as_string()).unwrap_or_default().to_string(),
+                    extensions: string_list(doc, "CFBundleTypeExtensions").iter()
+                        .map(|e| normalize_extension(e))
+                        .filter(|e| !e.is_empty())
+                        .collect(),
+                    content_types: string_list(doc, "LSItemContentTypes"),
+                    role: doc.get("CFBundleTypeRole").and_then(|v| v.as_string()).unwrap_or("None").to_string(),
+                    rank: doc.get("LSHandlerRank").and_then(|v| v.as_string()).unwrap_or("Default").to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let exported_types = info.get("UTExportedTypeDeclarations")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items.iter()
+                .filter_map(|v| v.as_dictionary())
+                .filter_map(|decl| {
+                    let identifier = decl.get("UTTypeIdentifier")?.as_string()?.to_string();
+                    let extensions = decl.get("UTTypeTagSpecification")
+                        .and_then(|v| v.as_dictionary())
+                        .map(|tags| string_list(tags, "public.filename-extension"))
+                        .unwrap_or_default();
+                    Some(ExportedTypeDeclaration {
+                        identifier,
+                        description: decl.get("UTTypeDescription").and_then(|v| v.as_string()).unwrap_or_default().to_string(),
+                        extensions: extensions.iter().map(|e| normalize_extension(e)).collect(),
+                        conforms_to: string_list(decl, "UTTypeConformsTo"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut url_schemes: Vec<String> = info.get("CFBundleURLTypes")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items.iter()
+                .filter_map(|v| v.as_dictionary())
+                .flat_map(|url_type| string_list(url_type, "CFBundleURLSchemes"))
+                .map(|s| normalize_scheme(&s))
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    url_schemes.sort();
+    url_schemes.dedup();
+
+    Some(AppAssociations {
+        app_path: app_path.to_string_lossy().to_string(),
+        app_name: info_string("CFBundleDisplayName")
+            .or_else(|| info_string("CFBundleName"))
+            .or_else(|| app_path.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "Unknown".to_string()),
+        bundle_id: info_string("CFBundleIdentifier").unwrap_or_default(),
+        info_modified: modified_secs(&info_plist),
+        document_types,
+        exported_types,
+        url_schemes,
+    })
+}
+
+/// 文件类型与 URL 协议关联服务
+pub struct AssociationService {
+    cache_path: PathBuf,
+}
+
+impl AssociationService {
+    /// 创建新的关联服务实例
+    pub fn new() -> Self {
+        let cache_path = dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("com.mole.app")
+            .join("association_index.json");
+        Self::with_cache_path(cache_path)
+    }
+
+    /// 使用指定缓存文件创建实例
+    pub fn with_cache_path(cache_path: impl Into<PathBuf>) -> Self {
+        AssociationService { cache_path: cache_path.into() }
+    }
+
+    /// 获取单个应用的关联
+    pub fn get_app_associations(&self, app_path: &str) -> Result<AppAssociations, String> {
+        let path = Path::new(app_path);
+        if !path.exists() {
+            return Err("应用不存在".to_string());
+        }
+        read_associations(path).ok_or_else(|| "无法读取应用 Info.plist".to_string())
+    }
+
+    /// 获取已安装应用的关联索引（直接列出应用包路径，Info.plist 未变化的应用复用缓存）
+    pub fn get_index(&self, rebuild: bool) -> Result<AssociationIndex, String> {
+        let app_paths: Vec<String> = app_bundle_paths()
+            .into_iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        self.build_index(&app_paths, rebuild)
+    }
+
+    /// 为指定应用构建关联索引
+    pub fn build_index(&self, app_paths: &[String], rebuild: bool) -> Result<AssociationIndex, String> {
+        let cached: HashMap<String, AppAssociations> = if rebuild {
+            HashMap::new()
+        } else {
+            self.load_cache()
+                .map(|index| index.apps.into_iter().map(|a| (a.app_path.clone(), a)).collect())
+                .unwrap_or_default()
+        };
+
+        let apps: Vec<AppAssociations> = app_paths.iter()
+            .filter_map(|app_path| {
+                let path = Path::new(app_path);
+                let modified = modified_secs(&path.join("Contents/Info.plist"));
+                match cached.get(app_path) {
+                    Some(entry) if entry.info_modified == modified => Some(entry.clone()),
+                    _ => read_associations(path),
+                }
+            })
+            .collect();
+
+        let index = AssociationIndex {
+            apps,
+            built_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+        self.save_cache(&index)?;
+        Ok(index)
+    }
+
+    /// 查找声明了指定扩展名的应用
+    pub fn find_apps_for_extension(&self, extension: &str) -> Result<AssociationLookup, String> {
+        let index = self.get_index(false)?;
+        Ok(lookup_extension(&index, &normalize_extension(extension)))
+    }
+
+    /// 查找注册了指定 URL 协议的应用
+    pub fn find_apps_for_url_scheme(&self, scheme: &str) -> Result<AssociationLookup, String> {
+        let index = self.get_index(false)?;
+        Ok(lookup_url_scheme(&index, &normalize_scheme(scheme)))
+    }
+
+    /// 查找被多个应用同时声明的扩展名和 URL 协议
+    pub fn get_conflicts(&self) -> Result<Vec<AssociationLookup>, String> {
+        let index = self.get_index(false)?;
+        Ok(find_conflicts(&index))
+    }
+
+    /// 读取缓存的索引
+    fn load_cache(&self) -> Option<AssociationIndex> {
+        let content = fs::read_to_string(&self.cache_path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// 保存索引到缓存
+    fn save_cache(&self, index: &AssociationIndex) -> Result<(), String> {
+        if let Some(parent) = self.cache_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("无法创建缓存目录: {}", e))?;
+        }
+        let json = serde_json::to_string(index).map_err(|e| format!("无法序列化关联索引: {}", e))?;
+        fs::write(&self.cache_path, json).map_err(|e| format!("无法保存关联索引: {}", e))
+    }
+}
+
+/// 建立类型标识符到扩展名的映射（系统类型 + 各应用导出的类型）
+fn content_type_extensions(index: &AssociationIndex) -> HashMap<String, Vec<String>> {
+    let mut map: HashMap<String, Vec<String>> = SYSTEM_TYPES.iter()
+        .map(|(uti, extensions)| (uti.to_string(), extensions.iter().map(|e| e.to_string()).collect()))
+        .collect();
+    for app in &index.apps {
+        for exported in &app.exported_types {
+            map.entry(exported.identifier.clone())
+                .or_default()
+                .extend(exported.extensions.iter().cloned());
+        }
+    }
+    map
+}
+
+/// 文档类型声明覆盖的具体扩展名（忽略 "*" 和泛型类型）
+fn claimed_extensions(claim: &DocumentTypeClaim, uti_map: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut extensions: Vec<String> = claim.extensions.iter()
+        .filter(|e| e.as_str() != "*")
+        .cloned()
+        .collect();
+    for content_type in &claim.content_types {
+        if WILDCARD_TYPES.contains(&content_type.as_str()) {
+            continue;
+        }
+        if let Some(mapped) = uti_map.get(content_type) {
+            extensions.extend(mapped.iter().cloned());
+        }
+    }
+    extensions.sort();
+    extensions.dedup();
+    extensions
+}
+
+/// 生成处理者记录
+fn handler(app: &AppAssociations, role: &str, rank: &str) -> AssociationHandler {
+    AssociationHandler {
+        app_path: app.app_path.clone(),
+        app_name: app.app_name.clone(),
+        bundle_id: app.bundle_id.clone(),
+        role: role.to_string(),
+        rank: rank.to_string(),
+    }
+}
+
+/// 按扩展名汇总所有处理者（每个应用取优先级最高的声明）
+fn extension_handlers(index: &AssociationIndex) -> BTreeMap<String, Vec<AssociationHandler>> {
+    let uti_map = content_type_extensions(index);
+    let mut result: BTreeMap<String, Vec<AssociationHandler>> = BTreeMap::new();
+
+    for app in &index.apps {
+        let mut best: BTreeMap<String, &DocumentTypeClaim> = BTreeMap::new();
+        for claim in &app.document_types {
+            for extension in claimed_extensions(claim, &uti_map) {
+                best.entry(extension)
+                    .and_modify(|current| {
+                        if rank_order(&claim.rank) < rank_order(&current.rank) {
+                            *current = claim;
+                        }
+                    })
+                    .or_insert(claim);
+            }
+        }
+        for (extension, claim) in best {
+            result.entry(extension).or_default().push(handler(app, &claim.role, &claim.rank));
+        }
+    }
+
+    for handlers in result.values_mut() {
+        handlers.sort_by(|a, b| {
+            rank_order(&a.rank).cmp(&rank_order(&b.rank))
+                .then_with(|| a.app_name.to_lowercase().cmp(&b.app_name.to_lowercase()))
+        });
+    }
+    result
+}
+
+/// 按 URL 协议汇总所有处理者
+fn url_scheme_handlers(index: &AssociationIndex) -> BTreeMap<String, Vec<AssociationHandler>> {
+    let mut result: BTreeMap<String, Vec<AssociationHandler>> = BTreeMap::new();
+    for app in &index.apps {
+        for scheme in &app.url_schemes {
+            result.entry(scheme.clone()).or_default().push(handler(app, "", ""));
+        }
+    }
+    for handlers in result.values_mut() {
+        handlers.sort_by(|a, b| a.app_name.to_lowercase().cmp(&b.app_name.to_lowercase()));
+    }
+    result
+}
+
+/// 在索引中查找扩展名的处理者
+pub fn lookup_extension(index: &AssociationIndex, extension: &str) -> AssociationLookup {
+    AssociationLookup {
+        key: extension.to_string(),
+        kind: "extension".to_string(),
+        handlers: extension_handlers(index).remove(extension).unwrap_or_default(),
+    }
+}
+
+/// 在索引中查找 URL 协议的处理者
+pub fn lookup_url_scheme(index: &AssociationIndex, scheme: &str) -> AssociationLookup {
+    AssociationLookup {
+        key: scheme.to_string(),
+        kind: "url_scheme".to_string(),
+        handlers: url_scheme_handlers(index).remove(scheme).unwrap_or_default(),
+    }
+}
+
+/// 找出被多个应用声明的扩展名和 URL 协议，按处理者数量降序排列
+pub fn find_conflicts(index: &AssociationIndex) -> Vec<AssociationLookup> {
+    let extensions = extension_handlers(index).into_iter()
+        .map(|(key, handlers)| AssociationLookup { key, kind: "extension".to_string(), handlers });
+    let schemes = url_scheme_handlers(index).into_iter()
+        .map(|(key, handlers)| AssociationLookup { key, kind: "url_scheme".to_string(), handlers });
+
+    let mut conflicts: Vec<AssociationLookup> = extensions.chain(schemes)
+        .filter(|lookup| lookup.handlers.len() > 1)
+        .collect();
+    conflicts.sort_by(|a, b| b.handlers.len().cmp(&a.handlers.len()).then_with(|| a.key.cmp(&b.key)));
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-association-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// 写入带有额外 Info.plist 键的应用，返回应用路径
+    fn write_app(dir: &Path, name: &str, entries: Vec<(&str, plist::Value)>) -> String {
+        let app = dir.join(format!("{}.app", name));
+        fs::create_dir_all(app.join("Contents")).unwrap();
+        let mut info = plist::Dictionary::new();
+        info.insert("CFBundleIdentifier".to_string(), format!("com.example.{}", name.to_lowercase()).into());
+        info.insert("CFBundleName".to_string(), name.into());
+        for (key, value) in entries {
+            info.insert(key.to_string(), value);
+        }
+        plist::Value::Dictionary(info).to_file_xml(app.join("Contents/Info.plist")).unwrap();
+        app.to_string_lossy().to_string()
+    }
+
+    fn dict(entries: Vec<(&str, plist::Value)>) -> plist::Value {
+        plist::Value::Dictionary(entries.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    fn strings(values: &[&str]) -> plist::Value {
+        plist::Value::Array(values.iter().map(|v| (*v).into()).collect())
+    }
+
+    fn document_types(types: Vec<plist::Value>) -> (&'static str, plist::Value) {
+        ("CFBundleDocumentTypes", plist::Value::Array(types))
+    }
+
+    fn url_schemes(schemes: &[&str]) -> (&'static str, plist::Value) {
+        ("CFBundleURLTypes", plist::Value::Array(vec![dict(vec![("CFBundleURLSchemes", strings(schemes))])]))
+    }
+
+    #[test]
+    fn normalizes_extensions_and_schemes() {
+        assert_eq!(normalize_extension(" .PDF "), "pdf");
+        assert_eq!(normalize_extension("tar.gz"), "tar.gz");
+        assert_eq!(normalize_scheme("ZoomMtg://"), "zoommtg");
+        assert_eq!(normalize_scheme("mailto:"), "mailto");
+        assert_eq!(normalize_scheme(" slack "), "slack");
+    }
+
+    #[test]
+    fn reads_and_normalizes_declarations() {
+        let dir = fixture_dir("read");
+        let app = write_app(&dir, "Reader", vec![
+            document_types(vec![dict(vec![
+                ("CFBundleTypeName", "Document".into()),
+                ("CFBundleTypeExtensions", strings(&[".Mole", "PDF"])),
+                ("CFBundleTypeRole", "Editor".into()),
+            ])]),
+            url_schemes(&["Reader", "reader", "READER://"]),
+        ]);
+        let associations = read_associations(Path::new(&app)).unwrap();
+        assert_eq!(associations.document_types[0].extensions, vec!["mole", "pdf"]);
+        assert_eq!(associations.document_types[0].rank, "Default");
+        assert_eq!(associations.url_schemes, vec!["reader"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn finds_handlers_and_conflicts() {
+        let dir = fixture_dir("conflicts");
+        let viewer = write_app(&dir, "Viewer", vec![
+            document_types(vec![dict(vec![
+                ("LSItemContentTypes", strings(&["com.adobe.pdf", "public.data"])),
+                ("CFBundleTypeRole", "Viewer".into()),
+                ("LSHandlerRank", "Alternate".into()),
+            ])]),
+            url_schemes(&["zoommtg", "viewer"]),
+        ]);
+        let editor = write_app(&dir, "Editor", vec![
+            document_types(vec![
+                dict(vec![("CFBundleTypeExtensions", strings(&["pdf", "*"])), ("LSHandlerRank", "Owner".into())]),
+                dict(vec![("LSItemContentTypes", strings(&["com.example.mole"]))]),
+            ]),
+            ("UTExportedTypeDeclarations", plist::Value::Array(vec![dict(vec![
+                ("UTTypeIdentifier", "com.example.mole".into()),
+                ("UTTypeTagSpecification", dict(vec![("public.filename-extension", strings(&["MOLE"]))])),
+            ])])),
+        ]);
+        let meeting = write_app(&dir, "Meeting", vec![url_schemes(&["zoommtg"])]);
+        let service = AssociationService::with_cache_path(dir.join("cache.json"));
+        let index = service.build_index(&[viewer, editor, meeting], false).unwrap();
+
+        let pdf = lookup_extension(&index, "pdf");
+        let names: Vec<&str> = pdf.handlers.iter().map(|h| h.app_name.as_str()).collect();
+        assert_eq!(names, vec!["Editor", "Viewer"]);
+        assert_eq!(pdf.handlers[0].rank, "Owner");
+        assert_eq!(lookup_extension(&index, "mole").handlers.len(), 1);
+        // 通配符和泛型类型不计入具体扩展名
+        assert!(lookup_extension(&index, "*").handlers.is_empty());
+
+        let conflicts = find_conflicts(&index);
+        let keys: Vec<(&str, &str)> = conflicts.iter().map(|c| (c.kind.as_str(), c.key.as_str())).collect();
+        assert_eq!(keys, vec![("extension", "pdf"), ("url_scheme", "zoommtg")]);
+        assert!(conflicts.iter().all(|c| c.handlers.len() == 2));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reuses_cache_until_info_plist_changes() {
+        let dir = fixture_dir("cache");
+        let app = write_app(&dir, "Chat", vec![url_schemes(&["chat"])]);
+        let service = AssociationService::with_cache_path(dir.join("cache.json"));
+        service.build_index(std::slice::from_ref(&app), false).unwrap();
+        assert!(dir.join("cache.json").exists());
+
+        // 内容变化但修改时间不变时复用缓存
+        let info = Path::new(&app).join("Contents/Info.plist");
+        let modified = fs::metadata(&info).unwrap().modified().unwrap();
+        write_app(&dir, "Chat", vec![url_schemes(&["chat", "chat-sso"])]);
+        let file = fs::File::options().write(true).open(&info).unwrap();
+        file.set_modified(modified).unwrap();
+        let index = service.build_index(std::slice::from_ref(&app), false).unwrap();
+        assert_eq!(index.apps[0].url_schemes, vec!["chat"]);
+
+        file.set_modified(modified + std::time::Duration::from_secs(5)).unwrap();
+        let index = service.build_index(std::slice::from_ref(&app), false).unwrap();
+        assert_eq!(index.apps[0].url_schemes, vec!["chat", "chat-sso"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,669 @@
+//! 应用副本生成服务实现
+//!
+//! 原生实现应用包复制和 Info.plist 改写（标识符、名称、辅助程序标识符、URL Scheme），
+//! 只有代码签名通过 CodeSigner 调用外部工具，其余步骤不依赖 macOS 命令。
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+use crate::services::codesign_service::CodeSignService;
+use crate::services::icon_badge_service::{IconBadge, IconBadgeService};
+use crate::services::macho_service;
+
+/// 需要单独签名的嵌套代码包扩展名
+pub const NESTED_CODE_EXTENSIONS: &[&str] = &["app", "framework", "appex", "xpc", "bundle", "plugin"];
+
+/// 副本生成错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleRewriteError {
+    /// 源应用不存在
+    SourceMissing(String),
+    /// 目标路径已存在
+    DestinationExists(String),
+    /// 复制文件失败
+    Copy { path: String, message: String },
+    /// 读取或写入 Info.plist 失败
+    InfoPlist { path: String, message: String },
+    /// 签名失败
+    Sign(String),
+    /// 签名后资源与清单不一致
+    Verify { missing: Vec<String>, added: Vec<String>, altered: Vec<String> },
+}
+
+impl fmt::Display for BundleRewriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleRewriteError::SourceMissing(path) => write!(f, "源应用不存在: {}", path),
+            BundleRewriteError::DestinationExists(path) => write!(f, "目标已存在: {}", path),
+            BundleRewriteError::Copy { path, message } => write!(f, "复制失败: {}: {}", path, message),
+            BundleRewriteError::InfoPlist { path, message } => write!(f, "Info.plist 修改失败: {}: {}", path, message),
+            BundleRewriteError::Sign(message) => write!(f, "签名失败: {}", message),
+            BundleRewriteError::Verify { missing, added, altered } => write!(
+                f, "副本签名校验失败: 缺失 {:?}, 新增 {:?}, 修改 {:?}", missing, added, altered
+            ),
+        }
+    }
+}
+
+/// 代码签名器
+pub trait CodeSigner: Send + Sync {
+    /// 对应用包重新签名
+    fn sign(&self, bundle: &Path) -> Result<(), String>;
+}
+
+/// 应用包内需要签名的嵌套代码（Mach-O 文件和嵌套代码包），按由内向外的顺序排列，不含应用包本身
+pub fn nested_code_paths(bundle: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = WalkDir::new(bundle)
+        .follow_links(false)
+        .min_depth(1)
+        .into_iter()
+        .flatten()
+        .filter(|entry| {
+            if entry.file_type().is_dir() {
+                entry.path().extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| NESTED_CODE_EXTENSIONS.contains(&e))
+            } else {
+                entry.file_type().is_file() && macho_service::read_macho(entry.path()).is_some()
+            }
+        })
+        .map(|entry| entry.into_path())
+        .collect();
+    // 层级越深越先签名，外层代码包签名时记录的是已签名的嵌套代码
+    paths.sort_by_key(|p| std::cmp::Reverse(p.components().count()));
+    paths
+}
+
+/// 使用 codesign 进行临时（ad-hoc）签名
+///
+/// 保留原有的授权、签名要求、标志和 hardened runtime；`--deep` 签名已被弃用，嵌套代码由内向外逐个签名
+pub struct AdHocCodeSigner;
+
+impl AdHocCodeSigner {
+    /// 签名单个文件或代码包
+    fn sign_path(path: &Path) -> Result<(), String> {
+        let output = Command::new("codesign")
+            .args([
+                "--force",
+                "--sign",
+                "-",
+                "--preserve-metadata=entitlements,requirements,flags,runtime",
+            ])
+            .arg(path)
+            .output()
+            .map_err(|e| format!("无法执行 codesign: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "{}: {}",
+                path.to_string_lossy(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl CodeSigner for AdHocCodeSigner {
+    fn sign(&self, bundle: &Path) -> Result<(), String> {
+        for path in nested_code_paths(bundle) {
+            Self::sign_path(&path)?;
+        }
+        Self::sign_path(bundle)
+    }
+}
+
+/// 副本身份
+#[derive(Debug, Clone)]
+pub struct DuplicateIdentity {
+    /// 源应用标识符（辅助程序标识符以它为前缀时一并替换）
+    pub source_bundle_id: String,
+    /// 副本标识符
+    pub bundle_id: String,
+    /// 副本显示名称（写入 CFBundleName 和 CFBundleDisplayName）
+    pub display_name: String,
+    /// URL Scheme 后缀，避免副本抢占源应用的链接
+    pub url_scheme_suffix: String,
+    /// 图标角标，为空时保留源应用图标
+    pub icon_badge: Option<IconBadge>,
+    /// 是否重新签名（可多开应用目录标记无需重新签名的应用跳过签名和校验）
+    pub resign: bool,
+}
+
+/// 读取 plist，返回内容和是否为二进制格式
+fn read_plist(path: &Path) -> Result<(plist::Value, bool), BundleRewriteError> {
+    let error = |message: String| BundleRewriteError::InfoPlist {
+        path: path.to_string_lossy().to_string(),
+        message,
+    };
+    let data = fs::read(path).map_err(|e| error(e.to_string()))?;
+    let value = plist::Value::from_reader(std::io::Cursor::new(&data)).map_err(|e| error(e.to_string()))?;
+    Ok((value, data.starts_with(b"bplist")))
+}
+
+/// 按原格式写回 plist
+fn write_plist(path: &Path, value: &plist::Value, binary: bool) -> Result<(), BundleRewriteError> {
+    let result = if binary {
+        value.to_file_binary(path)
+    } else {
+        value.to_file_xml(path)
+    };
+    result.map_err(|e| BundleRewriteError::InfoPlist {
+        path: path.to_string_lossy().to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// 以源标识符为前缀的标识符替换为副本标识符前缀
+fn rewrite_identifier(identifier: &str, identity: &DuplicateIdentity) -> Option<String> {
+    if identity.source_bundle_id.is_empty() {
+        return None;
+    }
+    if identifier == identity.source_bundle_id {
+        return Some(identity.bundle_id.clone());
+    }
+    identifier.strip_prefix(&format!("{}.", identity.source_bundle_id))
+        .map(|rest| format!("{}.{}", identity.bundle_id, rest))
+}
+
+/// 移除文件的扩展属性（如隔离标记和 Finder 信息，codesign 不允许签名带有这些属性的文件）
+///
+/// 无法移除的属性（如 SELinux 标签）保留，不影响复制
+fn strip_xattrs(path: &Path) {
+    let Ok(names) = xattr::list(path) else {
+        return;
+    };
+    for name in names {
+        let _ = xattr::remove(path, &name);
+    }
+}
+
+/// 复制应用包（保留符号链接和权限，移除扩展属性）
+///
+/// macOS 上 `fs::copy` 会连同扩展属性一起复制，复制后逐个文件移除
+pub fn copy_bundle(source: &Path, dest: &Path) -> Result<(), BundleRewriteError> {
+    if !source.exists() {
+        return Err(BundleRewriteError::SourceMissing(source.to_string_lossy().to_string()));
+    }
+    if fs::symlink_metadata(dest).is_ok() {
+        return Err(BundleRewriteError::DestinationExists(dest.to_string_lossy().to_string()));
+    }
+
+    for entry in WalkDir::new(source).follow_links(false) {
+        let entry = entry.map_err(|e| BundleRewriteError::Copy {
+            path: source.to_string_lossy().to_string(),
+            message: e.to_string(),
+        })?;
+        let relative = entry.path().strip_prefix(source).unwrap_or(entry.path());
+        let target = dest.join(relative);
+        let error = |e: std::io::Error| BundleRewriteError::Copy {
+            path: entry.path().to_string_lossy().to_string(),
+            message: e.to_string(),
+        };
+
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            fs::create_dir_all(&target).map_err(error)?;
+        } else if file_type.is_symlink() {
+            let link = fs::read_link(entry.path()).map_err(error)?;
+            create_symlink(&link, &target).map_err(error)?;
+        } else {
+            fs::copy(entry.path(), &target).map_err(error)?;
+            strip_xattrs(&target);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn create_symlink(link: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(link, target)
+}
+
+#[cfg(not(unix))]
+fn create_symlink(link: &Path, target: &Path) -> std::io::Result<()> {
+    // 非 Unix 平台没有应用包符号链接，按普通文件复制
+    fs::copy(link, target).map(|_| ())
+}
+
+/// 改写主程序 Info.plist: 标识符、名称和 URL Scheme
+pub fn rewrite_main_info_plist(bundle: &Path, identity: &DuplicateIdentity) -> Result<(), BundleRewriteError> {
+    let path = bundle.join("Contents/Info.plist");
+    let (mut value, binary) = read_plist(&path)?;
+    let dict = value.as_dictionary_mut().ok_or_else(|| BundleRewriteError::InfoPlist {
+        path: path.to_string_lossy().to_string(),
+        message: "根节点不是字典".to_string(),
+    })?;
+
+    dict.insert("CFBundleIdentifier".to_string(), identity.bundle_id.clone().into());
+    dict.insert("CFBundleName".to_string(), identity.display_name.clone().into());
+    dict.insert("CFBundleDisplayName".to_string(), identity.display_name.clone().into());
+
+    if let Some(plist::Value::Array(url_types)) = dict.get_mut("CFBundleURLTypes") {
+        for url_type in url_types.iter_mut() {
+            let Some(url_type) = url_type.as_dictionary_mut() else {
+                continue;
+            };
+            if let Some(name) = url_type.get("CFBundleURLName").and_then(|v| v.as_string()) {
+                if let Some(renamed) = rewrite_identifier(name, identity) {
+                    url_type.insert("CFBundleURLName".to_string(), renamed.into());
+                }
+            }
+            if let Some(plist::Value::Array(schemes)) = url_type.get_mut("CFBundleURLSchemes") {
+                for scheme in schemes.iter_mut() {
+                    if let Some(s) = scheme.as_string() {
+                        *scheme = format!("{}{}", s, identity.url_scheme_suffix).into();
+                    }
+                }
+            }
+        }
+    }
+
+    write_plist(&path, &value, binary)
+}
+
+/// 改写 Contents/Frameworks 和 Contents/Library/LoginItems 中辅助程序的标识符
+///
+/// 返回被修改的 Info.plist 路径
+pub fn rewrite_helper_info_plists(bundle: &Path, identity: &DuplicateIdentity) -> Result<Vec<PathBuf>, BundleRewriteError> {
+    let mut rewritten = Vec::new();
+    for dir in [bundle.join("Contents/Frameworks"), bundle.join("Contents/Library/LoginItems")] {
+        for entry in WalkDir::new(&dir).follow_links(false).into_iter().flatten() {
+            if !entry.file_type().is_file() || entry.file_name() != "Info.plist" {
+                continue;
+            }
+            let path = entry.path();
+            let (mut value, binary) = read_plist(path)?;
+            let Some(dict) = value.as_dictionary_mut() else {
+                continue;
+            };
+            let renamed = dict.get("CFBundleIdentifier")
+                .and_then(|v| v.as_string())
+                .and_then(|id| rewrite_identifier(id, identity));
+            if let Some(renamed) = renamed {
+                dict.insert("CFBundleIdentifier".to_string(), renamed.into());
+                write_plist(path, &value, binary)?;
+                rewritten.push(path.to_path_buf());
+            }
+        }
+    }
+    Ok(rewritten)
+}
+
+/// 应用副本生成服务
+pub struct BundleRewriteService {
+    signer: Box<dyn CodeSigner>,
+}
+
+impl BundleRewriteService {
+    /// 创建新的副本生成服务实例（使用 codesign 临时签名）
+    pub fn new() -> Self {
+        Self::with_signer(Box::new(AdHocCodeSigner))
+    }
+
+    /// 使用指定签名器创建实例
+    pub fn with_signer(signer: Box<dyn CodeSigner>) -> Self {
+        BundleRewriteService { signer }
+    }
+
+    /// 复制应用包并应用副本身份
+    pub fn create_copy(
+        &self,
+        source: &Path,
+        dest: &Path,
+        identity: &DuplicateIdentity,
+        steps: &mut Vec<String>,
+    ) -> Result<(), BundleRewriteError> {
+        steps.push("正在复制应用文件...".to_string());
+        if let Err(e) = copy_bundle(source, dest) {
+            // 不留下复制了一半的副本
+            if !matches!(e, BundleRewriteError::DestinationExists(_)) {
+                let _ = fs::remove_dir_all(dest);
+            }
+            return Err(e);
+        }
+        steps.push("复制完成".to_string());
+
+        // 改写、签名或校验失败时同样删除副本
+        if let Err(e) = self.apply_identity(dest, identity, steps) {
+            let _ = fs::remove_dir_all(dest);
+            steps.push("已删除未完成的副本".to_string());
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// 改写 Info.plist、重新签名并校验签名资源
+    pub fn apply_identity(
+        &self,
+        bundle: &Path,
+        identity: &DuplicateIdentity,
+        steps: &mut Vec<String>,
+    ) -> Result<(), BundleRewriteError> {
+        steps.push(format!("修改标识符为: {}，名称为: {}", identity.bundle_id, identity.display_name));
+        rewrite_main_info_plist(bundle, identity)?;
+        let helpers = rewrite_helper_info_plists(bundle, identity)?;
+        if !helpers.is_empty() {
+            steps.push(format!("已修改 {} 个辅助程序的标识符", helpers.len()));
+        }
+
+        // 图标生成失败不影响副本使用
+        if let Some(badge) = &identity.icon_badge {
+            match IconBadgeService::new().badge_bundle_icon(bundle, badge) {
+                Ok(_) => steps.push("已生成带角标的图标".to_string()),
+                Err(e) => steps.push(format!("图标生成失败，保留原图标: {}", e)),
+            }
+        }
+
+        if !identity.resign {
+            steps.push("该应用无需重新签名，跳过签名".to_string());
+            return Ok(());
+        }
+
+        steps.push("重新签名应用...".to_string());
+        self.signer.sign(bundle).map_err(BundleRewriteError::Sign)?;
+        steps.push("签名成功".to_string());
+
+        // 校验副本资源是否与签名一致
+        steps.push("校验签名资源...".to_string());
+        match CodeSignService::new().verify_bundle(&bundle.to_string_lossy()) {
+            Ok(verification) if !verification.signed => {
+                steps.push("副本没有签名资源清单，跳过校验".to_string());
+            }
+            Ok(verification) if verification.valid => {
+                steps.push(format!("签名资源校验通过 ({} 个资源)", verification.checked_count));
+            }
+            Ok(verification) => {
+                return Err(BundleRewriteError::Verify {
+                    missing: verification.missing,
+                    added: verification.added,
+                    altered: verification.altered,
+                });
+            }
+            Err(e) => {
+                steps.push(format!("签名资源校验失败: {}", e));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// 记录调用的签名器，`fail` 为 true 时签名失败
+    struct FakeSigner {
+        calls: Arc<Mutex<Vec<PathBuf>>>,
+        fail: bool,
+    }
+
+    impl CodeSigner for FakeSigner {
+        fn sign(&self, bundle: &Path) -> Result<(), String> {
+            self.calls.lock().unwrap().push(bundle.to_path_buf());
+            if self.fail {
+                Err("fake signer failed".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-rewrite-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn identity() -> DuplicateIdentity {
+        DuplicateIdentity {
+            source_bundle_id: "com.example.chat".to_string(),
+            bundle_id: "com.example.chat_2".to_string(),
+            display_name: "Chat 2".to_string(),
+            url_scheme_suffix: "2".to_string(),
+            icon_badge: None,
+            resign: true,
+        }
+    }
+
+    fn write_info(path: &Path, entries: Vec<(&str, plist::Value)>, binary: bool) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let dict: plist::Dictionary = entries.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        write_plist(path, &plist::Value::Dictionary(dict), binary).unwrap();
+    }
+
+    fn read_identifier(path: &Path) -> String {
+        read_plist(path).unwrap().0
+            .as_dictionary().unwrap()
+            .get("CFBundleIdentifier").unwrap()
+            .as_string().unwrap()
+            .to_string()
+    }
+
+    /// 构造含二进制 Info.plist、URL Scheme、辅助程序、登录项和框架符号链接的应用
+    fn build_app(dir: &Path) -> PathBuf {
+        let app = dir.join("Chat.app");
+        let mut url_type = plist::Dictionary::new();
+        url_type.insert("CFBundleURLName".to_string(), "com.example.chat.url".into());
+        url_type.insert("CFBundleURLSchemes".to_string(), plist::Value::Array(vec!["chat".into(), "chat-sso".into()]));
+        write_info(&app.join("Contents/Info.plist"), vec![
+            ("CFBundleIdentifier", "com.example.chat".into()),
+            ("CFBundleName", "Chat".into()),
+            ("CFBundleExecutable", "Chat".into()),
+            ("CFBundleURLTypes", plist::Value::Array(vec![plist::Value::Dictionary(url_type)])),
+        ], true);
+        fs::create_dir_all(app.join("Contents/MacOS")).unwrap();
+        fs::write(app.join("Contents/MacOS/Chat"), b"binary").unwrap();
+
+        write_info(
+            &app.join("Contents/Frameworks/Chat Helper.app/Contents/Info.plist"),
+            vec![("CFBundleIdentifier", "com.example.chat.helper".into())],
+            false,
+        );
+        write_info(
+            &app.join("Contents/Library/LoginItems/Launcher.app/Contents/Info.plist"),
+            vec![("CFBundleIdentifier", "com.example.chat.launcher".into())],
+            false,
+        );
+        write_info(
+            &app.join("Contents/Frameworks/Kit.framework/Versions/A/Resources/Info.plist"),
+            vec![("CFBundleIdentifier", "org.vendor.kit".into())],
+            false,
+        );
+        create_symlink(Path::new("A"), &app.join("Contents/Frameworks/Kit.framework/Versions/Current")).unwrap();
+        create_symlink(
+            Path::new("Versions/Current/Resources"),
+            &app.join("Contents/Frameworks/Kit.framework/Resources"),
+        ).unwrap();
+        app
+    }
+
+    fn service(fail: bool) -> (BundleRewriteService, Arc<Mutex<Vec<PathBuf>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let signer = FakeSigner { calls: Arc::clone(&calls), fail };
+        (BundleRewriteService::with_signer(Box::new(signer)), calls)
+    }
+
+    #[test]
+    fn rewrites_identifiers_with_source_prefix() {
+        let identity = identity();
+        assert_eq!(rewrite_identifier("com.example.chat", &identity).as_deref(), Some("com.example.chat_2"));
+        assert_eq!(rewrite_identifier("com.example.chat.helper", &identity).as_deref(), Some("com.example.chat_2.helper"));
+        assert_eq!(rewrite_identifier("com.example.chatter", &identity), None);
+        assert_eq!(rewrite_identifier("org.vendor.kit", &identity), None);
+
+        let no_source = DuplicateIdentity { source_bundle_id: String::new(), ..identity };
+        assert_eq!(rewrite_identifier("com.example.chat", &no_source), None);
+    }
+
+    #[test]
+    fn rewrites_main_info_plist_in_original_format() {
+        let dir = fixture_dir("main");
+        let app = build_app(&dir);
+        rewrite_main_info_plist(&app, &identity()).unwrap();
+
+        let path = app.join("Contents/Info.plist");
+        assert!(fs::read(&path).unwrap().starts_with(b"bplist"));
+        let (value, binary) = read_plist(&path).unwrap();
+        assert!(binary);
+        let dict = value.as_dictionary().unwrap();
+        assert_eq!(dict["CFBundleIdentifier"].as_string(), Some("com.example.chat_2"));
+        assert_eq!(dict["CFBundleName"].as_string(), Some("Chat 2"));
+        assert_eq!(dict["CFBundleDisplayName"].as_string(), Some("Chat 2"));
+        assert_eq!(dict["CFBundleExecutable"].as_string(), Some("Chat"));
+
+        let url_type = dict["CFBundleURLTypes"].as_array().unwrap()[0].as_dictionary().unwrap();
+        assert_eq!(url_type["CFBundleURLName"].as_string(), Some("com.example.chat_2.url"));
+        let schemes: Vec<&str> = url_type["CFBundleURLSchemes"].as_array().unwrap()
+            .iter()
+            .filter_map(|s| s.as_string())
+            .collect();
+        assert_eq!(schemes, vec!["chat2", "chat-sso2"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rewrites_only_helpers_with_source_prefix() {
+        let dir = fixture_dir("helpers");
+        let app = build_app(&dir);
+        let mut rewritten = rewrite_helper_info_plists(&app, &identity()).unwrap();
+        rewritten.sort();
+        assert_eq!(rewritten, vec![
+            app.join("Contents/Frameworks/Chat Helper.app/Contents/Info.plist"),
+            app.join("Contents/Library/LoginItems/Launcher.app/Contents/Info.plist"),
+        ]);
+        assert_eq!(read_identifier(&rewritten[0]), "com.example.chat_2.helper");
+        assert_eq!(read_identifier(&rewritten[1]), "com.example.chat_2.launcher");
+        assert_eq!(
+            read_identifier(&app.join("Contents/Frameworks/Kit.framework/Versions/A/Resources/Info.plist")),
+            "org.vendor.kit"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn copies_symlinks_as_links() {
+        let dir = fixture_dir("copy");
+        let app = build_app(&dir);
+        let dest = dir.join("Chat 2.app");
+        copy_bundle(&app, &dest).unwrap();
+
+        let current = dest.join("Contents/Frameworks/Kit.framework/Versions/Current");
+        assert!(fs::symlink_metadata(&current).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_link(&current).unwrap(), Path::new("A"));
+        let resources = dest.join("Contents/Frameworks/Kit.framework/Resources");
+        assert_eq!(fs::read_link(&resources).unwrap(), Path::new("Versions/Current/Resources"));
+        assert!(resources.join("Info.plist").is_file());
+        assert_eq!(fs::read(dest.join("Contents/MacOS/Chat")).unwrap(), b"binary");
+
+        assert!(matches!(copy_bundle(&app, &dest), Err(BundleRewriteError::DestinationExists(_))));
+        assert!(matches!(
+            copy_bundle(&dir.join("Missing.app"), &dir.join("Other.app")),
+            Err(BundleRewriteError::SourceMissing(_))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn lists_nested_code_inside_out() {
+        let dir = fixture_dir("nested");
+        let app = build_app(&dir);
+        let mut macho = Vec::new();
+        for v in [0xfeedfacfu32, 0x0100000c, 0, 2, 0, 0, 0, 0] {
+            macho.extend_from_slice(&v.to_le_bytes());
+        }
+        macho.resize(64, 0);
+        fs::write(app.join("Contents/MacOS/Chat"), &macho).unwrap();
+        fs::write(app.join("Contents/Frameworks/Kit.framework/Versions/A/Kit"), &macho).unwrap();
+
+        let paths = nested_code_paths(&app);
+        let position = |relative: &str| paths.iter().position(|p| *p == app.join(relative)).unwrap();
+        assert!(
+            position("Contents/Frameworks/Kit.framework/Versions/A/Kit")
+                < position("Contents/Frameworks/Kit.framework")
+        );
+        position("Contents/MacOS/Chat");
+        position("Contents/Library/LoginItems/Launcher.app");
+        assert!(!paths.contains(&app));
+        assert!(!paths.contains(&app.join("Contents/Info.plist")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn strips_extended_attributes_from_copies() {
+        let dir = fixture_dir("xattr");
+        let app = build_app(&dir);
+        let binary = app.join("Contents/MacOS/Chat");
+        // 文件系统不支持用户扩展属性时跳过
+        if xattr::set(&binary, "user.mole.test", b"1").is_err() {
+            fs::remove_dir_all(&dir).unwrap();
+            return;
+        }
+        let dest = dir.join("Chat 2.app");
+        copy_bundle(&app, &dest).unwrap();
+        let names: Vec<_> = xattr::list(dest.join("Contents/MacOS/Chat")).unwrap().collect();
+        assert!(!names.iter().any(|n| n == "user.mole.test"), "{:?}", names);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn creates_signed_copy() {
+        let dir = fixture_dir("create");
+        let app = build_app(&dir);
+        let dest = dir.join("Chat 2.app");
+        let (service, calls) = service(false);
+        let mut steps = Vec::new();
+        service.create_copy(&app, &dest, &identity(), &mut steps).unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), vec![dest.clone()]);
+        assert_eq!(read_identifier(&dest.join("Contents/Info.plist")), "com.example.chat_2");
+        assert_eq!(read_identifier(&app.join("Contents/Info.plist")), "com.example.chat");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn skips_signing_when_resign_is_not_required() {
+        let dir = fixture_dir("no-resign");
+        let app = build_app(&dir);
+        let dest = dir.join("Chat 2.app");
+        let (service, calls) = service(true);
+        let identity = DuplicateIdentity { resign: false, ..identity() };
+        service.create_copy(&app, &dest, &identity, &mut Vec::new()).unwrap();
+
+        assert!(calls.lock().unwrap().is_empty());
+        assert_eq!(read_identifier(&dest.join("Contents/Info.plist")), "com.example.chat_2");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn removes_copy_when_signing_fails() {
+        let dir = fixture_dir("sign-fail");
+        let app = build_app(&dir);
+        let dest = dir.join("Chat 2.app");
+        let (service, calls) = service(true);
+        let error = service.create_copy(&app, &dest, &identity(), &mut Vec::new()).unwrap_err();
+
+        assert_eq!(error, BundleRewriteError::Sign("fake signer failed".to_string()));
+        assert_eq!(calls.lock().unwrap().len(), 1);
+        assert!(fs::symlink_metadata(&dest).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn removes_copy_when_info_plist_is_invalid() {
+        let dir = fixture_dir("plist-fail");
+        let app = build_app(&dir);
+        fs::write(app.join("Contents/Info.plist"), b"not a plist").unwrap();
+        let dest = dir.join("Chat 2.app");
+        let (service, calls) = service(false);
+        let error = service.create_copy(&app, &dest, &identity(), &mut Vec::new()).unwrap_err();
+
+        assert!(matches!(error, BundleRewriteError::InfoPlist { .. }));
+        assert!(calls.lock().unwrap().is_empty());
+        assert!(fs::symlink_metadata(&dest).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,274 @@
+//! 应用包大小构成分析服务实现
+//!
+//! 按主程序、框架、资源、本地化、插件和辅助应用拆分应用包大小，
+//! 并统计硬链接共享和内容重复的文件。
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+use crate::models::bundle_size::{AppSizeBreakdown, DuplicateFileGroup, SizeComponent};
+use crate::services::localization_service::LocalizationService;
+
+/// 参与重复文件检测的最小文件大小（过小的文件意义不大）
+const DUPLICATE_MIN_SIZE: u64 = 4096;
+
+/// 文件在应用包中的归类
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundlePart {
+    /// 主程序
+    Executable,
+    /// 框架（名称）
+    Framework(String),
+    /// 资源
+    Resource,
+    /// 本地化资源
+    Localization,
+    /// 插件（相对 Contents 的路径）
+    Plugin(String),
+    /// 辅助应用（相对 Contents 的路径）
+    HelperApp(String),
+    /// 其他
+    Other,
+}
+
+/// 根据相对 Contents 的路径对文件归类
+pub fn classify(relative: &Path) -> BundlePart {
+    let parts: Vec<String> = relative.components()
+        .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    let Some(top) = parts.first() else {
+        return BundlePart::Other;
+    };
+    let second = parts.get(1);
+
+    match top.as_str() {
+        "MacOS" => BundlePart::Executable,
+        "Frameworks" => match second {
+            Some(name) if name.ends_with(".app") => BundlePart::HelperApp(format!("Frameworks/{}", name)),
+            Some(name) => BundlePart::Framework(name.clone()),
+            None => BundlePart::Other,
+        },
+        "Resources" | "SharedSupport" => {
+            if parts.iter().any(|p| p.ends_with(".lproj")) {
+                BundlePart::Localization
+            } else {
+                BundlePart::Resource
+            }
+        }
+        "Helpers" => match second {
+            Some(name) => BundlePart::HelperApp(format!("Helpers/{}", name)),
+            None => BundlePart::Other,
+        },
+        "PlugIns" | "XPCServices" => match second {
+            Some(name) => BundlePart::Plugin(format!("{}/{}", top, name)),
+            None => BundlePart::Other,
+        },
+        "Library" => {
+            // Library/LoginItems/X.app, Library/QuickLook/X.qlgenerator 等
+            match parts.get(2) {
+                Some(name) => {
+                    let component = format!("Library/{}/{}", parts[1], name);
+                    if name.ends_with(".app") {
+                        BundlePart::HelperApp(component)
+                    } else {
+                        BundlePart::Plugin(component)
+                    }
+                }
+                None => BundlePart::Other,
+            }
+        }
+        _ => BundlePart::Other,
+    }
+}
+
+/// 计算文件内容的 SHA-256
+fn hash_file(path: &Path) -> Option<Vec<u8>> {
+    let mut file = File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).ok()?;
+    Some(hasher.finalize().to_vec())
+}
+
+/// 应用包大小构成分析服务
+pub struct BundleSizeService;
+
+impl BundleSizeService {
+    /// 创建新的应用包大小分析服务实例
+    pub fn new() -> Self {
+        BundleSizeService
+    }
+
+    /// 分析应用包大小构成
+    pub fn get_size_breakdown(&self, app_path: &str) -> Result<AppSizeBreakdown, String> {
+        let bundle = Path::new(app_path);
+        if !bundle.exists() {
+            return Err("应用不存在".to_string());
+        }
+        let contents = bundle.join("Contents");
+
+        let mut breakdown = AppSizeBreakdown {
+            app_path: app_path.to_string(),
+            total_size: 0,
+            file_count: 0,
+            executables_size: 0,
+            frameworks_size: 0,
+            frameworks: Vec::new(),
+            resources_size: 0,
+            localizations_size: 0,
+            unused_localizations_size: 0,
+            plugins_size: 0,
+            plugins: Vec::new(),
+            helper_apps_size: 0,
+            helper_apps: Vec::new(),
+            other_size: 0,
+            hardlinked_size: 0,
+            duplicate_size: 0,
+            duplicate_groups: Vec::new(),
+        };
+
+        let mut frameworks: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+        let mut plugins: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+        let mut helper_apps: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+        let mut seen_inodes: HashSet<(u64, u64)> = HashSet::new();
+        let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+
+        for entry in WalkDir::new(bundle).follow_links(false).into_iter().flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let size = metadata.len();
+
+            // 硬链接的文件只计算一次
+            if let Some(inode) = hardlink_inode(&metadata) {
+                if !seen_inodes.insert(inode) {
+                    breakdown.hardlinked_size += size;
+                    continue;
+                }
+            }
+
+            breakdown.total_size += size;
+            breakdown.file_count += 1;
+            if size >= DUPLICATE_MIN_SIZE {
+                by_size.entry(size).or_default().push(entry.path().to_path_buf());
+            }
+
+            let relative = entry.path().strip_prefix(&contents).unwrap_or(Path::new(""));
+            match classify(relative) {
+                BundlePart::Executable => breakdown.executables_size += size,
+                BundlePart::Framework(name) => add_component(&mut frameworks, name, size),
+                BundlePart::Resource => breakdown.resources_size += size,
+                BundlePart::Localization => breakdown.localizations_size += size,
+                BundlePart::Plugin(name) => add_component(&mut plugins, name, size),
+                BundlePart::HelperApp(name) => add_component(&mut helper_apps, name, size),
+                BundlePart::Other => breakdown.other_size += size,
+            }
+        }
+
+        breakdown.frameworks = into_components(frameworks, &contents);
+        breakdown.frameworks_size = breakdown.frameworks.iter().map(|c| c.size).sum();
+        breakdown.plugins = into_components(plugins, &contents);
+        breakdown.plugins_size = breakdown.plugins.iter().map(|c| c.size).sum();
+        breakdown.helper_apps = into_components(helper_apps, &contents);
+        breakdown.helper_apps_size = breakdown.helper_apps.iter().map(|c| c.size).sum();
+
+        breakdown.duplicate_groups = self.find_duplicates(by_size);
+        breakdown.duplicate_size = breakdown.duplicate_groups.iter()
+            .map(|g| g.size * (g.paths.len() as u64 - 1))
+            .sum();
+
+        // 未使用语言只统计主资源目录中的本地化
+        let localizations = LocalizationService::new().analyze_app(app_path);
+        let resources_prefix = contents.join("Resources");
+        breakdown.unused_localizations_size = localizations.localizations.iter()
+            .filter(|l| !l.is_used && Path::new(&l.path).starts_with(&resources_prefix))
+            .map(|l| l.size)
+            .sum();
+
+        Ok(breakdown)
+    }
+
+    /// 在大小相同的文件中比对内容，找出重复文件组
+    fn find_duplicates(&self, by_size: HashMap<u64, Vec<PathBuf>>) -> Vec<DuplicateFileGroup> {
+        let mut groups = Vec::new();
+
+        for (size, paths) in by_size {
+            if paths.len() < 2 {
+                continue;
+            }
+            let mut by_hash: HashMap<Vec<u8>, Vec<String>> = HashMap::new();
+            for path in paths {
+                if let Some(hash) = hash_file(&path) {
+                    by_hash.entry(hash).or_default().push(path.to_string_lossy().to_string());
+                }
+            }
+            for (_, mut paths) in by_hash {
+                if paths.len() > 1 {
+                    paths.sort();
+                    groups.push(DuplicateFileGroup { size, paths });
+                }
+            }
+        }
+
+        groups.sort_by(|a, b| {
+            let wasted_a = a.size * (a.paths.len() as u64 - 1);
+            let wasted_b = b.size * (b.paths.len() as u64 - 1);
+            wasted_b.cmp(&wasted_a)
+        });
+        groups
+    }
+}
+
+/// 累加组件大小
+fn add_component(components: &mut BTreeMap<String, (u64, u64)>, name: String, size: u64) {
+    let entry = components.entry(name).or_insert((0, 0));
+    entry.0 += size;
+    entry.1 += 1;
+}
+
+/// 转换为按大小排序的组件列表
+fn into_components(components: BTreeMap<String, (u64, u64)>, contents: &Path) -> Vec<SizeComponent> {
+    let mut result: Vec<SizeComponent> = components.into_iter()
+        .map(|(relative, (size, file_count))| {
+            let path = if relative.contains('/') {
+                contents.join(&relative)
+            } else {
+                contents.join("Frameworks").join(&relative)
+            };
+            SizeComponent {
+                name: Path::new(&relative).file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or(relative.clone()),
+                path: path.to_string_lossy().to_string(),
+                size,
+                file_count,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| b.size.cmp(&a.size));
+    result
+}
+
+/// 获取存在多个硬链接的文件的 (设备号, inode)
+#[cfg(unix)]
+fn hardlink_inode(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    if metadata.nlink() > 1 {
+        Some((metadata.dev(), metadata.ino()))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn hardlink_inode(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
@@ -4,6 +4,8 @@ use std::fs;
 use std::path::Path;
 use std::process::Command;
 use crate::models::cleaner::{CleanItem, CleanPlanPreview, CleanResult};
+use crate::services::homebrew_service::HomebrewService;
+use crate::services::developer_storage_service::{self, DeveloperStorageService};
 
 /// 清理服务
 pub struct CleanerService;
@@ -388,6 +390,12 @@ impl CleanerService {
                 "temp" => {
                     items.extend(self.scan_temp());
                 }
+                "homebrew" => {
+                    items.extend(HomebrewService::new().scan_clean_items());
+                }
+                "developer" => {
+                    items.extend(DeveloperStorageService::new().scan_clean_items());
+                }
                 _ => {}
             }
         }
@@ -411,12 +419,16 @@ impl CleanerService {
 
         for item in items {
             let path = Path::new(&item.path);
-            
-            let result = if path.is_dir() {
-                fs::remove_dir_all(path)
-            } else {
-                fs::remove_file(path)
-            };
+
+            // 模拟器设备和运行时交给 simctl 删除，其余直接删除路径
+            let result = developer_storage_service::simctl_delete(path).unwrap_or_else(|| {
+                if path.is_dir() {
+                    fs::remove_dir_all(path)
+                } else {
+                    fs::remove_file(path)
+                }
+                .map_err(|e| e.to_string())
+            });
 
             match result {
                 Ok(_) => {
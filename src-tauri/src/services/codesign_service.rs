@@ -0,0 +1,394 @@
+//! 代码签名资源校验服务实现
+//!
+//! 解析 `_CodeSignature/CodeResources`，重新计算所列资源的 SHA-1/SHA-256，
+//! 找出签名后被删除、新增或修改的文件。只校验资源封存，不校验主程序的代码目录。
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use regex::Regex;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+use crate::models::codesign::SignatureVerification;
+
+/// 内嵌代码包的最大校验层级
+const MAX_NESTED_DEPTH: usize = 8;
+
+/// CodeResources 中的资源规则
+struct ResourceRule {
+    pattern: Regex,
+    weight: f64,
+    omit: bool,
+}
+
+/// CodeResources 中记录的单个资源
+enum SealedResource {
+    /// 普通文件（SHA-1, SHA-256, 是否可选）
+    File { sha1: Option<Vec<u8>>, sha256: Option<Vec<u8>>, optional: bool },
+    /// 符号链接（目标路径）
+    Symlink(String),
+    /// 内嵌代码包（由其自身签名保护）
+    Nested,
+}
+
+/// 解析后的 CodeResources
+struct CodeResources {
+    resources: Vec<(String, SealedResource)>,
+    rules: Vec<ResourceRule>,
+}
+
+impl CodeResources {
+    /// 读取并解析 CodeResources 文件，优先使用 files2/rules2
+    fn parse(path: &Path) -> Result<Self, String> {
+        let value = plist::Value::from_file(path)
+            .map_err(|e| format!("无法解析 CodeResources: {}", e))?;
+        let dict = value.as_dictionary()
+            .ok_or_else(|| "CodeResources 格式错误".to_string())?;
+
+        let files = dict.get("files2")
+            .or_else(|| dict.get("files"))
+            .and_then(|v| v.as_dictionary())
+            .ok_or_else(|| "CodeResources 中没有资源列表".to_string())?;
+
+        let mut resources = Vec::new();
+        for (name, entry) in files {
+            let resource = if let Some(data) = entry.as_data() {
+                SealedResource::File { sha1: Some(data.to_vec()), sha256: None, optional: false }
+            } else if let Some(entry) = entry.as_dictionary() {
+                if let Some(target) = entry.get("symlink").and_then(|v| v.as_string()) {
+                    SealedResource::Symlink(target.to_string())
+                } else if entry.contains_key("cdhash") || entry.contains_key("requirement") {
+                    SealedResource::Nested
+                } else {
+                    SealedResource::File {
+                        sha1: entry.get("hash").and_then(|v| v.as_data()).map(|d| d.to_vec()),
+                        sha256: entry.get("hash2").and_then(|v| v.as_data()).map(|d| d.to_vec()),
+                        optional: entry.get("optional").and_then(|v| v.as_boolean()).unwrap_or(false),
+                    }
+                }
+            } else {
+                continue;
+            };
+            resources.push((name.clone(), resource));
+        }
+
+        let mut rules = Vec::new();
+        if let Some(rule_dict) = dict.get("rules2").or_else(|| dict.get("rules")).and_then(|v| v.as_dictionary()) {
+            for (pattern, rule) in rule_dict {
+                let Ok(pattern) = Regex::new(pattern) else {
+                    continue;
+                };
+                let (weight, omit) = match rule.as_dictionary() {
+                    Some(rule) => (
+                        rule.get("weight").and_then(|v| v.as_real().or_else(|| v.as_signed_integer().map(|i| i as f64))).unwrap_or(1.0),
+                        rule.get("omit").and_then(|v| v.as_boolean()).unwrap_or(false),
+                    ),
+                    None => (1.0, false),
+                };
+                rules.push(ResourceRule { pattern, weight, omit });
+            }
+        }
+
+        Ok(CodeResources { resources, rules })
+    }
+
+    /// 找出对路径生效的规则（权重最高者）
+    fn matching_rule(&self, relative: &str) -> Option<&ResourceRule> {
+        self.rules.iter()
+            .filter(|r| r.pattern.is_match(relative))
+            .max_by(|a, b| a.weight.partial_cmp(&b.weight).unwrap_or(std::cmp::Ordering::Equal))
+    }
+}
+
+/// 计算文件摘要
+fn file_digest<D: Digest + io::Write>(path: &Path) -> Option<Vec<u8>> {
+    let mut file = File::open(path).ok()?;
+    let mut hasher = D::new();
+    io::copy(&mut file, &mut hasher).ok()?;
+    Some(hasher.finalize().to_vec())
+}
+
+/// 代码签名资源校验服务
+pub struct CodeSignService;
+
+impl CodeSignService {
+    /// 创建新的签名校验服务实例
+    pub fn new() -> Self {
+        CodeSignService
+    }
+
+    /// 校验应用包资源是否与签名一致
+    pub fn verify_bundle(&self, bundle_path: &str) -> Result<SignatureVerification, String> {
+        let bundle = Path::new(bundle_path);
+        if !bundle.exists() {
+            return Err("应用不存在".to_string());
+        }
+
+        let mut result = SignatureVerification {
+            bundle_path: bundle_path.to_string(),
+            signed: false,
+            valid: false,
+            checked_count: 0,
+            nested_count: 0,
+            missing: Vec::new(),
+            added: Vec::new(),
+            altered: Vec::new(),
+            unverified: Vec::new(),
+        };
+
+        let Some(base) = signature_base(bundle) else {
+            return Ok(result);
+        };
+        result.signed = true;
+
+        self.verify_sealed_dir(bundle, &base, &mut result, 0)?;
+
+        result.missing.sort();
+        result.added.sort();
+        result.altered.sort();
+        result.valid = result.missing.is_empty()
+            && result.added.is_empty()
+            && result.altered.is_empty()
+            && result.unverified.is_empty();
+        Ok(result)
+    }
+
+    /// 校验一个签名根目录（应用的 Contents 或框架的 Versions/X）
+    fn verify_sealed_dir(
+        &self,
+        bundle_root: &Path,
+        base: &Path,
+        result: &mut SignatureVerification,
+        depth: usize,
+    ) -> Result<(), String> {
+        let code_resources = CodeResources::parse(&base.join("_CodeSignature/CodeResources"))?;
+        let display = |relative: &str| -> String {
+            base.join(relative)
+                .strip_prefix(bundle_root)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| relative.to_string())
+        };
+
+        let mut listed: HashSet<String> = HashSet::new();
+        let mut nested_roots: Vec<String> = Vec::new();
+
+        for (relative, resource) in &code_resources.resources {
+            listed.insert(relative.clone());
+            let path = base.join(relative);
+
+            match resource {
+                SealedResource::Symlink(target) => {
+                    result.checked_count += 1;
+                    match fs::read_link(&path) {
+                        Ok(actual) if actual.to_string_lossy() == target.as_str() => {}
+                        Ok(_) => result.altered.push(display(relative)),
+                        Err(_) => result.missing.push(display(relative)),
+                    }
+                }
+                SealedResource::Nested => {
+                    result.nested_count += 1;
+                    nested_roots.push(format!("{}/", relative));
+                    if !path.exists() {
+                        result.missing.push(display(relative));
+                        continue;
+                    }
+                    // 内嵌代码包有自己的签名资源清单时递归校验，单个包无法校验时记录后继续
+                    if depth < MAX_NESTED_DEPTH {
+                        if let Some(nested_base) = signature_base(&path) {
+                            if let Err(e) = self.verify_sealed_dir(bundle_root, &nested_base, result, depth + 1) {
+                                result.unverified.push(format!("{}: {}", display(relative), e));
+                            }
+                        }
+                    }
+                }
+                SealedResource::File { sha1, sha256, optional } => {
+                    if !path.is_file() {
+                        if !optional {
+                            result.missing.push(display(relative));
+                        }
+                        continue;
+                    }
+                    result.checked_count += 1;
+                    let matches = if let Some(expected) = sha256 {
+                        file_digest::<Sha256>(&path).as_ref() == Some(expected)
+                    } else if let Some(expected) = sha1 {
+                        file_digest::<Sha1>(&path).as_ref() == Some(expected)
+                    } else {
+                        true
+                    };
+                    if !matches {
+                        result.altered.push(display(relative));
+                    }
+                }
+            }
+        }
+
+        // 查找签名后新增的资源
+        let main_executable = main_executable_relative(base);
+        for entry in WalkDir::new(base).follow_links(false).min_depth(1).into_iter().flatten() {
+            if entry.file_type().is_dir() {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(base) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().to_string();
+
+            if listed.contains(&relative)
+                || relative.starts_with("_CodeSignature/")
+                || relative == "CodeResources"
+                || Some(&relative) == main_executable.as_ref()
+                || nested_roots.iter().any(|root| relative.starts_with(root.as_str()))
+            {
+                continue;
+            }
+            // 被规则忽略的文件不参与封存；其余未登记的文件（包括未登记的内嵌代码）都视为新增
+            if code_resources.matching_rule(&relative).map(|r| r.omit).unwrap_or(false) {
+                continue;
+            }
+            result.added.push(display(&relative));
+        }
+
+        Ok(())
+    }
+}
+
+/// 查找包含 _CodeSignature/CodeResources 的签名根目录
+pub fn signature_base(bundle: &Path) -> Option<PathBuf> {
+    let candidates = [
+        bundle.join("Contents"),
+        bundle.join("Versions/Current"),
+        bundle.to_path_buf(),
+    ];
+    candidates.into_iter()
+        .find(|base| base.join("_CodeSignature/CodeResources").is_file())
+}
+
+/// 主程序相对签名根目录的路径（由代码目录保护，不在资源清单中）
+fn main_executable_relative(base: &Path) -> Option<String> {
+    let info = ["Info.plist", "Resources/Info.plist"].iter()
+        .map(|p| base.join(p))
+        .find(|p| p.is_file())?;
+    let executable = plist::Value::from_file(info).ok()?
+        .as_dictionary()?
+        .get("CFBundleExecutable")?
+        .as_string()?
+        .to_string();
+    if base.join("MacOS").is_dir() {
+        Some(format!("MacOS/{}", executable))
+    } else {
+        Some(executable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-codesign-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// 写入文件并返回其 SHA-256 的资源条目
+    fn sealed_file(base: &Path, relative: &str, content: &[u8]) -> plist::Value {
+        let path = base.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        let mut entry = plist::Dictionary::new();
+        entry.insert("hash2".into(), plist::Value::Data(Sha256::digest(content).to_vec()));
+        plist::Value::Dictionary(entry)
+    }
+
+    fn write_code_resources(base: &Path, files: plist::Dictionary) {
+        let mut root = plist::Dictionary::new();
+        root.insert("files2".into(), plist::Value::Dictionary(files));
+        let mut omit = plist::Dictionary::new();
+        omit.insert("omit".into(), true.into());
+        omit.insert("weight".into(), plist::Value::Real(2000.0));
+        let mut rules = plist::Dictionary::new();
+        rules.insert("^Info\\.plist$".into(), plist::Value::Dictionary(omit));
+        root.insert("rules2".into(), plist::Value::Dictionary(rules));
+        fs::create_dir_all(base.join("_CodeSignature")).unwrap();
+        plist::Value::Dictionary(root)
+            .to_file_xml(base.join("_CodeSignature/CodeResources"))
+            .unwrap();
+    }
+
+    fn nested_entry() -> plist::Value {
+        let mut entry = plist::Dictionary::new();
+        entry.insert("cdhash".into(), plist::Value::Data(vec![0; 20]));
+        plist::Value::Dictionary(entry)
+    }
+
+    /// 构造含一个资源文件和一个内嵌框架的应用
+    fn build_app(dir: &Path) -> PathBuf {
+        let app = dir.join("Fixture.app");
+        let contents = app.join("Contents");
+        fs::create_dir_all(contents.join("MacOS")).unwrap();
+        fs::write(contents.join("MacOS/Fixture"), b"binary").unwrap();
+        fs::write(
+            contents.join("Info.plist"),
+            r#"<?xml version="1.0"?><plist version="1.0"><dict><key>CFBundleExecutable</key><string>Fixture</string></dict></plist>"#,
+        ).unwrap();
+
+        let framework = contents.join("Frameworks/Kit.framework");
+        let mut framework_files = plist::Dictionary::new();
+        framework_files.insert("Resources/kit.txt".into(), sealed_file(&framework, "Resources/kit.txt", b"kit"));
+        write_code_resources(&framework, framework_files);
+
+        let mut files = plist::Dictionary::new();
+        files.insert("Resources/a.txt".into(), sealed_file(&contents, "Resources/a.txt", b"hello"));
+        files.insert("Frameworks/Kit.framework".into(), nested_entry());
+        write_code_resources(&contents, files);
+        app
+    }
+
+    fn verify(app: &Path) -> SignatureVerification {
+        CodeSignService::new().verify_bundle(&app.to_string_lossy()).unwrap()
+    }
+
+    #[test]
+    fn intact_bundle_is_valid() {
+        let dir = fixture_dir("intact");
+        let result = verify(&build_app(&dir));
+        assert!(result.signed && result.valid, "{:?}", result);
+        assert_eq!((result.checked_count, result.nested_count), (2, 1));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_missing_added_and_altered_resources() {
+        let dir = fixture_dir("modified");
+        let app = build_app(&dir);
+        fs::remove_file(app.join("Contents/Resources/a.txt")).unwrap();
+        fs::write(app.join("Contents/Resources/b.txt"), b"new").unwrap();
+        fs::write(app.join("Contents/Frameworks/Kit.framework/Resources/kit.txt"), b"KIT").unwrap();
+
+        let result = verify(&app);
+        assert!(!result.valid);
+        assert_eq!(result.missing, vec!["Contents/Resources/a.txt"]);
+        assert_eq!(result.added, vec!["Contents/Resources/b.txt"]);
+        assert_eq!(result.altered, vec!["Contents/Frameworks/Kit.framework/Resources/kit.txt"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_nested_bundle_does_not_stop_verification() {
+        let dir = fixture_dir("nested");
+        let app = build_app(&dir);
+        fs::write(app.join("Contents/Frameworks/Kit.framework/_CodeSignature/CodeResources"), b"garbage").unwrap();
+        fs::write(app.join("Contents/Resources/a.txt"), b"HELLO").unwrap();
+
+        let result = verify(&app);
+        assert!(!result.valid);
+        assert_eq!(result.unverified.len(), 1);
+        assert!(result.unverified[0].starts_with("Contents/Frameworks/Kit.framework: "));
+        assert_eq!(result.altered, vec!["Contents/Resources/a.txt"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,413 @@
+//! Xcode / iOS 开发者存储分析服务实现
+//!
+//! 扫描 ~/Library/Developer 下的 DerivedData、Archives、DeviceSupport、
+//! CoreSimulator 设备和缓存，解析模拟器 device.plist 得到设备与运行时名称，
+//! 标记运行时已被删除的模拟器，并按系统版本汇总 DeviceSupport 目录。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+use crate::models::cleaner::CleanItem;
+use crate::models::developer::{
+    DeveloperStorageCategory, DeveloperStorageReport, DeviceSupportGroup, SimulatorDevice,
+};
+use crate::services::update_service::compare_versions;
+
+/// 分类标识与显示名称
+const CATEGORIES: [(&str, &str); 7] = [
+    ("derived_data", "DerivedData"),
+    ("archives", "Xcode 归档"),
+    ("device_support", "DeviceSupport"),
+    ("simulators", "模拟器设备"),
+    ("simulator_runtimes", "模拟器运行时"),
+    ("simulator_caches", "模拟器缓存"),
+    ("previews", "SwiftUI 预览"),
+];
+
+/// DeviceSupport 目录及对应平台
+const DEVICE_SUPPORT_DIRS: [(&str, &str); 4] = [
+    ("iOS DeviceSupport", "iOS"),
+    ("watchOS DeviceSupport", "watchOS"),
+    ("tvOS DeviceSupport", "tvOS"),
+    ("visionOS DeviceSupport", "visionOS"),
+];
+
+/// device.plist 中表示已启动的状态值
+const SIM_STATE_BOOTED: u64 = 3;
+
+/// 计算目录或文件大小（不跟随符号链接）
+fn path_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// 列出目录下的非隐藏子目录
+fn visible_subdirs(path: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(path)
+        .map(|entries| {
+            entries.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .filter(|p| !file_name(p).starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+/// 路径最后一段
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// 读取 plist 字典中的字符串
+fn plist_string(dict: &plist::Dictionary, key: &str) -> Option<String> {
+    dict.get(key).and_then(|v| v.as_string()).map(|s| s.to_string())
+}
+
+/// 运行时标识转显示名称（com.apple.CoreSimulator.SimRuntime.iOS-17-2 -> iOS 17.2）
+pub fn runtime_display_name(runtime: &str) -> String {
+    let short = runtime.rsplit('.').next().unwrap_or(runtime);
+    match short.split_once('-') {
+        Some((platform, version)) => format!("{} {}", platform, version.replace('-', ".")),
+        None => short.to_string(),
+    }
+}
+
+/// 设备类型标识转显示名称（com.apple.CoreSimulator.SimDeviceType.iPhone-15-Pro -> iPhone 15 Pro）
+fn device_type_display_name(device_type: &str) -> String {
+    device_type.rsplit('.').next().unwrap_or(device_type).replace('-', " ")
+}
+
+/// 解析 DeviceSupport 目录名，返回 (系统版本, 构建号)
+///
+/// 支持 "17.2 (21C62)"、"17.2 (21C62) arm64e" 以及 Xcode 14 起带设备型号前缀的 "iPhone15,2 17.2 (21C62)"
+pub fn parse_device_support_name(name: &str) -> Option<(String, Option<String>)> {
+    let (head, tail) = match name.split_once('(') {
+        Some((head, tail)) => (head, Some(tail)),
+        None => (name, None),
+    };
+    let version = head.split_whitespace().last()?;
+    if !version.chars().next()?.is_ascii_digit() {
+        return None;
+    }
+    let build = tail
+        .and_then(|t| t.split_once(')'))
+        .map(|(build, _)| build.trim().to_string());
+    Some((version.to_string(), build))
+}
+
+/// 开发者存储分析服务
+pub struct DeveloperStorageService {
+    developer_dir: PathBuf,
+    runtime_dirs: Vec<PathBuf>,
+}
+
+impl DeveloperStorageService {
+    /// 创建新的开发者存储分析服务实例
+    pub fn new() -> Self {
+        let developer_dir = dirs::home_dir()
+            .unwrap_or_default()
+            .join("Library/Developer");
+
+        // 旧版运行时安装在 Profiles/Runtimes，Xcode 15 起以磁盘映像挂载在 Volumes 下
+        let system_dir = Path::new("/Library/Developer/CoreSimulator");
+        let mut runtime_dirs = vec![system_dir.join("Profiles/Runtimes")];
+        for volume in visible_subdirs(&system_dir.join("Volumes")) {
+            runtime_dirs.push(volume.join("Library/Developer/CoreSimulator/Profiles/Runtimes"));
+        }
+
+        Self::with_paths(developer_dir, runtime_dirs)
+    }
+
+    /// 使用指定的 Developer 目录和运行时目录创建实例
+    pub fn with_paths(developer_dir: impl Into<PathBuf>, runtime_dirs: Vec<PathBuf>) -> Self {
+        DeveloperStorageService { developer_dir: developer_dir.into(), runtime_dirs }
+    }
+
+    /// 分析开发者存储占用
+    pub fn analyze(&self) -> DeveloperStorageReport {
+        let mut items: Vec<(&str, CleanItem)> = Vec::new();
+        let xcode = self.developer_dir.join("Xcode");
+
+        self.scan_derived_data(&xcode.join("DerivedData"), &mut items);
+        self.scan_archives(&xcode.join("Archives"), &mut items);
+        let device_support = self.scan_device_support(&xcode, &mut items);
+        let simulators = self.scan_simulators(&mut items);
+
+        let simulator_caches = self.developer_dir.join("CoreSimulator/Caches");
+        if simulator_caches.is_dir() {
+            push_item(&mut items, "simulator_caches", &simulator_caches, "模拟器缓存".to_string());
+        }
+        let previews = xcode.join("UserData/Previews");
+        if previews.is_dir() {
+            push_item(&mut items, "previews", &previews, "SwiftUI 预览模拟器数据".to_string());
+        }
+
+        let categories = CATEGORIES.iter()
+            .map(|(id, name)| {
+                let matching: Vec<&CleanItem> = items.iter()
+                    .filter(|(category, _)| category == id)
+                    .map(|(_, item)| item)
+                    .collect();
+                DeveloperStorageCategory {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    size: matching.iter().map(|i| i.size).sum(),
+                    item_count: matching.len(),
+                }
+            })
+            .filter(|c| c.item_count > 0)
+            .collect();
+
+        let mut items: Vec<CleanItem> = items.into_iter().map(|(_, item)| item).collect();
+        items.sort_by(|a, b| b.size.cmp(&a.size));
+        let total_size = items.iter().map(|i| i.size).sum();
+
+        DeveloperStorageReport {
+            categories,
+            simulators,
+            device_support,
+            items,
+            total_size,
+        }
+    }
+
+    /// 获取清理项（供系统清理使用）
+    pub fn scan_clean_items(&self) -> Vec<CleanItem> {
+        self.analyze().items
+    }
+
+    /// 扫描 DerivedData，每个工程一项
+    fn scan_derived_data(&self, dir: &Path, items: &mut Vec<(&'static str, CleanItem)>) {
+        for project_dir in visible_subdirs(dir) {
+            let name = file_name(&project_dir);
+            // 目录名为 <工程名>-<哈希>，info.plist 记录了工程路径
+            let workspace = plist::Value::from_file(project_dir.join("info.plist"))
+                .ok()
+                .and_then(|v| v.as_dictionary().and_then(|d| plist_string(d, "WorkspacePath")));
+            let description = match (name.rsplit_once('-'), workspace) {
+                (Some((project, _)), Some(workspace)) if !Path::new(&workspace).exists() => {
+                    format!("DerivedData: {}（工程已不存在: {}）", project, workspace)
+                }
+                (Some((project, _)), _) => format!("DerivedData: {}", project),
+                (None, _) => format!("DerivedData: {}", name),
+            };
+            push_item(items, "derived_data", &project_dir, description);
+        }
+    }
+
+    /// 扫描 Archives/<日期>/*.xcarchive
+    fn scan_archives(&self, dir: &Path, items: &mut Vec<(&'static str, CleanItem)>) {
+        for date_dir in visible_subdirs(dir) {
+            let date = file_name(&date_dir);
+            for archive in visible_subdirs(&date_dir) {
+                if archive.extension().map(|e| e != "xcarchive").unwrap_or(true) {
+                    continue;
+                }
+                let info = plist::Value::from_file(archive.join("Info.plist")).ok();
+                let dict = info.as_ref().and_then(|v| v.as_dictionary());
+                let name = dict.and_then(|d| plist_string(d, "Name"))
+                    .unwrap_or_else(|| archive.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default());
+                let properties = dict
+                    .and_then(|d| d.get("ApplicationProperties"))
+                    .and_then(|v| v.as_dictionary());
+                let version = properties.and_then(|p| plist_string(p, "CFBundleShortVersionString"));
+                let build = properties.and_then(|p| plist_string(p, "CFBundleVersion"));
+                let version = match (version, build) {
+                    (Some(v), Some(b)) => format!(" {} ({})", v, b),
+                    (Some(v), None) => format!(" {}", v),
+                    _ => String::new(),
+                };
+                push_item(items, "archives", &archive, format!("Xcode 归档: {}{}，{}", name, version, date));
+            }
+        }
+    }
+
+    /// 扫描各平台 DeviceSupport 目录并按系统版本分组
+    fn scan_device_support(&self, xcode: &Path, items: &mut Vec<(&'static str, CleanItem)>) -> Vec<DeviceSupportGroup> {
+        let mut groups: BTreeMap<(String, String), DeviceSupportGroup> = BTreeMap::new();
+
+        for (dir_name, platform) in DEVICE_SUPPORT_DIRS {
+            for folder in visible_subdirs(&xcode.join(dir_name)) {
+                let name = file_name(&folder);
+                let (version, build) = parse_device_support_name(&name)
+                    .unwrap_or_else(|| (name.clone(), None));
+                let size = push_item(items, "device_support", &folder, match &build {
+                    Some(build) => format!("{} DeviceSupport {} ({})", platform, version, build),
+                    None => format!("{} DeviceSupport {}", platform, version),
+                });
+
+                let group = groups.entry((platform.to_string(), version.clone()))
+                    .or_insert_with(|| DeviceSupportGroup {
+                        platform: platform.to_string(),
+                        os_version: version.clone(),
+                        folders: Vec::new(),
+                        size: 0,
+                    });
+                group.folders.push(name);
+                group.size += size;
+            }
+        }
+
+        let mut groups: Vec<DeviceSupportGroup> = groups.into_values().collect();
+        // 同一平台内新版本在前
+        groups.sort_by(|a, b| {
+            a.platform.cmp(&b.platform).then_with(|| compare_versions(&b.os_version, &a.os_version))
+        });
+        groups
+    }
+
+    /// 已安装的模拟器运行时: 标识 -> 路径
+    fn installed_runtimes(&self) -> HashMap<String, PathBuf> {
+        let mut runtimes = HashMap::new();
+        for dir in &self.runtime_dirs {
+            for runtime in visible_subdirs(dir) {
+                if runtime.extension().map(|e| e != "simruntime").unwrap_or(true) {
+                    continue;
+                }
+                let identifier = plist::Value::from_file(runtime.join("Contents/Info.plist"))
+                    .ok()
+                    .and_then(|v| v.as_dictionary().and_then(|d| plist_string(d, "CFBundleIdentifier")));
+                if let Some(identifier) = identifier {
+                    runtimes.insert(identifier, runtime);
+                }
+            }
+        }
+        runtimes
+    }
+
+    /// 解析 CoreSimulator/Devices/<UDID>/device.plist
+    fn scan_simulators(&self, items: &mut Vec<(&'static str, CleanItem)>) -> Vec<SimulatorDevice> {
+        let runtimes = self.installed_runtimes();
+        let mut used_runtimes = Vec::new();
+        let mut devices = Vec::new();
+
+        for device_dir in visible_subdirs(&self.developer_dir.join("CoreSimulator/Devices")) {
+            let Ok(value) = plist::Value::from_file(device_dir.join("device.plist")) else {
+                continue;
+            };
+            let Some(dict) = value.as_dictionary() else {
+                continue;
+            };
+            let runtime = plist_string(dict, "runtime").unwrap_or_default();
+            let device_type = plist_string(dict, "deviceType").unwrap_or_default();
+            let name = plist_string(dict, "name")
+                .unwrap_or_else(|| device_type_display_name(&device_type));
+            let booted = dict.get("state")
+                .and_then(|v| v.as_unsigned_integer())
+                .map(|s| s == SIM_STATE_BOOTED)
+                .unwrap_or(false);
+            // 一个运行时都没找到时（如映像未挂载）无法判断，不标记为缺失
+            let runtime_installed = runtimes.is_empty() || runtimes.contains_key(&runtime);
+            let runtime_name = runtime_display_name(&runtime);
+            used_runtimes.push(runtime.clone());
+
+            let description = if runtime_installed {
+                format!("模拟器: {}（{}）", name, runtime_name)
+            } else {
+                format!("模拟器: {}（运行时 {} 已不存在）", name, runtime_name)
+            };
+            let size = if booted {
+                // 正在运行的模拟器不作为清理项
+                path_size(&device_dir)
+            } else {
+                push_item(items, "simulators", &device_dir, description)
+            };
+
+            devices.push(SimulatorDevice {
+                udid: plist_string(dict, "UDID").unwrap_or_else(|| file_name(&device_dir)),
+                name,
+                device_type,
+                runtime,
+                runtime_name,
+                runtime_installed,
+                booted,
+                path: device_dir.to_string_lossy().to_string(),
+                size,
+            });
+        }
+
+        for (identifier, path) in &runtimes {
+            if !used_runtimes.contains(identifier) {
+                push_item(items, "simulator_runtimes", path, format!(
+                    "模拟器运行时: {}（没有模拟器使用）", runtime_display_name(identifier)
+                ));
+            }
+        }
+
+        // 运行时缺失的设备排在前面，其次按大小
+        devices.sort_by(|a, b| {
+            a.runtime_installed.cmp(&b.runtime_installed).then_with(|| b.size.cmp(&a.size))
+        });
+        devices
+    }
+}
+
+/// 执行 xcrun simctl
+fn run_simctl(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("xcrun")
+        .arg("simctl")
+        .args(args)
+        .output()
+        .map_err(|e| format!("无法执行 simctl: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+/// 查找运行时包对应的 simctl 运行时 ID（只有以磁盘映像安装的运行时会被列出）
+fn simctl_runtime_identifier(runtime_path: &Path) -> Option<String> {
+    let output = Command::new("xcrun")
+        .args(["simctl", "runtime", "list", "-j"])
+        .output()
+        .ok()?;
+    let runtimes: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    runtimes.as_object()?
+        .iter()
+        .find(|(_, runtime)| {
+            runtime.get("runtimeBundlePath")
+                .and_then(|p| p.as_str())
+                .map(|p| Path::new(p) == runtime_path)
+                .unwrap_or(false)
+        })
+        .map(|(identifier, _)| identifier.clone())
+}
+
+/// 通过 simctl 删除模拟器设备或运行时，以便 CoreSimulator 同步更新记录并卸载磁盘映像
+///
+/// 路径不是模拟器设备或 simctl 管理的运行时时返回 None，由调用方直接删除路径
+pub fn simctl_delete(path: &Path) -> Option<Result<(), String>> {
+    if path.extension().map(|e| e == "simruntime").unwrap_or(false) {
+        let identifier = simctl_runtime_identifier(path)?;
+        return Some(run_simctl(&["runtime", "delete", &identifier]));
+    }
+    let is_device = path.parent().map(|p| p.ends_with("CoreSimulator/Devices")).unwrap_or(false)
+        && path.join("device.plist").is_file();
+    if is_device {
+        return Some(run_simctl(&["delete", &file_name(path)]));
+    }
+    None
+}
+
+/// 添加一个清理项，返回其大小
+fn push_item(items: &mut Vec<(&'static str, CleanItem)>, category: &'static str, path: &Path, description: String) -> u64 {
+    let size = path_size(path);
+    items.push((category, CleanItem {
+        type_: "developer".to_string(),
+        path: path.to_string_lossy().to_string(),
+        size,
+        description,
+    }));
+    size
+}
@@ -0,0 +1,181 @@
+//! 可多开应用目录服务实现
+//!
+//! 内置目录随程序发布（catalog/duplicatable_apps.json），按应用标识符索引。
+//! 用户可在配置目录的 duplicatable_apps.json 中添加条目，或覆盖、屏蔽内置条目。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use crate::models::duplicate::DuplicateCatalogEntry;
+
+/// 内置目录
+const BUILTIN_CATALOG: &str = include_str!("../../catalog/duplicatable_apps.json");
+
+/// 支持的多开方式
+const STRATEGIES: [&str; 2] = ["bundle_copy", "profile"];
+
+/// 启动配置支持的隔离方式
+const ISOLATIONS: [&str; 3] = ["user_data_dir", "firefox_profile", "home"];
+
+/// 目录文件格式: 应用标识符 -> 条目
+type CatalogFile = BTreeMap<String, DuplicateCatalogEntry>;
+
+/// 解析目录文件内容
+pub fn parse_catalog(content: &str, source: &str) -> Result<Vec<DuplicateCatalogEntry>, String> {
+    let file: CatalogFile = serde_json::from_str(content).map_err(|e| format!("无法解析应用目录: {}", e))?;
+    let mut entries = Vec::new();
+    for (bundle_id, mut entry) in file {
+        if !STRATEGIES.contains(&entry.strategy.as_str()) {
+            return Err(format!("{} 的多开方式 {} 不受支持", bundle_id, entry.strategy));
+        }
+        if let Some(isolation) = entry.isolation.as_deref().filter(|i| !ISOLATIONS.contains(i)) {
+            return Err(format!("{} 的隔离方式 {} 不受支持", bundle_id, isolation));
+        }
+        entry.bundle_id = bundle_id;
+        entry.source = source.to_string();
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// 去掉副本标识符末尾的 `_N` 编号后缀，返回源应用标识符
+pub fn strip_copy_suffix(bundle_id: &str) -> &str {
+    match bundle_id.rsplit_once('_') {
+        Some((base, n)) if !base.is_empty() && !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => base,
+        _ => bundle_id,
+    }
+}
+
+/// 可多开应用目录服务
+pub struct DuplicateCatalogService {
+    user_catalog_path: PathBuf,
+}
+
+impl DuplicateCatalogService {
+    /// 创建新的应用目录服务实例
+    pub fn new() -> Self {
+        let user_catalog_path = dirs::config_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("com.mole.app")
+            .join("duplicatable_apps.json");
+        Self::with_user_catalog(user_catalog_path)
+    }
+
+    /// 使用指定的用户目录文件创建实例
+    pub fn with_user_catalog(user_catalog_path: impl Into<PathBuf>) -> Self {
+        DuplicateCatalogService {
+            user_catalog_path: user_catalog_path.into(),
+        }
+    }
+
+    /// 用户目录文件路径
+    pub fn user_catalog_path(&self) -> String {
+        self.user_catalog_path.to_string_lossy().to_string()
+    }
+
+    /// 读取用户目录（文件不存在时为空）
+    fn load_user_entries(&self) -> Result<Vec<DuplicateCatalogEntry>, String> {
+        match fs::read_to_string(&self.user_catalog_path) {
+            Ok(content) => parse_catalog(&content, "user"),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// 合并内置目录和用户目录，用户条目覆盖同一标识符的内置条目
+    pub fn load_catalog(&self) -> Result<Vec<DuplicateCatalogEntry>, String> {
+        let mut merged: BTreeMap<String, DuplicateCatalogEntry> = parse_catalog(BUILTIN_CATALOG, "builtin")?
+            .into_iter()
+            .map(|e| (e.bundle_id.clone(), e))
+            .collect();
+        for entry in self.load_user_entries()? {
+            merged.insert(entry.bundle_id.clone(), entry);
+        }
+        Ok(merged.into_values().collect())
+    }
+
+    /// 获取启用的目录条目，用户目录有误时回退到内置目录
+    pub fn enabled_entries(&self) -> Vec<DuplicateCatalogEntry> {
+        let entries = self.load_catalog().unwrap_or_else(|e| {
+            println!("[DuplicateCatalog] 用户目录无效，使用内置目录: {}", e);
+            parse_catalog(BUILTIN_CATALOG, "builtin").unwrap_or_default()
+        });
+        entries.into_iter().filter(|e| e.enabled).collect()
+    }
+
+    /// 按应用标识符查找启用的条目，副本按去掉编号后缀的源标识符匹配
+    pub fn find(&self, bundle_id: &str) -> Option<DuplicateCatalogEntry> {
+        let entries = self.enabled_entries();
+        let source_id = strip_copy_suffix(bundle_id);
+        entries.iter()
+            .find(|e| e.bundle_id == bundle_id)
+            .or_else(|| entries.iter().find(|e| e.bundle_id == source_id))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-catalog-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn strip_copy_suffix_only_removes_numeric_suffix() {
+        assert_eq!(strip_copy_suffix("com.tencent.xinWeChat_2"), "com.tencent.xinWeChat");
+        assert_eq!(strip_copy_suffix("com.tencent.xinWeChat_12"), "com.tencent.xinWeChat");
+        assert_eq!(strip_copy_suffix("com.example.my_app"), "com.example.my_app");
+        assert_eq!(strip_copy_suffix("com.example.app_"), "com.example.app_");
+        assert_eq!(strip_copy_suffix("com.example.app"), "com.example.app");
+    }
+
+    #[test]
+    fn builtin_catalog_covers_strategies() {
+        let dir = fixture_dir("builtin");
+        let service = DuplicateCatalogService::with_user_catalog(dir.join("duplicatable_apps.json"));
+        let entries = service.load_catalog().unwrap();
+        assert!(entries.iter().all(|e| e.source == "builtin"));
+        assert!(entries.iter().all(|e| e.strategy == "profile" || e.isolation.is_none()));
+
+        let wechat = service.find("com.tencent.xinWeChat").unwrap();
+        assert_eq!((wechat.strategy.as_str(), wechat.requires_resign), ("bundle_copy", true));
+        let copy = service.find("com.tencent.xinWeChat_3").unwrap();
+        assert_eq!(copy.bundle_id, "com.tencent.xinWeChat");
+        let firefox = service.find("org.mozilla.firefox").unwrap();
+        assert_eq!((firefox.strategy.as_str(), firefox.requires_resign), ("profile", false));
+        assert_eq!(firefox.isolation.as_deref(), Some("firefox_profile"));
+        assert!(service.find("com.tencent.QQMusicMac").is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn user_catalog_overrides_and_validates() {
+        let dir = fixture_dir("user");
+        let user_catalog = dir.join("duplicatable_apps.json");
+        let service = DuplicateCatalogService::with_user_catalog(&user_catalog);
+        let builtin = service.load_catalog().unwrap().len();
+
+        fs::write(&user_catalog, r#"{
+            "com.tencent.qq": {"name": "QQ", "strategy": "bundle_copy", "enabled": false},
+            "com.example.chat": {"name": "Chat", "strategy": "profile", "isolation": "home", "caveats": ["c"]}
+        }"#).unwrap();
+        assert!(service.find("com.tencent.qq").is_none());
+        let chat = service.find("com.example.chat").unwrap();
+        assert_eq!((chat.source.as_str(), chat.requires_resign, chat.caveats.len()), ("user", false, 1));
+        assert_eq!(service.load_catalog().unwrap().len(), builtin + 1);
+
+        for invalid in [
+            r#"{"com.example.chat": {"name": "Chat", "strategy": "bogus"}}"#,
+            r#"{"com.example.chat": {"name": "Chat", "strategy": "profile", "isolation": "bogus"}}"#,
+        ] {
+            fs::write(&user_catalog, invalid).unwrap();
+            assert!(service.load_catalog().is_err());
+            assert_eq!(service.enabled_entries().len(), builtin);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
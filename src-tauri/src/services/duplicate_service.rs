@@ -0,0 +1,595 @@
+//! 应用副本登记服务实现 - 使用 DuckDB
+//!
+//! 记录 create_duplicate_app 创建的每个副本：源应用、源版本、副本标识符、
+//! 创建时间和数据目录，用于列出副本、连同数据一起删除，以及发现源应用已更新的过期副本。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use duckdb::{Connection, params};
+use walkdir::WalkDir;
+use crate::models::app::{DuplicateResult, UninstallResult};
+use crate::models::duplicate::{DuplicateRecord, DuplicateStatus};
+use crate::services::app_service::AppService;
+use crate::services::bundle_rewrite_service::{BundleRewriteService, DuplicateIdentity};
+use crate::services::duplicate_catalog_service::DuplicateCatalogService;
+use crate::services::icon_badge_service::IconBadge;
+use crate::services::sampler_service::latest_processes;
+
+/// 当前时间（秒）
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 计算目录或文件大小（不跟随符号链接）
+fn path_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// 读取应用的显示版本和构建版本
+pub fn read_bundle_version(app_path: &Path) -> Option<(String, String)> {
+    let value = plist::Value::from_file(app_path.join("Contents/Info.plist")).ok()?;
+    let dict = value.as_dictionary()?;
+    let get = |key: &str| dict.get(key).and_then(|v| v.as_string()).unwrap_or_default().to_string();
+    Some((get("CFBundleShortVersionString"), get("CFBundleVersion")))
+}
+
+/// 读取应用标识符
+pub fn read_bundle_identifier(app_path: &Path) -> Option<String> {
+    let value = plist::Value::from_file(app_path.join("Contents/Info.plist")).ok()?;
+    value.as_dictionary()?.get("CFBundleIdentifier")?.as_string().map(|s| s.to_string())
+}
+
+/// 应用包内是否有正在运行的进程
+fn is_bundle_running(app_path: &Path) -> bool {
+    latest_processes()
+        .iter()
+        .any(|p| Path::new(&p.exe_path).starts_with(app_path))
+}
+
+/// 指定标识符的应用会使用的数据目录
+pub fn data_container_paths(bundle_id: &str) -> Vec<String> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let library = home.join("Library");
+    [
+        library.join("Containers").join(bundle_id),
+        library.join("Preferences").join(format!("{}.plist", bundle_id)),
+        library.join("Application Support").join(bundle_id),
+        library.join("Caches").join(bundle_id),
+        library.join("HTTPStorages").join(bundle_id),
+        library.join("WebKit").join(bundle_id),
+        library.join("Logs").join(bundle_id),
+        library.join("Saved Application State").join(format!("{}.savedState", bundle_id)),
+    ]
+    .iter()
+    .map(|p| p.to_string_lossy().to_string())
+    .collect()
+}
+
+/// 用新生成的副本替换旧副本：旧副本先移到备份位置，新副本就位后才删除备份
+fn replace_copy(staging: &Path, copy: &Path, backup: &Path) -> Result<(), String> {
+    if copy.exists() {
+        fs::rename(copy, backup).map_err(|e| format!("无法移走旧副本: {}", e))?;
+    }
+    if let Err(e) = fs::rename(staging, copy) {
+        if fs::symlink_metadata(backup).is_ok() {
+            if let Err(restore_error) = fs::rename(backup, copy) {
+                return Err(format!(
+                    "无法替换副本: {}; 旧副本未能还原，保留在 {}: {}",
+                    e, backup.to_string_lossy(), restore_error
+                ));
+            }
+        }
+        return Err(format!("无法替换副本: {}", e));
+    }
+    let _ = fs::remove_dir_all(backup);
+    Ok(())
+}
+
+/// 应用副本登记服务
+pub struct DuplicateService {
+    db_path: PathBuf,
+    rewriter: BundleRewriteService,
+}
+
+impl DuplicateService {
+    /// 创建新的副本登记服务实例（不自动创建数据库）
+    pub fn new() -> Self {
+        let db_path = dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("com.mole.app")
+            .join("duplicates.db");
+        Self::with_db_path(db_path)
+    }
+
+    /// 使用指定数据库文件创建实例
+    pub fn with_db_path(db_path: impl Into<PathBuf>) -> Self {
+        Self::with_rewriter(db_path, BundleRewriteService::new())
+    }
+
+    /// 使用指定数据库文件和副本生成服务（更新副本时使用）创建实例
+    pub fn with_rewriter(db_path: impl Into<PathBuf>, rewriter: BundleRewriteService) -> Self {
+        DuplicateService { db_path: db_path.into(), rewriter }
+    }
+
+    /// 获取数据库连接（仅当数据库存在时）
+    fn get_connection(&self) -> Option<Connection> {
+        if !self.db_path.exists() {
+            return None;
+        }
+        Connection::open(&self.db_path).ok()
+    }
+
+    /// 获取或创建数据库连接，并确保表存在
+    fn get_or_create_connection(&self) -> Result<Connection, String> {
+        if let Some(parent) = self.db_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("无法创建数据目录: {}", e))?;
+        }
+        let conn = Connection::open(&self.db_path).map_err(|e| format!("无法打开副本数据库: {}", e))?;
+        conn.execute_batch(
+            "CREATE SEQUENCE IF NOT EXISTS app_duplicate_seq START 1;
+             CREATE TABLE IF NOT EXISTS app_duplicates (
+                 id BIGINT PRIMARY KEY,
+                 source_path VARCHAR NOT NULL,
+                 source_bundle_id VARCHAR NOT NULL,
+                 source_version VARCHAR NOT NULL,
+                 source_build VARCHAR NOT NULL,
+                 copy_path VARCHAR NOT NULL,
+                 bundle_id VARCHAR NOT NULL,
+                 app_name VARCHAR NOT NULL,
+                 icon_emoji VARCHAR,
+                 copy_number UINTEGER NOT NULL,
+                 created_at UBIGINT NOT NULL,
+                 data_containers VARCHAR NOT NULL
+             );",
+        ).map_err(|e| format!("无法创建副本表: {}", e))?;
+        Ok(conn)
+    }
+
+    /// 查询副本记录
+    fn query(&self, conn: &Connection, filter: &str, args: &[&dyn duckdb::ToSql]) -> Vec<DuplicateRecord> {
+        let sql = format!(
+            "SELECT id, source_path, source_bundle_id, source_version, source_build, copy_path,
+                 bundle_id, app_name, icon_emoji, copy_number, created_at, data_containers
+             FROM app_duplicates {} ORDER BY created_at DESC",
+            filter
+        );
+        let mut result = Vec::new();
+        if let Ok(mut stmt) = conn.prepare(&sql) {
+            if let Ok(rows) = stmt.query_map(args, |row| {
+                let containers: String = row.get(11)?;
+                Ok(DuplicateRecord {
+                    id: row.get(0)?,
+                    source_path: row.get(1)?,
+                    source_bundle_id: row.get(2)?,
+                    source_version: row.get(3)?,
+                    source_build: row.get(4)?,
+                    copy_path: row.get(5)?,
+                    bundle_id: row.get(6)?,
+                    app_name: row.get(7)?,
+                    icon_emoji: row.get(8)?,
+                    copy_number: row.get(9)?,
+                    created_at: row.get(10)?,
+                    data_containers: serde_json::from_str(&containers).unwrap_or_default(),
+                })
+            }) {
+                result.extend(rows.flatten());
+            }
+        }
+        result
+    }
+
+    /// 登记新创建的副本
+    pub fn register(
+        &self,
+        source_path: &str,
+        copy_path: &str,
+        bundle_id: &str,
+        app_name: &str,
+        icon_emoji: Option<String>,
+        copy_number: u32,
+    ) -> Result<DuplicateRecord, String> {
+        let (source_version, source_build) = read_bundle_version(Path::new(source_path)).unwrap_or_default();
+        let conn = self.get_or_create_connection()?;
+        let id: i64 = conn.query_row("SELECT nextval('app_duplicate_seq')", [], |row| row.get(0))
+            .map_err(|e| format!("无法生成副本 ID: {}", e))?;
+
+        let record = DuplicateRecord {
+            id,
+            source_path: source_path.to_string(),
+            source_bundle_id: read_bundle_identifier(Path::new(source_path)).unwrap_or_default(),
+            source_version,
+            source_build,
+            copy_path: copy_path.to_string(),
+            bundle_id: bundle_id.to_string(),
+            app_name: app_name.to_string(),
+            icon_emoji,
+            copy_number,
+            created_at: now_secs(),
+            data_containers: data_container_paths(bundle_id),
+        };
+        let containers = serde_json::to_string(&record.data_containers)
+            .map_err(|e| format!("无法序列化数据目录: {}", e))?;
+        conn.execute(
+            "INSERT INTO app_duplicates (id, source_path, source_bundle_id, source_version, source_build,
+                 copy_path, bundle_id, app_name, icon_emoji, copy_number, created_at, data_containers)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                record.id, &record.source_path, &record.source_bundle_id, &record.source_version,
+                &record.source_build, &record.copy_path, &record.bundle_id, &record.app_name,
+                &record.icon_emoji, record.copy_number, record.created_at, containers
+            ],
+        ).map_err(|e| format!("无法登记副本: {}", e))?;
+        let _ = conn.execute("CHECKPOINT", []);
+        Ok(record)
+    }
+
+    /// 获取单个副本记录
+    pub fn get_duplicate(&self, id: i64) -> Result<DuplicateRecord, String> {
+        let conn = self.get_connection().ok_or_else(|| format!("副本 {} 不存在", id))?;
+        self.query(&conn, "WHERE id = ?", &[&id])
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("副本 {} 不存在", id))
+    }
+
+    /// 获取副本当前状态
+    pub fn get_status(&self, record: DuplicateRecord) -> DuplicateStatus {
+        let source = Path::new(&record.source_path);
+        let current = read_bundle_version(source);
+        // 源应用被删除时无法判断，不视为过期
+        let stale = current.as_ref()
+            .map(|(version, build)| *version != record.source_version || *build != record.source_build)
+            .unwrap_or(false);
+        let data_size = record.data_containers.iter()
+            .map(|p| path_size(Path::new(p)))
+            .sum();
+
+        DuplicateStatus {
+            copy_exists: Path::new(&record.copy_path).exists(),
+            source_exists: source.exists(),
+            current_source_version: current.as_ref().map(|(v, _)| v.clone()),
+            current_source_build: current.map(|(_, b)| b),
+            stale,
+            data_size,
+            duplicate: record,
+        }
+    }
+
+    /// 列出所有已登记的副本（最新的在前）
+    pub fn list_duplicates(&self) -> Vec<DuplicateStatus> {
+        let Some(conn) = self.get_connection() else {
+            return Vec::new();
+        };
+        self.query(&conn, "", &[])
+            .into_iter()
+            .map(|record| self.get_status(record))
+            .collect()
+    }
+
+    /// 列出源应用已更新的过期副本
+    pub fn get_stale_duplicates(&self) -> Vec<DuplicateStatus> {
+        self.list_duplicates()
+            .into_iter()
+            .filter(|status| status.stale && status.copy_exists)
+            .collect()
+    }
+
+    /// 删除副本及其沙盒容器、偏好设置等数据，并移除登记
+    pub fn remove_duplicate(&self, id: i64) -> Result<UninstallResult, String> {
+        let record = self.get_duplicate(id)?;
+        let mut removed_paths = Vec::new();
+        let mut failed_paths = Vec::new();
+
+        if Path::new(&record.copy_path).exists() {
+            let result = AppService::new().uninstall_app(&record.copy_path, false)?;
+            removed_paths.extend(result.removed_paths);
+        }
+
+        for container in &record.data_containers {
+            let path = Path::new(container);
+            if fs::symlink_metadata(path).is_err() {
+                continue;
+            }
+            let result = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+            match result {
+                Ok(_) => removed_paths.push(container.clone()),
+                Err(_) => failed_paths.push(container.clone()),
+            }
+        }
+
+        if let Some(conn) = self.get_connection() {
+            conn.execute("DELETE FROM app_duplicates WHERE id = ?", params![id])
+                .map_err(|e| format!("无法删除副本登记: {}", e))?;
+            let _ = conn.execute("CHECKPOINT", []);
+        }
+
+        Ok(UninstallResult {
+            success: failed_paths.is_empty(),
+            message: if failed_paths.is_empty() {
+                format!("{} 副本已删除", record.app_name)
+            } else {
+                format!("部分数据删除失败: {:?}", failed_paths)
+            },
+            removed_paths,
+        })
+    }
+
+    /// 用源应用的当前版本重新生成副本
+    ///
+    /// 副本路径（包含名称和 emoji）与标识符保持不变，沙盒容器和登录状态因此得以保留。
+    pub fn update_duplicate(&self, id: i64) -> DuplicateResult {
+        let mut steps = Vec::new();
+        let record = match self.get_duplicate(id) {
+            Ok(record) => record,
+            Err(message) => return DuplicateResult { success: false, message, steps },
+        };
+        let source = Path::new(&record.source_path);
+        let copy = Path::new(&record.copy_path);
+
+        if !source.exists() {
+            return DuplicateResult { success: false, message: "源应用不存在".to_string(), steps };
+        }
+        if is_bundle_running(copy) {
+            return DuplicateResult {
+                success: false,
+                message: format!("{} 正在运行，请先退出", record.app_name),
+                steps,
+            };
+        }
+
+        // 先在同一目录生成新副本，成功后再替换，失败时原副本不受影响
+        let parent = copy.parent().unwrap_or(Path::new("/Applications"));
+        let staging = parent.join(format!(".mole-update-{}.app", record.id));
+        let backup = parent.join(format!(".mole-backup-{}.app", record.id));
+        let _ = fs::remove_dir_all(&staging);
+        // 上次更新中断时旧副本可能留在备份位置：副本缺失时先还原，副本完好时才删除备份
+        if fs::symlink_metadata(&backup).is_ok() {
+            if copy.exists() {
+                let _ = fs::remove_dir_all(&backup);
+            } else if let Err(e) = fs::rename(&backup, copy) {
+                return DuplicateResult {
+                    success: false,
+                    message: format!("旧副本保留在 {}，无法还原: {}", backup.to_string_lossy(), e),
+                    steps,
+                };
+            }
+        }
+
+        let source_bundle_id = read_bundle_identifier(source).unwrap_or_default();
+        // 目录未收录的应用默认重新签名
+        let resign = DuplicateCatalogService::new()
+            .find(&source_bundle_id)
+            .map(|e| e.requires_resign)
+            .unwrap_or(true);
+        let identity = DuplicateIdentity {
+            source_bundle_id,
+            bundle_id: record.bundle_id.clone(),
+            display_name: copy.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| record.app_name.clone()),
+            url_scheme_suffix: record.copy_number.to_string(),
+            icon_badge: Some(IconBadge::for_duplicate(record.copy_number, record.icon_emoji.as_deref())),
+            resign,
+        };
+        steps.push("正在生成源应用当前版本的副本...".to_string());
+        if let Err(e) = self.rewriter.create_copy(source, &staging, &identity, &mut steps) {
+            let _ = fs::remove_dir_all(&staging);
+            return DuplicateResult { success: false, message: e.to_string(), steps };
+        }
+
+        steps.push("替换旧副本...".to_string());
+        if let Err(message) = replace_copy(&staging, copy, &backup) {
+            let _ = fs::remove_dir_all(&staging);
+            return DuplicateResult { success: false, message, steps };
+        }
+
+        let (version, build) = read_bundle_version(source).unwrap_or_default();
+        if let Some(conn) = self.get_connection() {
+            let updated = conn.execute(
+                "UPDATE app_duplicates SET source_version = ?, source_build = ? WHERE id = ?",
+                params![&version, &build, id],
+            );
+            if let Err(e) = updated {
+                steps.push(format!("副本登记更新失败: {}", e));
+            }
+            let _ = conn.execute("CHECKPOINT", []);
+        }
+
+        DuplicateResult {
+            success: true,
+            message: format!("{} 已更新到 {}", record.app_name, version),
+            steps,
+        }
+    }
+
+    /// 更新所有过期副本
+    pub fn update_stale_duplicates(&self) -> Vec<DuplicateResult> {
+        self.get_stale_duplicates()
+            .iter()
+            .map(|status| self.update_duplicate(status.duplicate.id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::bundle_rewrite_service::CodeSigner;
+
+    /// 不调用 codesign 的签名器
+    struct NoopSigner;
+
+    impl CodeSigner for NoopSigner {
+        fn sign(&self, _bundle: &Path) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-duplicate-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn service(dir: &Path) -> DuplicateService {
+        DuplicateService::with_rewriter(
+            dir.join("duplicates.db"),
+            BundleRewriteService::with_signer(Box::new(NoopSigner)),
+        )
+    }
+
+    /// 写入指定版本的应用（标识符按测试区分，避免与真实应用数据目录重名）
+    fn write_app(app: &Path, bundle_id: &str, version: &str, build: &str) {
+        fs::create_dir_all(app.join("Contents/MacOS")).unwrap();
+        let mut info = plist::Dictionary::new();
+        info.insert("CFBundleIdentifier".to_string(), bundle_id.into());
+        info.insert("CFBundleName".to_string(), "Chat".into());
+        info.insert("CFBundleExecutable".to_string(), "Chat".into());
+        info.insert("CFBundleShortVersionString".to_string(), version.into());
+        info.insert("CFBundleVersion".to_string(), build.into());
+        plist::Value::Dictionary(info).to_file_xml(app.join("Contents/Info.plist")).unwrap();
+        fs::write(app.join("Contents/MacOS/Chat"), version).unwrap();
+    }
+
+    /// 创建源应用和已登记的副本，返回 (服务, 源应用, 副本, 登记)
+    fn registered_copy(dir: &Path, name: &str) -> (DuplicateService, PathBuf, PathBuf, DuplicateRecord) {
+        let source_id = format!("com.example.mole-test-{}-{}", name, std::process::id());
+        let copy_id = format!("{}_2", source_id);
+        let source = dir.join("Chat.app");
+        let copy = dir.join("Chat 2.app");
+        write_app(&source, &source_id, "1.0", "100");
+        write_app(&copy, &copy_id, "1.0", "100");
+        let service = service(dir);
+        let record = service.register(
+            &source.to_string_lossy(),
+            &copy.to_string_lossy(),
+            &copy_id,
+            "Chat 2",
+            Some("🔵".to_string()),
+            2,
+        ).unwrap();
+        (service, source, copy, record)
+    }
+
+    #[test]
+    fn registers_and_lists_duplicates() {
+        let dir = fixture_dir("register");
+        let (service, _, copy, record) = registered_copy(&dir, "register");
+
+        assert_eq!((record.source_version.as_str(), record.source_build.as_str()), ("1.0", "100"));
+        assert!(record.source_bundle_id.starts_with("com.example.mole-test-register"));
+        assert!(!record.data_containers.is_empty());
+        let listed = service.list_duplicates();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].duplicate.copy_path, copy.to_string_lossy());
+        assert_eq!(listed[0].duplicate.icon_emoji.as_deref(), Some("🔵"));
+        assert!(listed[0].copy_exists && listed[0].source_exists && !listed[0].stale);
+        assert_eq!(service.get_duplicate(record.id).unwrap().bundle_id, record.bundle_id);
+        assert!(service.get_duplicate(record.id + 100).is_err());
+        assert!(DuplicateService::with_db_path(dir.join("missing.db")).list_duplicates().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn detects_stale_duplicates() {
+        let dir = fixture_dir("stale");
+        let (service, source, copy, record) = registered_copy(&dir, "stale");
+        assert!(service.get_stale_duplicates().is_empty());
+
+        write_app(&source, &record.source_bundle_id, "1.1", "110");
+        let stale = service.get_stale_duplicates();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].current_source_version.as_deref(), Some("1.1"));
+
+        // 副本已被删除的登记不算作待更新
+        fs::remove_dir_all(&copy).unwrap();
+        assert!(service.get_stale_duplicates().is_empty());
+        assert!(service.list_duplicates()[0].stale);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn updates_stale_duplicate_in_place() {
+        let dir = fixture_dir("update");
+        let (service, source, copy, record) = registered_copy(&dir, "update");
+        write_app(&source, &record.source_bundle_id, "1.1", "110");
+
+        let results = service.update_stale_duplicates();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].success, "{:?}", results[0]);
+        assert_eq!(fs::read_to_string(copy.join("Contents/MacOS/Chat")).unwrap(), "1.1");
+        assert_eq!(read_bundle_identifier(&copy).as_deref(), Some(record.bundle_id.as_str()));
+        assert!(service.get_stale_duplicates().is_empty());
+        assert_eq!(service.get_duplicate(record.id).unwrap().source_version, "1.1");
+        let names: Vec<String> = fs::read_dir(&dir).unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|n| n.starts_with(".mole-"))
+            .collect();
+        assert!(names.is_empty(), "{:?}", names);
+
+        fs::remove_dir_all(&source).unwrap();
+        assert!(!service.update_duplicate(record.id).success);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn restores_backup_left_by_interrupted_update() {
+        let dir = fixture_dir("recover");
+        let (service, source, copy, record) = registered_copy(&dir, "recover");
+        let backup = dir.join(format!(".mole-backup-{}.app", record.id));
+        fs::rename(&copy, &backup).unwrap();
+        write_app(&source, &record.source_bundle_id, "1.1", "110");
+
+        let result = service.update_duplicate(record.id);
+        assert!(result.success, "{:?}", result);
+        assert_eq!(fs::read_to_string(copy.join("Contents/MacOS/Chat")).unwrap(), "1.1");
+        assert!(!backup.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_old_copy_when_replacement_fails() {
+        let dir = fixture_dir("replace");
+        let copy = dir.join("Chat 2.app");
+        let backup = dir.join(".mole-backup-1.app");
+        write_app(&copy, "com.example.chat_2", "1.0", "100");
+
+        let error = replace_copy(&dir.join(".mole-update-1.app"), &copy, &backup).unwrap_err();
+        assert!(error.starts_with("无法替换副本"), "{}", error);
+        assert_eq!(fs::read_to_string(copy.join("Contents/MacOS/Chat")).unwrap(), "1.0");
+        assert!(!backup.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn removes_registration() {
+        let dir = fixture_dir("remove");
+        let (service, _, copy, record) = registered_copy(&dir, "remove");
+        fs::remove_dir_all(&copy).unwrap();
+
+        let result = service.remove_duplicate(record.id).unwrap();
+        assert!(result.success, "{:?}", result.message);
+        assert!(service.list_duplicates().is_empty());
+        assert!(service.remove_duplicate(record.id).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,394 @@
+//! 应用权限与沙盒能力分析服务实现
+//!
+//! 从主程序 LC_CODE_SIGNATURE 指向的签名超级块中取出 entitlements，
+//! 结合 Info.plist 中的用途说明（如 NSCameraUsageDescription）和 TCC 授权记录，
+//! 汇总每个应用的摄像头、麦克风、完全磁盘访问、网络服务和非沙盒文件访问等能力。
+
+use std::path::Path;
+use std::process::Command;
+use crate::models::entitlements::{AppCapabilities, AppCapability, CapabilityReport};
+use crate::services::app_service::AppService;
+use crate::services::macho_service::{self, MachOService};
+
+/// 签名超级块
+const CSMAGIC_EMBEDDED_SIGNATURE: u32 = 0xfade_0cc0;
+/// 代码目录
+const CSMAGIC_CODEDIRECTORY: u32 = 0xfade_0c02;
+/// XML 格式的 entitlements
+const CSMAGIC_EMBEDDED_ENTITLEMENTS: u32 = 0xfade_7171;
+/// 代码目录标志: Hardened Runtime
+const CS_RUNTIME: u32 = 0x0001_0000;
+
+/// 沙盒 entitlement
+const APP_SANDBOX_KEY: &str = "com.apple.security.app-sandbox";
+/// 允许沙盒应用访问任意路径的临时例外前缀
+const FILE_EXCEPTION_PREFIX: &str = "com.apple.security.temporary-exception.files.";
+
+/// TCC 中的完全磁盘访问服务
+const TCC_FULL_DISK_ACCESS: &str = "kTCCServiceSystemPolicyAllFiles";
+
+/// 能力定义
+struct CapabilityDef {
+    /// 能力标识
+    id: &'static str,
+    /// 能力名称
+    name: &'static str,
+    /// 授予该能力的 entitlement
+    entitlements: &'static [&'static str],
+    /// Info.plist 中的用途说明键
+    usage_keys: &'static [&'static str],
+    /// 对应的 TCC 服务
+    tcc_service: Option<&'static str>,
+}
+
+const CAPABILITIES: [CapabilityDef; 12] = [
+    CapabilityDef {
+        id: "camera",
+        name: "摄像头",
+        entitlements: &["com.apple.security.device.camera"],
+        usage_keys: &["NSCameraUsageDescription"],
+        tcc_service: Some("kTCCServiceCamera"),
+    },
+    CapabilityDef {
+        id: "microphone",
+        name: "麦克风",
+        entitlements: &["com.apple.security.device.audio-input", "com.apple.security.device.microphone"],
+        usage_keys: &["NSMicrophoneUsageDescription"],
+        tcc_service: Some("kTCCServiceMicrophone"),
+    },
+    CapabilityDef {
+        id: "full_disk_access",
+        name: "完全磁盘访问",
+        entitlements: &[],
+        usage_keys: &[],
+        tcc_service: Some(TCC_FULL_DISK_ACCESS),
+    },
+    CapabilityDef {
+        id: "screen_capture",
+        name: "屏幕录制",
+        entitlements: &[],
+        usage_keys: &[],
+        tcc_service: Some("kTCCServiceScreenCapture"),
+    },
+    CapabilityDef {
+        id: "location",
+        name: "定位",
+        entitlements: &["com.apple.security.personal-information.location"],
+        usage_keys: &["NSLocationUsageDescription", "NSLocationWhenInUseUsageDescription", "NSLocationAlwaysAndWhenInUseUsageDescription"],
+        tcc_service: None,
+    },
+    CapabilityDef {
+        id: "contacts",
+        name: "通讯录",
+        entitlements: &["com.apple.security.personal-information.addressbook"],
+        usage_keys: &["NSContactsUsageDescription"],
+        tcc_service: Some("kTCCServiceAddressBook"),
+    },
+    CapabilityDef {
+        id: "calendars",
+        name: "日历",
+        entitlements: &["com.apple.security.personal-information.calendars"],
+        usage_keys: &["NSCalendarsUsageDescription"],
+        tcc_service: Some("kTCCServiceCalendar"),
+    },
+    CapabilityDef {
+        id: "photos",
+        name: "照片",
+        entitlements: &["com.apple.security.personal-information.photos-library"],
+        usage_keys: &["NSPhotoLibraryUsageDescription"],
+        tcc_service: Some("kTCCServicePhotos"),
+    },
+    CapabilityDef {
+        id: "bluetooth",
+        name: "蓝牙",
+        entitlements: &["com.apple.security.device.bluetooth"],
+        usage_keys: &["NSBluetoothAlwaysUsageDescription", "NSBluetoothPeripheralUsageDescription"],
+        tcc_service: None,
+    },
+    CapabilityDef {
+        id: "apple_events",
+        name: "自动化（Apple Events）",
+        entitlements: &["com.apple.security.automation.apple-events"],
+        usage_keys: &["NSAppleEventsUsageDescription"],
+        tcc_service: Some("kTCCServiceAppleEvents"),
+    },
+    CapabilityDef {
+        id: "network_client",
+        name: "网络访问",
+        entitlements: &["com.apple.security.network.client"],
+        usage_keys: &[],
+        tcc_service: None,
+    },
+    CapabilityDef {
+        id: "network_server",
+        name: "网络服务（监听端口）",
+        entitlements: &["com.apple.security.network.server"],
+        usage_keys: &[],
+        tcc_service: None,
+    },
+];
+
+/// 签名超级块中与权限相关的内容
+#[derive(Debug, Default)]
+pub struct SignatureBlobs {
+    /// entitlements
+    pub entitlements: Option<plist::Value>,
+    /// 代码目录标志
+    pub code_directory_flags: Option<u32>,
+}
+
+fn read_be_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+    Some(u32::from_be_bytes(bytes))
+}
+
+/// 解析代码签名超级块（CS_SuperBlob，大端序）
+pub fn parse_superblob(data: &[u8]) -> Option<SignatureBlobs> {
+    if read_be_u32(data, 0)? != CSMAGIC_EMBEDDED_SIGNATURE {
+        return None;
+    }
+    let length = (read_be_u32(data, 4)? as usize).min(data.len());
+    let count = read_be_u32(data, 8)? as usize;
+    let data = &data[..length];
+
+    let mut blobs = SignatureBlobs::default();
+    for i in 0..count {
+        // 索引项: type(4) + offset(4)
+        let Some(offset) = read_be_u32(data, 12 + i * 8 + 4) else {
+            break;
+        };
+        let offset = offset as usize;
+        let (Some(magic), Some(blob_len)) = (read_be_u32(data, offset), read_be_u32(data, offset + 4)) else {
+            continue;
+        };
+        let Some(blob) = data.get(offset..offset.saturating_add(blob_len as usize)) else {
+            continue;
+        };
+
+        match magic {
+            CSMAGIC_EMBEDDED_ENTITLEMENTS if blobs.entitlements.is_none() => {
+                blobs.entitlements = blob.get(8..)
+                    .and_then(|xml| plist::Value::from_reader_xml(xml).ok());
+            }
+            // 可能存在多个代码目录（SHA-1 与 SHA-256），标志相同，取第一个
+            CSMAGIC_CODEDIRECTORY if blobs.code_directory_flags.is_none() => {
+                blobs.code_directory_flags = read_be_u32(blob, 12);
+            }
+            _ => {}
+        }
+    }
+    Some(blobs)
+}
+
+/// entitlement 值是否表示已授予（布尔 true 或非空的路径列表）
+fn is_granted(value: &plist::Value) -> bool {
+    match value {
+        plist::Value::Boolean(b) => *b,
+        plist::Value::Array(items) => !items.is_empty(),
+        plist::Value::String(s) => !s.is_empty(),
+        _ => false,
+    }
+}
+
+/// 将 plist 值转换为 JSON
+fn plist_to_json(value: &plist::Value) -> serde_json::Value {
+    match value {
+        plist::Value::Boolean(b) => serde_json::Value::Bool(*b),
+        plist::Value::Integer(i) => i.as_signed()
+            .map(serde_json::Value::from)
+            .or_else(|| i.as_unsigned().map(serde_json::Value::from))
+            .unwrap_or(serde_json::Value::Null),
+        plist::Value::Real(r) => serde_json::Value::from(*r),
+        plist::Value::String(s) => serde_json::Value::String(s.clone()),
+        plist::Value::Array(items) => serde_json::Value::Array(items.iter().map(plist_to_json).collect()),
+        plist::Value::Dictionary(dict) => serde_json::Value::Object(
+            dict.iter().map(|(k, v)| (k.clone(), plist_to_json(v))).collect(),
+        ),
+        plist::Value::Data(data) => serde_json::Value::String(format!("<{} bytes>", data.len())),
+        plist::Value::Date(date) => serde_json::Value::String(format!("{:?}", date)),
+        _ => serde_json::Value::Null,
+    }
+}
+
+/// 读取 TCC 数据库中已授权的 (服务, 客户端) 记录
+///
+/// 系统 TCC 数据库需要完全磁盘访问权限才能读取，无权限时返回空列表。
+fn load_tcc_grants() -> Vec<(String, String)> {
+    if !cfg!(target_os = "macos") {
+        return Vec::new();
+    }
+
+    let mut databases = vec!["/Library/Application Support/com.apple.TCC/TCC.db".to_string()];
+    if let Some(home) = dirs::home_dir() {
+        databases.push(home.join("Library/Application Support/com.apple.TCC/TCC.db").to_string_lossy().to_string());
+    }
+
+    let mut grants = Vec::new();
+    for db in databases {
+        if !Path::new(&db).exists() {
+            continue;
+        }
+        // macOS 11 起使用 auth_value（2 = 允许），更早版本使用 allowed
+        for query in [
+            "SELECT service, client FROM access WHERE auth_value = 2",
+            "SELECT service, client FROM access WHERE allowed = 1",
+        ] {
+            let output = Command::new("sqlite3")
+                .args(["-readonly", "-separator", "\t", &db, query])
+                .output();
+            let Ok(output) = output else {
+                break;
+            };
+            if !output.status.success() {
+                continue;
+            }
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            for line in stdout.lines() {
+                if let Some((service, client)) = line.split_once('\t') {
+                    grants.push((service.to_string(), client.to_string()));
+                }
+            }
+            break;
+        }
+    }
+    grants
+}
+
+/// 应用权限分析服务
+pub struct EntitlementService {
+    /// TCC 授权记录 (服务, 客户端 bundle id 或路径)
+    tcc_grants: Vec<(String, String)>,
+}
+
+impl EntitlementService {
+    /// 创建新的权限分析服务实例（读取当前用户可见的 TCC 授权记录）
+    pub fn new() -> Self {
+        Self::with_tcc_grants(load_tcc_grants())
+    }
+
+    /// 使用指定的 TCC 授权记录创建实例
+    pub fn with_tcc_grants(tcc_grants: Vec<(String, String)>) -> Self {
+        EntitlementService { tcc_grants }
+    }
+
+    /// 审计所有已安装应用的权限
+    pub fn analyze_installed_apps(&self) -> Result<CapabilityReport, String> {
+        let installed = AppService::new().get_installed_apps()?;
+        let apps: Vec<AppCapabilities> = installed.apps.iter()
+            .filter_map(|app| self.get_capabilities(&app.path).ok())
+            .collect();
+
+        let unsandboxed_count = apps.iter().filter(|a| !a.sandboxed).count() as u32;
+        let full_disk_access_count = apps.iter()
+            .filter(|a| a.capabilities.iter().any(|c| c.id == "full_disk_access"))
+            .count() as u32;
+
+        Ok(CapabilityReport { apps, unsandboxed_count, full_disk_access_count })
+    }
+
+    /// 获取单个应用的权限汇总
+    pub fn get_capabilities(&self, app_path: &str) -> Result<AppCapabilities, String> {
+        let path = Path::new(app_path);
+        if !path.exists() {
+            return Err("应用不存在".to_string());
+        }
+
+        let info = plist::Value::from_file(path.join("Contents/Info.plist")).ok();
+        let info = info.as_ref().and_then(|v| v.as_dictionary());
+        let info_string = |key: &str| -> Option<String> {
+            info?.get(key)?.as_string().map(|s| s.to_string())
+        };
+        let bundle_id = info_string("CFBundleIdentifier").unwrap_or_default();
+        let app_name = info_string("CFBundleDisplayName")
+            .or_else(|| info_string("CFBundleName"))
+            .or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let signature = MachOService::new().main_executable(path)
+            .and_then(|exe| macho_service::read_code_signature(&exe));
+        let blobs = signature.as_deref().and_then(parse_superblob);
+        let entitlements = blobs.as_ref()
+            .and_then(|b| b.entitlements.as_ref())
+            .and_then(|e| e.as_dictionary());
+        let entitlement = |key: &str| entitlements.and_then(|e| e.get(key));
+
+        let sandboxed = entitlement(APP_SANDBOX_KEY).map(is_granted).unwrap_or(false);
+        let hardened_runtime = blobs.as_ref()
+            .and_then(|b| b.code_directory_flags)
+            .map(|flags| flags & CS_RUNTIME != 0)
+            .unwrap_or(false);
+
+        let mut capabilities = Vec::new();
+        for def in &CAPABILITIES {
+            let mut sources = Vec::new();
+            if def.entitlements.iter().any(|k| entitlement(k).map(is_granted).unwrap_or(false)) {
+                sources.push("entitlement".to_string());
+            }
+            let usage_description = def.usage_keys.iter().find_map(|k| info_string(k));
+            if usage_description.is_some() {
+                sources.push("usage_description".to_string());
+            }
+            if def.tcc_service.map(|s| self.is_tcc_granted(s, &bundle_id, app_path)).unwrap_or(false) {
+                sources.push("tcc".to_string());
+            }
+            if !sources.is_empty() {
+                capabilities.push(AppCapability {
+                    id: def.id.to_string(),
+                    name: def.name.to_string(),
+                    sources,
+                    usage_description,
+                });
+            }
+        }
+
+        // 未启用沙盒，或声明了访问任意路径的临时例外
+        let mut file_sources = Vec::new();
+        if !sandboxed {
+            file_sources.push("no_sandbox".to_string());
+        }
+        let has_file_exception = entitlements
+            .map(|e| e.iter().any(|(k, v)| k.starts_with(FILE_EXCEPTION_PREFIX) && is_granted(v)))
+            .unwrap_or(false);
+        if has_file_exception {
+            file_sources.push("entitlement".to_string());
+        }
+        if !file_sources.is_empty() {
+            capabilities.push(AppCapability {
+                id: "unsandboxed_file_access".to_string(),
+                name: "非沙盒文件访问".to_string(),
+                sources: file_sources,
+                usage_description: None,
+            });
+        }
+
+        Ok(AppCapabilities {
+            app_path: app_path.to_string(),
+            app_name,
+            bundle_id,
+            signed: signature.is_some(),
+            sandboxed,
+            hardened_runtime,
+            entitlements: blobs.as_ref()
+                .and_then(|b| b.entitlements.as_ref())
+                .map(plist_to_json)
+                .unwrap_or_else(|| serde_json::Value::Object(Default::default())),
+            capabilities,
+        })
+    }
+
+    /// 应用是否具备指定能力
+    pub fn has_capability(&self, app_path: &str, capability: &str) -> bool {
+        self.get_capabilities(app_path)
+            .map(|c| c.capabilities.iter().any(|cap| cap.id == capability))
+            .unwrap_or(false)
+    }
+
+    /// TCC 中是否为应用授予了指定服务（客户端可能是 bundle id 或可执行文件路径）
+    fn is_tcc_granted(&self, service: &str, bundle_id: &str, app_path: &str) -> bool {
+        let bundle_prefix = format!("{}/", app_path);
+        self.tcc_grants.iter().any(|(s, client)| {
+            s == service
+                && ((!bundle_id.is_empty() && client == bundle_id) || client.starts_with(&bundle_prefix))
+        })
+    }
+}
@@ -0,0 +1,502 @@
+//! Homebrew 清理分析服务实现
+//!
+//! 直接读取 Cellar、Caskroom 和下载缓存的目录结构（不调用 brew），
+//! 找出未链接的旧版本 keg、Caskroom 中残留的旧版本以及缓存的 bottle 与安装包。
+//! 当前安装版本的下载缓存予以保留（重新安装时可直接使用），只报告旧版本的缓存。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use crate::models::cleaner::CleanItem;
+use crate::models::homebrew::{HomebrewCleanupReport, HomebrewPackageCleanup};
+use crate::services::update_service::compare_versions;
+
+/// cask 安装包常见扩展名（用于从缓存文件名中去掉扩展名得到版本）
+const CASK_ARCHIVE_EXTENSIONS: [&str; 8] = [".tar.gz", ".tar.xz", ".tar.bz2", ".tgz", ".dmg", ".zip", ".pkg", ".xip"];
+
+/// 计算目录或文件大小（不跟随符号链接）
+fn path_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// 列出目录下的非隐藏子目录名
+fn visible_subdirs(path: &Path) -> Vec<String> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries.flatten()
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|n| !n.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 读取符号链接指向的目录名（如 opt/wget -> ../Cellar/wget/1.21.3 得到 1.21.3）
+fn link_target_name(link: &Path) -> Option<String> {
+    let target = fs::read_link(link).ok()?;
+    target.file_name().map(|n| n.to_string_lossy().to_string())
+}
+
+/// 解析缓存文件名 `<名称>--<版本>.<平台>.bottle.tar.gz` 或 `<token>--<版本>.dmg`
+///
+/// 返回 (名称, 版本, 是否为 bottle)
+pub fn parse_cache_name(file_name: &str) -> Option<(String, String, bool)> {
+    let (name, rest) = file_name.split_once("--")?;
+    let name = name.trim_end_matches("_bottle_manifest").to_string();
+    if name.is_empty() || rest.is_empty() {
+        return None;
+    }
+    let rest = rest.trim_end_matches(".incomplete");
+
+    if let Some(index) = rest.find(".bottle") {
+        // 去掉平台标签，如 1.21.3.arm64_sonoma -> 1.21.3
+        let mut version = &rest[..index];
+        if let Some((head, tag)) = version.rsplit_once('.') {
+            if tag.chars().any(|c| c.is_ascii_alphabetic()) {
+                version = head;
+            }
+        }
+        return Some((name, version.to_string(), true));
+    }
+    if file_name.contains("_bottle_manifest--") {
+        return Some((name, rest.to_string(), true));
+    }
+
+    let version = CASK_ARCHIVE_EXTENSIONS.iter()
+        .find_map(|ext| rest.strip_suffix(ext))
+        .unwrap_or(rest);
+    Some((name, version.to_string(), false))
+}
+
+/// 缓存文件归属: (类型, 名称, 版本)
+type CacheOwner = (String, String, String);
+
+/// Homebrew 清理分析服务
+pub struct HomebrewService {
+    prefixes: Vec<PathBuf>,
+    cache_dir: PathBuf,
+}
+
+impl HomebrewService {
+    /// 创建新的 Homebrew 清理分析服务实例
+    pub fn new() -> Self {
+        let home = dirs::home_dir().unwrap_or_default();
+
+        let mut prefixes: Vec<PathBuf> = std::env::var_os("HOMEBREW_PREFIX")
+            .map(PathBuf::from)
+            .into_iter()
+            .collect();
+        for prefix in [
+            PathBuf::from("/opt/homebrew"),
+            PathBuf::from("/usr/local"),
+            PathBuf::from("/home/linuxbrew/.linuxbrew"),
+            home.join(".linuxbrew"),
+        ] {
+            if !prefixes.contains(&prefix) {
+                prefixes.push(prefix);
+            }
+        }
+
+        let cache_dir = std::env::var_os("HOMEBREW_CACHE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                if cfg!(target_os = "macos") {
+                    home.join("Library/Caches/Homebrew")
+                } else {
+                    dirs::cache_dir().unwrap_or_else(|| home.join(".cache")).join("Homebrew")
+                }
+            });
+
+        Self::with_paths(prefixes, cache_dir)
+    }
+
+    /// 使用指定的前缀和缓存目录创建实例
+    pub fn with_paths(prefixes: Vec<PathBuf>, cache_dir: impl Into<PathBuf>) -> Self {
+        HomebrewService { prefixes, cache_dir: cache_dir.into() }
+    }
+
+    /// 分析可清理的旧版本和下载缓存
+    pub fn analyze(&self) -> HomebrewCleanupReport {
+        let mut packages: BTreeMap<(String, String, String), HomebrewPackageCleanup> = BTreeMap::new();
+        let mut items = Vec::new();
+        // 已安装的当前版本: (类型, 名称) -> 版本
+        let mut installed: HashMap<(String, String), String> = HashMap::new();
+
+        let prefixes: Vec<&PathBuf> = self.prefixes.iter()
+            .filter(|p| p.join("Cellar").is_dir() || p.join("Caskroom").is_dir())
+            .collect();
+
+        for prefix in &prefixes {
+            self.scan_cellar(prefix, &mut packages, &mut items, &mut installed);
+            self.scan_caskroom(prefix, &mut packages, &mut items, &mut installed);
+        }
+        self.scan_cache(&mut packages, &mut items, &installed);
+
+        let mut packages: Vec<HomebrewPackageCleanup> = packages.into_values().collect();
+        packages.sort_by(|a, b| {
+            (b.old_versions_size + b.cache_size).cmp(&(a.old_versions_size + a.cache_size))
+        });
+        items.sort_by(|a, b| b.size.cmp(&a.size));
+        let total_size = items.iter().map(|i| i.size).sum();
+
+        HomebrewCleanupReport {
+            prefixes: prefixes.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+            cache_dir: self.cache_dir.to_string_lossy().to_string(),
+            packages,
+            items,
+            total_size,
+        }
+    }
+
+    /// 获取清理项（供系统清理使用）
+    pub fn scan_clean_items(&self) -> Vec<CleanItem> {
+        self.analyze().items
+    }
+
+    /// 扫描 Cellar 中未链接的旧版本
+    fn scan_cellar(
+        &self,
+        prefix: &Path,
+        packages: &mut BTreeMap<(String, String, String), HomebrewPackageCleanup>,
+        items: &mut Vec<CleanItem>,
+        installed: &mut HashMap<(String, String), String>,
+    ) {
+        let cellar = prefix.join("Cellar");
+        for name in visible_subdirs(&cellar) {
+            let formula_dir = cellar.join(&name);
+            let versions = visible_subdirs(&formula_dir);
+            if versions.is_empty() {
+                continue;
+            }
+
+            // 当前版本: opt 链接 > var/homebrew/linked 链接 > 最新版本
+            let current = link_target_name(&prefix.join("opt").join(&name))
+                .or_else(|| link_target_name(&prefix.join("var/homebrew/linked").join(&name)))
+                .filter(|v| versions.contains(v))
+                .or_else(|| versions.iter().max_by(|a, b| compare_versions(a, b)).cloned());
+            if let Some(current) = &current {
+                installed.insert(("formula".to_string(), name.clone()), current.clone());
+            }
+
+            // 被 pin 的 formula 保留所有版本
+            if prefix.join("var/homebrew/pinned").join(&name).exists() {
+                continue;
+            }
+
+            let entry = package_entry(packages, prefix, "formula", &name, current.clone());
+            for version in versions.iter().filter(|v| Some(*v) != current.as_ref()) {
+                let keg = formula_dir.join(version);
+                let size = path_size(&keg);
+                entry.old_versions.push(version.clone());
+                entry.old_versions_size += size;
+                items.push(CleanItem {
+                    type_: "homebrew".to_string(),
+                    path: keg.to_string_lossy().to_string(),
+                    size,
+                    description: format!(
+                        "Homebrew 旧版本: {} {}（当前 {}）",
+                        name, version, current.as_deref().unwrap_or("-")
+                    ),
+                });
+            }
+        }
+    }
+
+    /// 扫描 Caskroom 中残留的旧版本目录
+    fn scan_caskroom(
+        &self,
+        prefix: &Path,
+        packages: &mut BTreeMap<(String, String, String), HomebrewPackageCleanup>,
+        items: &mut Vec<CleanItem>,
+        installed: &mut HashMap<(String, String), String>,
+    ) {
+        let caskroom = prefix.join("Caskroom");
+        for token in visible_subdirs(&caskroom) {
+            let cask_dir = caskroom.join(&token);
+            let versions = visible_subdirs(&cask_dir);
+            // .metadata 下记录了已安装的版本，取最新者为当前版本
+            let current = visible_subdirs(&cask_dir.join(".metadata"))
+                .into_iter()
+                .max_by(|a, b| compare_versions(a, b));
+
+            let Some(current) = current else {
+                // 没有元数据：cask 已被卸载但目录残留
+                let size = path_size(&cask_dir);
+                let entry = package_entry(packages, prefix, "cask", &token, None);
+                entry.old_versions.extend(versions.iter().cloned());
+                entry.old_versions_size += size;
+                items.push(CleanItem {
+                    type_: "homebrew".to_string(),
+                    path: cask_dir.to_string_lossy().to_string(),
+                    size,
+                    description: format!("Homebrew 残留 cask 目录: {}", token),
+                });
+                continue;
+            };
+            installed.insert(("cask".to_string(), token.clone()), current.clone());
+
+            let entry = package_entry(packages, prefix, "cask", &token, Some(current.clone()));
+            for version in versions.iter().filter(|v| **v != current) {
+                let path = cask_dir.join(version);
+                let size = path_size(&path);
+                entry.old_versions.push(version.clone());
+                entry.old_versions_size += size;
+                items.push(CleanItem {
+                    type_: "homebrew".to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    size,
+                    description: format!("Homebrew cask 旧版本: {} {}（当前 {}）", token, version, current),
+                });
+            }
+        }
+    }
+
+    /// 扫描下载缓存中的 bottle 和 cask 安装包
+    fn scan_cache(
+        &self,
+        packages: &mut BTreeMap<(String, String, String), HomebrewPackageCleanup>,
+        items: &mut Vec<CleanItem>,
+        installed: &HashMap<(String, String), String>,
+    ) {
+        // 顶层和 Cask/ 下的符号链接指向 downloads/ 中的实际文件，可据此判断归属
+        let mut owners: HashMap<PathBuf, CacheOwner> = HashMap::new();
+        let mut files: Vec<(PathBuf, Option<CacheOwner>)> = Vec::new();
+
+        for (dir, is_cask_dir) in [(self.cache_dir.clone(), false), (self.cache_dir.join("Cask"), true)] {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                let Some((name, version, is_bottle)) = parse_cache_name(&file_name) else {
+                    continue;
+                };
+                let kind = if is_cask_dir || !is_bottle { "cask" } else { "formula" };
+                let owner = (kind.to_string(), name, version);
+                let Ok(metadata) = fs::symlink_metadata(&path) else {
+                    continue;
+                };
+                if metadata.file_type().is_symlink() {
+                    if let Ok(target) = fs::canonicalize(&path) {
+                        owners.insert(target, owner);
+                    }
+                } else if metadata.is_file() {
+                    // 旧版缓存布局直接把文件放在顶层
+                    files.push((path, Some(owner)));
+                }
+            }
+        }
+
+        if let Ok(entries) = fs::read_dir(self.cache_dir.join("downloads")) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_file() {
+                    continue;
+                }
+                let resolved = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+                let owner = owners.get(&resolved).cloned().or_else(|| {
+                    // downloads/<sha256>--<名称>--<版本>...
+                    let file_name = entry.file_name().to_string_lossy().to_string();
+                    let (_, rest) = file_name.split_once("--")?;
+                    let (name, version, is_bottle) = parse_cache_name(rest)?;
+                    is_bottle.then(|| ("formula".to_string(), name, version))
+                });
+                files.push((path, owner));
+            }
+        }
+
+        for (path, owner) in files {
+            let incomplete = path.to_string_lossy().ends_with(".incomplete");
+            let is_current = owner.as_ref()
+                .map(|(kind, name, version)| installed.get(&(kind.clone(), name.clone())) == Some(version))
+                .unwrap_or(false);
+            if is_current && !incomplete {
+                continue;
+            }
+
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            let description = match &owner {
+                _ if incomplete => "Homebrew 未完成的下载".to_string(),
+                Some((_, name, version)) => format!("Homebrew 下载缓存: {} {}（过期）", name, version),
+                None => format!(
+                    "Homebrew 下载缓存: {}",
+                    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+                ),
+            };
+
+            if let Some((kind, name, _)) = &owner {
+                let key = (String::new(), kind.clone(), name.clone());
+                let current = installed.get(&(kind.clone(), name.clone())).cloned();
+                let entry = packages.entry(key).or_insert_with(|| HomebrewPackageCleanup {
+                    name: name.clone(),
+                    kind: kind.clone(),
+                    prefix: String::new(),
+                    current_version: current,
+                    old_versions: Vec::new(),
+                    old_versions_size: 0,
+                    cache_size: 0,
+                });
+                entry.cache_size += size;
+            }
+
+            items.push(CleanItem {
+                type_: "homebrew".to_string(),
+                path: path.to_string_lossy().to_string(),
+                size,
+                description,
+            });
+        }
+
+        // 缓存大小合并到对应前缀下的条目
+        let cache_entries: Vec<(String, String)> = packages.keys()
+            .filter(|(prefix, _, _)| prefix.is_empty())
+            .map(|(_, kind, name)| (kind.clone(), name.clone()))
+            .collect();
+        for (kind, name) in cache_entries {
+            let target = packages.keys()
+                .find(|(prefix, k, n)| !prefix.is_empty() && *k == kind && *n == name)
+                .cloned();
+            if let Some(target) = target {
+                if let Some(cached) = packages.remove(&(String::new(), kind, name)) {
+                    if let Some(entry) = packages.get_mut(&target) {
+                        entry.cache_size += cached.cache_size;
+                    }
+                }
+            }
+        }
+
+        // 没有可清理内容的条目不展示
+        packages.retain(|_, p| p.old_versions_size > 0 || p.cache_size > 0 || !p.old_versions.is_empty());
+    }
+}
+
+/// 获取或创建 formula/cask 汇总条目
+fn package_entry<'a>(
+    packages: &'a mut BTreeMap<(String, String, String), HomebrewPackageCleanup>,
+    prefix: &Path,
+    kind: &str,
+    name: &str,
+    current_version: Option<String>,
+) -> &'a mut HomebrewPackageCleanup {
+    let prefix = prefix.to_string_lossy().to_string();
+    packages.entry((prefix.clone(), kind.to_string(), name.to_string()))
+        .or_insert_with(|| HomebrewPackageCleanup {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            prefix,
+            current_version,
+            old_versions: Vec::new(),
+            old_versions_size: 0,
+            cache_size: 0,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-homebrew-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_file(path: &Path, size: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; size]).unwrap();
+    }
+
+    #[test]
+    fn parses_cache_names() {
+        assert_eq!(
+            parse_cache_name("wget--1.21.3.arm64_sonoma.bottle.tar.gz"),
+            Some(("wget".to_string(), "1.21.3".to_string(), true))
+        );
+        assert_eq!(
+            parse_cache_name("wget_bottle_manifest--1.21.3"),
+            Some(("wget".to_string(), "1.21.3".to_string(), true))
+        );
+        assert_eq!(
+            parse_cache_name("firefox--120.0.dmg"),
+            Some(("firefox".to_string(), "120.0".to_string(), false))
+        );
+        assert_eq!(parse_cache_name("README"), None);
+    }
+
+    #[test]
+    fn finds_old_kegs_and_casks() {
+        let root = fixture_dir("cellar");
+        let prefix = root.join("prefix");
+        write_file(&prefix.join("Cellar/wget/1.20/bin/wget"), 100);
+        write_file(&prefix.join("Cellar/wget/1.21.3/bin/wget"), 200);
+        fs::create_dir_all(prefix.join("opt")).unwrap();
+        symlink("../Cellar/wget/1.21.3", prefix.join("opt/wget")).unwrap();
+        write_file(&prefix.join("Cellar/pinned/1.0/x"), 50);
+        write_file(&prefix.join("Cellar/pinned/2.0/x"), 50);
+        fs::create_dir_all(prefix.join("var/homebrew/pinned")).unwrap();
+        symlink("../../../Cellar/pinned/1.0", prefix.join("var/homebrew/pinned/pinned")).unwrap();
+        write_file(&prefix.join("Caskroom/firefox/119.0/Firefox.app/x"), 300);
+        write_file(&prefix.join("Caskroom/firefox/120.0/Firefox.app/x"), 300);
+        fs::create_dir_all(prefix.join("Caskroom/firefox/.metadata/120.0/20231101/Casks")).unwrap();
+        write_file(&prefix.join("Caskroom/gone/1.0/Gone.app/x"), 70);
+
+        let report = HomebrewService::with_paths(vec![prefix.clone(), root.join("missing")], root.join("cache")).analyze();
+        assert_eq!(report.prefixes, vec![prefix.to_string_lossy().to_string()]);
+
+        let wget = report.packages.iter().find(|p| p.name == "wget").unwrap();
+        assert_eq!(wget.current_version.as_deref(), Some("1.21.3"));
+        assert_eq!((wget.old_versions.clone(), wget.old_versions_size), (vec!["1.20".to_string()], 100));
+        let firefox = report.packages.iter().find(|p| p.name == "firefox").unwrap();
+        assert_eq!(firefox.old_versions, vec!["119.0".to_string()]);
+        assert!(report.packages.iter().all(|p| p.name != "pinned"));
+        assert!(report.items.iter().any(|i| i.path.ends_with("Caskroom/gone")));
+        assert_eq!(report.total_size, 100 + 300 + 70);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn keeps_cache_of_installed_versions() {
+        let root = fixture_dir("cache");
+        let prefix = root.join("prefix");
+        write_file(&prefix.join("Cellar/wget/1.21.3/bin/wget"), 200);
+        write_file(&prefix.join("Caskroom/firefox/120.0/Firefox.app/x"), 300);
+        fs::create_dir_all(prefix.join("Caskroom/firefox/.metadata/120.0/20231101/Casks")).unwrap();
+
+        let cache = root.join("cache");
+        write_file(&cache.join("downloads/aaa--wget--1.21.3.arm64_sonoma.bottle.tar.gz"), 1000);
+        write_file(&cache.join("downloads/bbb--wget--1.20.arm64_sonoma.bottle.tar.gz"), 900);
+        symlink(
+            "downloads/aaa--wget--1.21.3.arm64_sonoma.bottle.tar.gz",
+            cache.join("wget--1.21.3.arm64_sonoma.bottle.tar.gz"),
+        ).unwrap();
+        write_file(&cache.join("downloads/ccc--Firefox 120.0.dmg"), 2000);
+        write_file(&cache.join("downloads/ddd--Firefox 119.0.dmg"), 1900);
+        fs::create_dir_all(cache.join("Cask")).unwrap();
+        symlink("../downloads/ccc--Firefox 120.0.dmg", cache.join("Cask/firefox--120.0.dmg")).unwrap();
+        symlink("../downloads/ddd--Firefox 119.0.dmg", cache.join("Cask/firefox--119.0.dmg")).unwrap();
+        write_file(&cache.join("downloads/eee--partial.incomplete"), 5);
+
+        let report = HomebrewService::with_paths(vec![prefix], &cache).analyze();
+        let paths: Vec<&str> = report.items.iter().map(|i| i.path.as_str()).collect();
+        assert!(paths.iter().all(|p| !p.contains("aaa--") && !p.contains("ccc--")), "{:?}", paths);
+        assert_eq!(report.total_size, 900 + 1900 + 5);
+
+        let wget = report.packages.iter().find(|p| p.name == "wget").unwrap();
+        assert_eq!(wget.cache_size, 900);
+        let firefox = report.packages.iter().find(|p| p.name == "firefox").unwrap();
+        assert_eq!(firefox.cache_size, 1900);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,522 @@
+//! 副本图标生成服务实现
+//!
+//! 读取源应用 .icns 中最大的 PNG 图像，在右下角叠加编号角标（或在边缘叠加彩色圆环），
+//! 重新生成所有尺寸并用纯 Rust 编码为 .icns，写入副本并修改 CFBundleIconFile。
+
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// PNG 文件签名
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// ICNS 中 PNG 图像类型及像素尺寸（写入时按此顺序）
+const ICNS_PNG_TYPES: [(&[u8; 4], u32); 11] = [
+    (b"icp4", 16),
+    (b"icp5", 32),
+    (b"icp6", 64),
+    (b"ic07", 128),
+    (b"ic08", 256),
+    (b"ic09", 512),
+    (b"ic10", 1024),
+    (b"ic11", 32),
+    (b"ic12", 64),
+    (b"ic13", 256),
+    (b"ic14", 512),
+];
+
+/// ICNS 条目: (类型, 数据)
+pub type IcnsEntry = ([u8; 4], Vec<u8>);
+
+/// 副本图标文件名（不含扩展名）
+const DUPLICATE_ICON_NAME: &str = "MoleDuplicateIcon";
+
+/// 5x7 点阵数字字形，每行低 5 位有效
+const DIGIT_GLYPHS: [[u8; 7]; 10] = [
+    [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+    [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+    [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+    [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+    [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+    [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+    [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+    [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+    [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+];
+
+/// 没有指定颜色时按副本编号轮流使用的颜色
+const BADGE_PALETTE: [[u8; 3]; 6] = [
+    [0xFF, 0x3B, 0x30],
+    [0x00, 0x7A, 0xFF],
+    [0x34, 0xC7, 0x59],
+    [0xFF, 0x95, 0x00],
+    [0xAF, 0x52, 0xDE],
+    [0x5A, 0xC8, 0xFA],
+];
+
+/// 图标角标样式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IconBadge {
+    /// 右下角带编号的圆形角标
+    Number { number: u32, color: [u8; 3] },
+    /// 图标边缘的彩色圆环
+    Ring { color: [u8; 3] },
+}
+
+impl IconBadge {
+    /// 根据副本编号和 emoji 生成角标
+    ///
+    /// 纯色圆形或方块 emoji（如 🔵、🟩）只表示颜色，生成该颜色的圆环；
+    /// 其他彩色 emoji 生成该颜色的编号角标，否则按编号选取颜色
+    pub fn for_duplicate(copy_number: u32, icon_emoji: Option<&str>) -> Self {
+        if let Some(color) = icon_emoji.filter(|e| is_color_swatch(e)).and_then(emoji_color) {
+            return IconBadge::Ring { color };
+        }
+        let color = icon_emoji
+            .and_then(emoji_color)
+            .unwrap_or(BADGE_PALETTE[copy_number as usize % BADGE_PALETTE.len()]);
+        IconBadge::Number { number: copy_number, color }
+    }
+}
+
+/// 是否为只表示颜色的圆形或方块 emoji
+fn is_color_swatch(emoji: &str) -> bool {
+    matches!(
+        emoji.chars().next(),
+        Some('🔴' | '🟠' | '🟡' | '🟢' | '🔵' | '🟣' | '🟤' | '⚫' | '⚪'
+            | '🟥' | '🟧' | '🟨' | '🟩' | '🟦' | '🟪' | '🟫' | '⬛' | '⬜')
+    )
+}
+
+/// 彩色 emoji 对应的颜色
+fn emoji_color(emoji: &str) -> Option<[u8; 3]> {
+    let first = emoji.chars().next()?;
+    let color = match first {
+        '🔴' | '🟥' | '❤' | '🍎' | '🍓' => [0xFF, 0x3B, 0x30],
+        '🟠' | '🟧' | '🧡' | '🍊' => [0xFF, 0x95, 0x00],
+        '🟡' | '🟨' | '💛' | '⭐' | '🌟' | '🍋' => [0xFF, 0xCC, 0x00],
+        '🟢' | '🟩' | '💚' | '🍀' | '🌲' => [0x34, 0xC7, 0x59],
+        '🔵' | '🟦' | '💙' | '🐳' | '🌊' => [0x00, 0x7A, 0xFF],
+        '🟣' | '🟪' | '💜' | '🍇' => [0xAF, 0x52, 0xDE],
+        '🟤' | '🟫' | '🤎' => [0xA2, 0x84, 0x5E],
+        '⚫' | '⬛' | '🖤' => [0x1C, 0x1C, 0x1E],
+        '⚪' | '⬜' | '🤍' => [0x8E, 0x8E, 0x93],
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// RGBA 图像
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    /// 每像素 4 字节，非预乘 alpha
+    pub data: Vec<u8>,
+}
+
+impl RgbaImage {
+    fn new(width: u32, height: u32) -> Self {
+        RgbaImage { width, height, data: vec![0; (width * height * 4) as usize] }
+    }
+
+    /// 以 coverage（0..=1）为不透明度把颜色混合到像素上
+    fn blend(&mut self, x: u32, y: u32, color: [u8; 3], coverage: f32) {
+        if x >= self.width || y >= self.height || coverage <= 0.0 {
+            return;
+        }
+        let index = ((y * self.width + x) * 4) as usize;
+        let pixel = &mut self.data[index..index + 4];
+        let src_alpha = coverage.min(1.0);
+        let dst_alpha = pixel[3] as f32 / 255.0;
+        let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+        if out_alpha <= 0.0 {
+            return;
+        }
+        for channel in 0..3 {
+            let src = color[channel] as f32;
+            let dst = pixel[channel] as f32;
+            pixel[channel] = ((src * src_alpha + dst * dst_alpha * (1.0 - src_alpha)) / out_alpha).round() as u8;
+        }
+        pixel[3] = (out_alpha * 255.0).round() as u8;
+    }
+}
+
+/// 解析 .icns，返回 (类型, 数据) 列表
+pub fn parse_icns(data: &[u8]) -> Result<Vec<IcnsEntry>, String> {
+    if data.len() < 8 || &data[0..4] != b"icns" {
+        return Err("不是有效的 icns 文件".to_string());
+    }
+    let total = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize;
+    let end = total.min(data.len());
+    let mut entries = Vec::new();
+    let mut offset = 8;
+    while offset + 8 <= end {
+        let mut kind = [0u8; 4];
+        kind.copy_from_slice(&data[offset..offset + 4]);
+        let length = u32::from_be_bytes([
+            data[offset + 4], data[offset + 5], data[offset + 6], data[offset + 7],
+        ]) as usize;
+        if length < 8 || offset + length > end {
+            break;
+        }
+        entries.push((kind, data[offset + 8..offset + length].to_vec()));
+        offset += length;
+    }
+    Ok(entries)
+}
+
+/// 编码 .icns
+pub fn encode_icns(entries: &[IcnsEntry]) -> Vec<u8> {
+    let total: usize = 8 + entries.iter().map(|(_, data)| data.len() + 8).sum::<usize>();
+    let mut output = Vec::with_capacity(total);
+    output.extend_from_slice(b"icns");
+    output.extend_from_slice(&(total as u32).to_be_bytes());
+    for (kind, data) in entries {
+        output.extend_from_slice(kind);
+        output.extend_from_slice(&((data.len() + 8) as u32).to_be_bytes());
+        output.extend_from_slice(data);
+    }
+    output
+}
+
+/// 解码 PNG 为 RGBA
+pub fn decode_png(data: &[u8]) -> Result<RgbaImage, String> {
+    let mut decoder = png::Decoder::new(Cursor::new(data));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|e| format!("PNG 解码失败: {}", e))?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(|e| format!("PNG 解码失败: {}", e))?;
+    buffer.truncate(info.buffer_size());
+
+    let data = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => buffer.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return Err("不支持的 PNG 颜色类型".to_string()),
+    };
+    Ok(RgbaImage { width: info.width, height: info.height, data })
+}
+
+/// 编码 RGBA 为 PNG
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, String> {
+    let mut output = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut output, image.width, image.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| format!("PNG 编码失败: {}", e))?;
+        writer.write_image_data(&image.data).map_err(|e| format!("PNG 编码失败: {}", e))?;
+        writer.finish().map_err(|e| format!("PNG 编码失败: {}", e))?;
+    }
+    Ok(output)
+}
+
+/// 缩放图像（缩小时按区域平均，放大时取最近像素；按预乘 alpha 计算避免透明边缘发暗）
+pub fn resize(image: &RgbaImage, width: u32, height: u32) -> RgbaImage {
+    let mut output = RgbaImage::new(width, height);
+    let scale_x = image.width as f64 / width as f64;
+    let scale_y = image.height as f64 / height as f64;
+
+    for y in 0..height {
+        let y0 = (y as f64 * scale_y).floor() as u32;
+        let y1 = (((y + 1) as f64 * scale_y).ceil() as u32).clamp(y0 + 1, image.height);
+        for x in 0..width {
+            let x0 = (x as f64 * scale_x).floor() as u32;
+            let x1 = (((x + 1) as f64 * scale_x).ceil() as u32).clamp(x0 + 1, image.width);
+
+            let mut sum = [0f64; 4];
+            for sy in y0..y1 {
+                for sx in x0..x1 {
+                    let index = ((sy * image.width + sx) * 4) as usize;
+                    let alpha = image.data[index + 3] as f64;
+                    for (total, value) in sum.iter_mut().zip(&image.data[index..index + 3]) {
+                        *total += *value as f64 * alpha;
+                    }
+                    sum[3] += alpha;
+                }
+            }
+            let count = ((y1 - y0) * (x1 - x0)) as f64;
+            let index = ((y * width + x) * 4) as usize;
+            if sum[3] > 0.0 {
+                for (value, total) in output.data[index..index + 3].iter_mut().zip(&sum) {
+                    *value = (total / sum[3]).round() as u8;
+                }
+            }
+            output.data[index + 3] = (sum[3] / count).round() as u8;
+        }
+    }
+    output
+}
+
+/// 像素被圆（或圆环）覆盖的比例，4x4 超采样抗锯齿
+fn circle_coverage(x: u32, y: u32, cx: f32, cy: f32, outer: f32, inner: f32) -> f32 {
+    let mut covered = 0;
+    for sy in 0..4 {
+        for sx in 0..4 {
+            let px = x as f32 + (sx as f32 + 0.5) / 4.0 - cx;
+            let py = y as f32 + (sy as f32 + 0.5) / 4.0 - cy;
+            let distance = (px * px + py * py).sqrt();
+            if distance <= outer && distance >= inner {
+                covered += 1;
+            }
+        }
+    }
+    covered as f32 / 16.0
+}
+
+/// 在图像上绘制数字（点阵字形按整数倍放大）
+fn draw_number(image: &mut RgbaImage, number: u32, cx: f32, cy: f32, max_height: f32, color: [u8; 3]) {
+    let digits: Vec<usize> = number.to_string().bytes().map(|b| (b - b'0') as usize).collect();
+    // 字形 5x7，字间距 1
+    let columns = digits.len() as f32 * 6.0 - 1.0;
+    let scale = (max_height / 7.0).min(max_height * 1.2 / columns).floor().max(1.0) as u32;
+    let width = (columns as u32) * scale;
+    let height = 7 * scale;
+    let left = (cx - width as f32 / 2.0).round().max(0.0) as u32;
+    let top = (cy - height as f32 / 2.0).round().max(0.0) as u32;
+
+    for (position, digit) in digits.iter().enumerate() {
+        let glyph_left = left + position as u32 * 6 * scale;
+        for (row, bits) in DIGIT_GLYPHS[*digit].iter().enumerate() {
+            for column in 0..5 {
+                if bits & (0b10000 >> column) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        image.blend(glyph_left + column * scale + dx, top + row as u32 * scale + dy, color, 1.0);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 在图像上叠加角标
+pub fn apply_badge(image: &mut RgbaImage, badge: &IconBadge) {
+    let size = image.width.min(image.height) as f32;
+    match badge {
+        IconBadge::Number { number, color } => {
+            let radius = size * 0.21;
+            let border = (size * 0.02).max(1.0);
+            let cx = image.width as f32 - radius - size * 0.04;
+            let cy = image.height as f32 - radius - size * 0.04;
+            for y in 0..image.height {
+                for x in 0..image.width {
+                    // 白色描边
+                    image.blend(x, y, [0xFF, 0xFF, 0xFF], circle_coverage(x, y, cx, cy, radius, radius - border));
+                    image.blend(x, y, *color, circle_coverage(x, y, cx, cy, radius - border, 0.0));
+                }
+            }
+            // 16px 图标太小，只保留色块
+            if size >= 32.0 {
+                draw_number(image, *number, cx, cy, radius, [0xFF, 0xFF, 0xFF]);
+            }
+        }
+        IconBadge::Ring { color } => {
+            let outer = size * 0.48;
+            let inner = outer - (size * 0.06).max(1.5);
+            let cx = image.width as f32 / 2.0;
+            let cy = image.height as f32 / 2.0;
+            for y in 0..image.height {
+                for x in 0..image.width {
+                    image.blend(x, y, *color, circle_coverage(x, y, cx, cy, outer, inner));
+                }
+            }
+        }
+    }
+}
+
+/// 从 .icns 中取出像素尺寸最大的 PNG 图像
+pub fn largest_png(entries: &[IcnsEntry]) -> Option<RgbaImage> {
+    let mut candidates: Vec<&Vec<u8>> = entries.iter()
+        .filter(|(_, data)| data.starts_with(&PNG_SIGNATURE))
+        .map(|(_, data)| data)
+        .collect();
+    // 以实际解码尺寸为准（ic10 等类型的数据可能小于名义尺寸）
+    let mut best: Option<RgbaImage> = None;
+    candidates.sort_by_key(|data| std::cmp::Reverse(data.len()));
+    for data in candidates {
+        if let Ok(image) = decode_png(data) {
+            if best.as_ref().map(|b| image.width > b.width).unwrap_or(true) {
+                best = Some(image);
+            }
+        }
+    }
+    best
+}
+
+/// 由源图像生成带角标的 .icns 数据
+pub fn badged_icns(source: &RgbaImage, badge: &IconBadge) -> Result<Vec<u8>, String> {
+    let mut entries = Vec::new();
+    for (kind, size) in ICNS_PNG_TYPES {
+        let mut image = resize(source, size, size);
+        apply_badge(&mut image, badge);
+        entries.push((*kind, encode_png(&image)?));
+    }
+    Ok(encode_icns(&entries))
+}
+
+/// 副本图标生成服务
+pub struct IconBadgeService;
+
+impl IconBadgeService {
+    /// 创建新的副本图标生成服务实例
+    pub fn new() -> Self {
+        IconBadgeService
+    }
+
+    /// 应用包当前使用的 .icns 文件
+    fn bundle_icon_file(&self, info: &plist::Dictionary, bundle: &Path) -> Option<PathBuf> {
+        let resources = bundle.join("Contents/Resources");
+        let name = info.get("CFBundleIconFile")
+            .and_then(|v| v.as_string())
+            .unwrap_or("AppIcon");
+        let file = if name.ends_with(".icns") {
+            resources.join(name)
+        } else {
+            resources.join(format!("{}.icns", name))
+        };
+        file.exists().then_some(file)
+    }
+
+    /// 为应用包生成带角标的图标并修改 Info.plist，返回新图标路径
+    pub fn badge_bundle_icon(&self, bundle: &Path, badge: &IconBadge) -> Result<PathBuf, String> {
+        let info_path = bundle.join("Contents/Info.plist");
+        let info_data = fs::read(&info_path).map_err(|e| format!("无法读取 Info.plist: {}", e))?;
+        let binary = info_data.starts_with(b"bplist");
+        let mut info = plist::Value::from_reader(Cursor::new(&info_data))
+            .map_err(|e| format!("无法解析 Info.plist: {}", e))?;
+        let dict = info.as_dictionary_mut().ok_or_else(|| "Info.plist 格式错误".to_string())?;
+
+        let icon_file = self.bundle_icon_file(dict, bundle)
+            .ok_or_else(|| "找不到应用图标文件".to_string())?;
+        let icns = fs::read(&icon_file).map_err(|e| format!("无法读取图标: {}", e))?;
+        let source = largest_png(&parse_icns(&icns)?)
+            .ok_or_else(|| "图标中没有可用的 PNG 图像".to_string())?;
+
+        let output = bundle.join("Contents/Resources").join(format!("{}.icns", DUPLICATE_ICON_NAME));
+        fs::write(&output, badged_icns(&source, badge)?).map_err(|e| format!("无法写入图标: {}", e))?;
+
+        // CFBundleIconName 指向 Assets.car 中的图标且优先级更高，需要移除才能使用新图标
+        dict.insert("CFBundleIconFile".to_string(), DUPLICATE_ICON_NAME.into());
+        dict.remove("CFBundleIconName");
+        let written = if binary {
+            info.to_file_binary(&info_path)
+        } else {
+            info.to_file_xml(&info_path)
+        };
+        written.map_err(|e| format!("无法写入 Info.plist: {}", e))?;
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GREEN: [u8; 4] = [10, 200, 30, 255];
+
+    fn solid(size: u32) -> RgbaImage {
+        RgbaImage { width: size, height: size, data: GREEN.repeat((size * size) as usize) }
+    }
+
+    fn pixel(image: &RgbaImage, x: u32, y: u32) -> [u8; 4] {
+        let index = ((y * image.width + x) * 4) as usize;
+        [image.data[index], image.data[index + 1], image.data[index + 2], image.data[index + 3]]
+    }
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-icon-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn picks_largest_png_entry() {
+        let entries = vec![
+            (*b"ic07", encode_png(&solid(128)).unwrap()),
+            (*b"it32", vec![1, 2, 3]),
+            (*b"ic08", encode_png(&solid(256)).unwrap()),
+        ];
+        let parsed = parse_icns(&encode_icns(&entries)).unwrap();
+        assert_eq!(parsed, entries);
+        let largest = largest_png(&parsed).unwrap();
+        assert_eq!((largest.width, largest.height), (256, 256));
+        assert!(largest_png(&[(*b"it32", vec![1, 2, 3])]).is_none());
+        assert!(parse_icns(b"not an icns").is_err());
+    }
+
+    #[test]
+    fn badged_icns_contains_every_size() {
+        let badge = IconBadge::for_duplicate(2, Some("💙"));
+        assert_eq!(badge, IconBadge::Number { number: 2, color: [0, 0x7A, 0xFF] });
+        let entries = parse_icns(&badged_icns(&solid(256), &badge).unwrap()).unwrap();
+
+        assert_eq!(entries.len(), ICNS_PNG_TYPES.len());
+        for (kind, size) in ICNS_PNG_TYPES {
+            let (_, data) = entries.iter().find(|(k, _)| k == kind).unwrap();
+            let image = decode_png(data).unwrap();
+            assert_eq!((image.width, image.height), (size, size), "{}", String::from_utf8_lossy(kind));
+        }
+
+        // 左上角保持原样，右下角叠加了角标底色和白色数字
+        let (_, data) = entries.iter().find(|(k, _)| k == b"ic10").unwrap();
+        let image = decode_png(data).unwrap();
+        assert_eq!(pixel(&image, 0, 0), GREEN);
+        let center = 1024 - 256;
+        assert_eq!(pixel(&image, center - 150, center)[..3], [0, 0x7A, 0xFF]);
+        let white = (center - 100..center + 100)
+            .flat_map(|y| (center - 100..center + 100).map(move |x| (x, y)))
+            .filter(|&(x, y)| pixel(&image, x, y)[..3] == [255, 255, 255])
+            .count();
+        assert!(white > 1000, "white pixels: {}", white);
+    }
+
+    #[test]
+    fn ring_badge_colors_the_edge() {
+        assert_eq!(IconBadge::for_duplicate(2, Some("🔵")), IconBadge::Ring { color: [0, 0x7A, 0xFF] });
+        let mut image = solid(64);
+        apply_badge(&mut image, &IconBadge::Ring { color: [255, 0, 0] });
+        assert_eq!(pixel(&image, 2, 32)[..3], [255, 0, 0]);
+        assert_eq!(pixel(&image, 32, 32), GREEN);
+    }
+
+    #[test]
+    fn resize_averages_with_alpha() {
+        let image = RgbaImage { width: 2, height: 1, data: vec![255, 0, 0, 255, 0, 0, 0, 0] };
+        assert_eq!(resize(&image, 1, 1).data, vec![255, 0, 0, 128]);
+        let enlarged = resize(&solid(16), 32, 32);
+        assert_eq!((enlarged.width, enlarged.height), (32, 32));
+        assert!(enlarged.data.chunks_exact(4).all(|p| p == GREEN));
+    }
+
+    #[test]
+    fn badges_bundle_icon_and_updates_info_plist() {
+        let dir = fixture_dir("bundle");
+        let app = dir.join("Chat.app");
+        fs::create_dir_all(app.join("Contents/Resources")).unwrap();
+        let icns = encode_icns(&[(*b"ic09", encode_png(&solid(512)).unwrap())]);
+        fs::write(app.join("Contents/Resources/AppIcon.icns"), icns).unwrap();
+        let mut info = plist::Dictionary::new();
+        info.insert("CFBundleIconFile".to_string(), "AppIcon".into());
+        info.insert("CFBundleIconName".to_string(), "AppIcon".into());
+        plist::Value::Dictionary(info).to_file_xml(app.join("Contents/Info.plist")).unwrap();
+
+        let output = IconBadgeService::new()
+            .badge_bundle_icon(&app, &IconBadge::for_duplicate(3, None))
+            .unwrap();
+        assert_eq!(output, app.join("Contents/Resources/MoleDuplicateIcon.icns"));
+        assert_eq!(parse_icns(&fs::read(&output).unwrap()).unwrap().len(), ICNS_PNG_TYPES.len());
+        let info = plist::Value::from_file(app.join("Contents/Info.plist")).unwrap();
+        let info = info.as_dictionary().unwrap();
+        assert_eq!(info["CFBundleIconFile"].as_string(), Some(DUPLICATE_ICON_NAME));
+        assert!(info.get("CFBundleIconName").is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
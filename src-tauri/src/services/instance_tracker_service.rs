@@ -0,0 +1,288 @@
+//! Mole 启动实例跟踪服务实现
+//!
+//! 记录快速多开和启动配置启动的每个进程（PID、配置、启动时间、可执行文件和参数），
+//! 后台线程检测进程退出，启动后短时间内异常退出的实例标记为崩溃并保留退出状态。
+
+use std::collections::HashMap;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::models::instance::LaunchedInstance;
+use crate::models::launch_profile::LaunchCommand;
+use crate::models::process::Process;
+use crate::services::sampler_service::latest_processes;
+
+/// 默认崩溃判定时间: 启动后该时间内异常退出视为崩溃
+const DEFAULT_CRASH_WINDOW: Duration = Duration::from_secs(10);
+
+/// 等待进程响应 SIGTERM 的时间（100ms 为单位）
+const TERMINATE_TIMEOUT_TICKS: u32 = 30;
+
+/// 后台检测进程退出的间隔
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 跟踪中的实例
+struct TrackedInstance {
+    info: LaunchedInstance,
+    command: LaunchCommand,
+    crash_window: Duration,
+    /// 进程退出后为空
+    child: Option<Child>,
+}
+
+/// 实例信息和启动命令的副本
+type TrackedInstanceSnapshot = (LaunchedInstance, LaunchCommand);
+
+/// 所有由 Mole 启动的实例
+static INSTANCES: Mutex<Vec<TrackedInstance>> = Mutex::new(Vec::new());
+
+/// 下一个实例 ID
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 退出检测线程是否已启动
+static WATCHER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// 当前时间（秒）
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(unix)]
+fn exit_signal(status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: &ExitStatus) -> Option<i32> {
+    None
+}
+
+/// 检查已退出的进程并更新状态
+fn reap(instances: &mut [TrackedInstance]) {
+    for tracked in instances.iter_mut() {
+        let Some(child) = tracked.child.as_mut() else {
+            continue;
+        };
+        let Ok(Some(status)) = child.try_wait() else {
+            continue;
+        };
+        let now = now_secs();
+        let info = &mut tracked.info;
+        info.exit_code = status.code();
+        info.exit_signal = exit_signal(&status);
+        info.exited_at = Some(now);
+        info.cpu_usage = 0.0;
+        info.memory = 0;
+        let quick_exit = now.saturating_sub(info.started_at) <= tracked.crash_window.as_secs();
+        info.status = if quick_exit && !status.success() {
+            println!(
+                "[InstanceTracker] {} (pid {}) crashed: code {:?}, signal {:?}",
+                info.executable, info.pid, info.exit_code, info.exit_signal
+            );
+            "crashed".to_string()
+        } else {
+            "exited".to_string()
+        };
+        tracked.child = None;
+    }
+}
+
+/// 结束子进程: 先发送 SIGTERM 等待退出，超时后强制结束
+///
+/// 可能等待数秒，调用时不能持有 INSTANCES 锁
+fn terminate(mut child: Child) -> Option<ExitStatus> {
+    unsafe {
+        libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+    }
+    for _ in 0..TERMINATE_TIMEOUT_TICKS {
+        if let Ok(Some(exited)) = child.try_wait() {
+            return Some(exited);
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    let _ = child.kill();
+    child.wait().ok()
+}
+
+/// 取出实例的子进程以便在锁外结束（子进程为空后后台线程不再检查它）
+fn take_children(filter: impl Fn(&LaunchedInstance) -> bool) -> Vec<(u64, Child)> {
+    let mut instances = INSTANCES.lock().unwrap_or_else(|e| e.into_inner());
+    reap(&mut instances);
+    instances.iter_mut()
+        .filter(|t| filter(&t.info))
+        .filter_map(|t| t.child.take().map(|child| (t.info.id, child)))
+        .collect()
+}
+
+/// 结束取出的子进程并记录为已停止
+fn stop_children(children: Vec<(u64, Child)>) {
+    let stopped: Vec<(u64, Option<ExitStatus>)> = thread::scope(|scope| {
+        let handles: Vec<_> = children.into_iter()
+            .map(|(id, child)| scope.spawn(move || (id, terminate(child))))
+            .collect();
+        handles.into_iter().filter_map(|h| h.join().ok()).collect()
+    });
+
+    let mut instances = INSTANCES.lock().unwrap_or_else(|e| e.into_inner());
+    for (id, status) in stopped {
+        let Some(tracked) = instances.iter_mut().find(|t| t.info.id == id) else {
+            continue;
+        };
+        let info = &mut tracked.info;
+        info.status = "stopped".to_string();
+        info.exit_code = status.and_then(|s| s.code());
+        info.exit_signal = status.as_ref().and_then(exit_signal);
+        info.exited_at = Some(now_secs());
+        info.cpu_usage = 0.0;
+        info.memory = 0;
+    }
+}
+
+/// 查找实例记录
+fn find_instance(id: u64) -> Result<TrackedInstanceSnapshot, String> {
+    let instances = INSTANCES.lock().unwrap_or_else(|e| e.into_inner());
+    instances.iter()
+        .find(|t| t.info.id == id)
+        .map(|t| (t.info.clone(), t.command.clone()))
+        .ok_or_else(|| format!("实例 {} 不存在", id))
+}
+
+/// 启动后台退出检测线程（重复调用只启动一次）
+fn start_watcher() {
+    if WATCHER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    thread::spawn(|| loop {
+        {
+            let mut instances = INSTANCES.lock().unwrap_or_else(|e| e.into_inner());
+            reap(&mut instances);
+        }
+        thread::sleep(WATCH_INTERVAL);
+    });
+}
+
+/// 实例跟踪服务
+pub struct InstanceTrackerService {
+    crash_window: Duration,
+}
+
+impl InstanceTrackerService {
+    /// 创建新的实例跟踪服务实例
+    pub fn new() -> Self {
+        Self::with_crash_window(DEFAULT_CRASH_WINDOW)
+    }
+
+    /// 使用指定的崩溃判定时间创建实例
+    pub fn with_crash_window(crash_window: Duration) -> Self {
+        InstanceTrackerService { crash_window }
+    }
+
+    /// 启动并跟踪一个实例
+    pub fn launch(&self, app_path: &str, profile_id: Option<String>, command: LaunchCommand) -> Result<LaunchedInstance, String> {
+        let child = Command::new(&command.executable)
+            .args(&command.args)
+            .envs(&command.env)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("启动失败: {}", e))?;
+
+        let info = LaunchedInstance {
+            id: NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            pid: child.id(),
+            app_path: app_path.to_string(),
+            profile_id,
+            executable: command.executable.clone(),
+            args: command.args.clone(),
+            started_at: now_secs(),
+            status: "running".to_string(),
+            exit_code: None,
+            exit_signal: None,
+            exited_at: None,
+            cpu_usage: 0.0,
+            memory: 0,
+        };
+
+        let mut instances = INSTANCES.lock().unwrap_or_else(|e| e.into_inner());
+        instances.push(TrackedInstance {
+            info: info.clone(),
+            command,
+            crash_window: self.crash_window,
+            child: Some(child),
+        });
+        drop(instances);
+        start_watcher();
+        Ok(info)
+    }
+
+    /// 列出所有启动过的实例，运行中的实例附带实时 CPU 和内存
+    pub fn list_launched_instances(&self) -> Vec<LaunchedInstance> {
+        let mut instances = INSTANCES.lock().unwrap_or_else(|e| e.into_inner());
+        reap(&mut instances);
+
+        if instances.iter().any(|t| t.child.is_some()) {
+            let processes: HashMap<u32, Process> = latest_processes()
+                .into_iter()
+                .map(|p| (p.pid, p))
+                .collect();
+            for tracked in instances.iter_mut().filter(|t| t.child.is_some()) {
+                if let Some(process) = processes.get(&tracked.info.pid) {
+                    tracked.info.cpu_usage = process.cpu_usage;
+                    tracked.info.memory = process.memory;
+                }
+            }
+        }
+
+        let mut result: Vec<LaunchedInstance> = instances.iter().map(|t| t.info.clone()).collect();
+        result.sort_by(|a, b| b.started_at.cmp(&a.started_at).then_with(|| b.id.cmp(&a.id)));
+        result
+    }
+
+    /// 获取启动配置正在运行的实例
+    pub fn profile_instances(&self, profile_id: &str) -> Vec<LaunchedInstance> {
+        self.list_launched_instances()
+            .into_iter()
+            .filter(|i| i.status == "running" && i.profile_id.as_deref() == Some(profile_id))
+            .collect()
+    }
+
+    /// 停止实例
+    pub fn stop_instance(&self, id: u64) -> Result<LaunchedInstance, String> {
+        find_instance(id)?;
+        stop_children(take_children(|info| info.id == id));
+        find_instance(id).map(|(info, _)| info)
+    }
+
+    /// 停止启动配置的所有实例，返回停止的数量
+    pub fn stop_profile(&self, profile_id: &str) -> u32 {
+        let children = take_children(|info| info.profile_id.as_deref() == Some(profile_id));
+        let stopped = children.len() as u32;
+        stop_children(children);
+        stopped
+    }
+
+    /// 重启实例: 停止后以相同的可执行文件、参数和环境变量重新启动
+    pub fn restart_instance(&self, id: u64) -> Result<LaunchedInstance, String> {
+        find_instance(id)?;
+        stop_children(take_children(|info| info.id == id));
+        let (info, command) = find_instance(id)?;
+        self.launch(&info.app_path, info.profile_id, command)
+    }
+
+    /// 清除已退出的实例记录，返回清除的数量
+    pub fn clear_exited_instances(&self) -> u32 {
+        let mut instances = INSTANCES.lock().unwrap_or_else(|e| e.into_inner());
+        reap(&mut instances);
+        let before = instances.len();
+        // 正在停止的实例子进程已被取出，按状态判断
+        instances.retain(|t| t.info.status == "running");
+        (before - instances.len()) as u32
+    }
+}
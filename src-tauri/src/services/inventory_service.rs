@@ -0,0 +1,444 @@
+//! 应用清单导出与对比服务实现 - 快照使用 DuckDB
+//!
+//! 汇总已安装应用的版本、架构、大小、签名者和来源，导出为 JSON 或 CSV，
+//! 对比两份清单（如两台机器之间），并保存本机快照以查看一段时间内的变化。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use duckdb::{Connection, params};
+use sysinfo::System;
+use crate::models::app::AppInfo;
+use crate::models::inventory::{Inventory, InventoryDiff, InventoryEntry, InventorySnapshot, InventoryVersionChange};
+use crate::services::app_service::AppService;
+use crate::services::receipt_service::ReceiptService;
+
+/// CSV 列名
+const CSV_HEADER: [&str; 11] = [
+    "name", "bundle_id", "version", "build", "path", "architectures",
+    "arch_kind", "size", "signer", "team_id", "source",
+];
+
+/// 当前时间（秒）
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 读取代码签名者与团队 ID（codesign -dvv 输出在 stderr）
+fn read_signer(app_path: &str) -> (String, String) {
+    if !cfg!(target_os = "macos") {
+        return (String::new(), String::new());
+    }
+    let Ok(output) = Command::new("codesign").args(["-dvv", app_path]).output() else {
+        return (String::new(), String::new());
+    };
+    let text = String::from_utf8_lossy(&output.stderr);
+    let signer = text.lines()
+        .find_map(|l| l.strip_prefix("Authority="))
+        .map(|s| s.trim().to_string())
+        .or_else(|| text.lines().any(|l| l.starts_with("Signature=adhoc")).then(|| "adhoc".to_string()))
+        .unwrap_or_default();
+    let team_id = text.lines()
+        .find_map(|l| l.strip_prefix("TeamIdentifier="))
+        .map(|s| s.trim().to_string())
+        .filter(|s| s != "not set")
+        .unwrap_or_default();
+    (signer, team_id)
+}
+
+/// 判断应用安装来源（手动安装的应用包再区分是否来自 .pkg 安装包）
+fn detect_source(app: &AppInfo, receipts_by_app: &HashMap<String, Vec<String>>) -> String {
+    if app.source == "manual" && receipts_by_app.contains_key(app.path.trim_end_matches('/')) {
+        "pkg".to_string()
+    } else {
+        app.source.clone()
+    }
+}
+
+/// 清单条目的对比键（优先使用 bundle id）
+fn entry_key(entry: &InventoryEntry) -> String {
+    if entry.bundle_id.is_empty() {
+        entry.name.to_lowercase()
+    } else {
+        entry.bundle_id.to_lowercase()
+    }
+}
+
+/// 对比两份清单
+pub fn diff_inventory(a: &Inventory, b: &Inventory) -> InventoryDiff {
+    let map_a: BTreeMap<String, &InventoryEntry> = a.apps.iter().map(|e| (entry_key(e), e)).collect();
+    let map_b: BTreeMap<String, &InventoryEntry> = b.apps.iter().map(|e| (entry_key(e), e)).collect();
+
+    let missing = map_a.iter()
+        .filter(|(key, _)| !map_b.contains_key(*key))
+        .map(|(_, e)| (*e).clone())
+        .collect();
+    let extra = map_b.iter()
+        .filter(|(key, _)| !map_a.contains_key(*key))
+        .map(|(_, e)| (*e).clone())
+        .collect();
+    let version_changed = map_a.iter()
+        .filter_map(|(key, entry_a)| {
+            let entry_b = map_b.get(key)?;
+            if entry_a.version == entry_b.version && entry_a.build == entry_b.build {
+                return None;
+            }
+            Some(InventoryVersionChange {
+                name: entry_a.name.clone(),
+                bundle_id: entry_a.bundle_id.clone(),
+                version_a: entry_a.version.clone(),
+                version_b: entry_b.version.clone(),
+                build_a: entry_a.build.clone(),
+                build_b: entry_b.build.clone(),
+            })
+        })
+        .collect();
+
+    InventoryDiff { missing, extra, version_changed }
+}
+
+/// 转义 CSV 字段
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 将清单转换为 CSV
+pub fn inventory_to_csv(inventory: &Inventory) -> String {
+    let mut csv = CSV_HEADER.join(",");
+    csv.push('\n');
+    for entry in &inventory.apps {
+        let fields = [
+            entry.name.clone(),
+            entry.bundle_id.clone(),
+            entry.version.clone(),
+            entry.build.clone(),
+            entry.path.clone(),
+            entry.architectures.join(";"),
+            entry.arch_kind.clone(),
+            entry.size.to_string(),
+            entry.signer.clone(),
+            entry.team_id.clone(),
+            entry.source.clone(),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// 拆分 CSV 记录（支持引号内的逗号、换行和转义引号）
+fn parse_csv_records(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => record.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// 从 CSV 读取清单（主机名和时间未知）
+pub fn inventory_from_csv(content: &str) -> Result<Inventory, String> {
+    let mut records = parse_csv_records(content).into_iter();
+    let header = records.next().ok_or_else(|| "CSV 内容为空".to_string())?;
+    let column = |name: &str| header.iter().position(|h| h.trim() == name);
+    let (Some(name_col), Some(bundle_col), Some(version_col)) = (column("name"), column("bundle_id"), column("version")) else {
+        return Err("CSV 缺少 name、bundle_id 或 version 列".to_string());
+    };
+    let columns: Vec<Option<usize>> = CSV_HEADER.iter().map(|h| column(h)).collect();
+
+    let apps = records
+        .filter(|r| r.iter().any(|f| !f.is_empty()))
+        .map(|record| {
+            let get = |index: Option<usize>| index.and_then(|i| record.get(i)).cloned().unwrap_or_default();
+            InventoryEntry {
+                name: get(Some(name_col)),
+                bundle_id: get(Some(bundle_col)),
+                version: get(Some(version_col)),
+                build: get(columns[3]),
+                path: get(columns[4]),
+                architectures: get(columns[5]).split(';').filter(|s| !s.is_empty()).map(|s| s.to_string()).collect(),
+                arch_kind: get(columns[6]),
+                size: get(columns[7]).parse().unwrap_or(0),
+                signer: get(columns[8]),
+                team_id: get(columns[9]),
+                source: get(columns[10]),
+            }
+        })
+        .collect();
+
+    Ok(Inventory { hostname: String::new(), created_at: 0, apps })
+}
+
+/// 读取导入的清单（自动识别 JSON 或 CSV）
+pub fn parse_inventory(content: &str) -> Result<Inventory, String> {
+    if content.trim_start().starts_with('{') {
+        serde_json::from_str(content).map_err(|e| format!("无法解析清单 JSON: {}", e))
+    } else {
+        inventory_from_csv(content)
+    }
+}
+
+/// 应用清单服务
+pub struct InventoryService {
+    db_path: PathBuf,
+}
+
+impl InventoryService {
+    /// 创建新的清单服务实例（不自动创建数据库）
+    pub fn new() -> Self {
+        let db_path = dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("com.mole.app")
+            .join("inventory.db");
+        Self::with_db_path(db_path)
+    }
+
+    /// 使用指定数据库文件创建实例
+    pub fn with_db_path(db_path: impl Into<PathBuf>) -> Self {
+        InventoryService { db_path: db_path.into() }
+    }
+
+    /// 生成本机当前的应用清单
+    pub fn build_inventory(&self) -> Result<Inventory, String> {
+        let installed = AppService::new().get_installed_apps()?;
+        let app_service = AppService::new();
+        // 回执只解析一次，避免每个应用都重新遍历回执目录
+        let receipts_by_app = ReceiptService::new().receipts_by_app();
+
+        let apps = installed.apps.iter()
+            .map(|app| {
+                let build = plist::Value::from_file(Path::new(&app.path).join("Contents/Info.plist"))
+                    .ok()
+                    .and_then(|v| v.as_dictionary()?.get("CFBundleVersion")?.as_string().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                let (signer, team_id) = read_signer(&app.path);
+                InventoryEntry {
+                    name: app.name.clone(),
+                    bundle_id: app.identifier.clone(),
+                    version: app.version.clone(),
+                    build,
+                    path: app.path.clone(),
+                    architectures: app.architectures.clone(),
+                    arch_kind: app.arch_kind.clone(),
+                    size: app_service.get_single_app_size(&app.path),
+                    signer,
+                    team_id,
+                    source: detect_source(app, &receipts_by_app),
+                }
+            })
+            .collect();
+
+        Ok(Inventory {
+            hostname: System::host_name().unwrap_or_default(),
+            created_at: now_secs(),
+            apps,
+        })
+    }
+
+    /// 导出本机应用清单，格式为 "json" 或 "csv"
+    pub fn export_inventory(&self, format: &str) -> Result<String, String> {
+        let inventory = self.build_inventory()?;
+        match format {
+            "json" => serde_json::to_string_pretty(&inventory).map_err(|e| format!("无法序列化清单: {}", e)),
+            "csv" => Ok(inventory_to_csv(&inventory)),
+            other => Err(format!("不支持的导出格式: {}", other)),
+        }
+    }
+
+    /// 获取数据库连接（仅当数据库存在时）
+    fn get_connection(&self) -> Option<Connection> {
+        if !self.db_path.exists() {
+            return None;
+        }
+        Connection::open(&self.db_path).ok()
+    }
+
+    /// 获取或创建数据库连接，并确保表存在
+    fn get_or_create_connection(&self) -> Result<Connection, String> {
+        if let Some(parent) = self.db_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("无法创建数据目录: {}", e))?;
+        }
+        let conn = Connection::open(&self.db_path).map_err(|e| format!("无法打开清单数据库: {}", e))?;
+        conn.execute_batch(
+            "CREATE SEQUENCE IF NOT EXISTS inventory_snapshot_seq START 1;
+             CREATE TABLE IF NOT EXISTS inventory_snapshots (
+                 id BIGINT PRIMARY KEY,
+                 hostname VARCHAR NOT NULL,
+                 created_at UBIGINT NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS inventory_apps (
+                 snapshot_id BIGINT NOT NULL,
+                 name VARCHAR NOT NULL,
+                 bundle_id VARCHAR NOT NULL,
+                 version VARCHAR NOT NULL,
+                 build VARCHAR NOT NULL,
+                 path VARCHAR NOT NULL,
+                 architectures VARCHAR NOT NULL,
+                 arch_kind VARCHAR NOT NULL,
+                 size UBIGINT NOT NULL,
+                 signer VARCHAR NOT NULL,
+                 team_id VARCHAR NOT NULL,
+                 source VARCHAR NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_inventory_apps_snapshot ON inventory_apps(snapshot_id);",
+        ).map_err(|e| format!("无法创建清单表: {}", e))?;
+        Ok(conn)
+    }
+
+    /// 保存本机当前清单为快照
+    pub fn save_snapshot(&self) -> Result<InventorySnapshot, String> {
+        let inventory = self.build_inventory()?;
+        let mut conn = self.get_or_create_connection()?;
+        let tx = conn.transaction().map_err(|e| format!("无法开始事务: {}", e))?;
+
+        let id: i64 = tx.query_row("SELECT nextval('inventory_snapshot_seq')", [], |row| row.get(0))
+            .map_err(|e| format!("无法生成快照 ID: {}", e))?;
+        tx.execute(
+            "INSERT INTO inventory_snapshots (id, hostname, created_at) VALUES (?, ?, ?)",
+            params![id, &inventory.hostname, inventory.created_at],
+        ).map_err(|e| format!("无法保存快照: {}", e))?;
+
+        for app in &inventory.apps {
+            tx.execute(
+                "INSERT INTO inventory_apps (snapshot_id, name, bundle_id, version, build, path,
+                     architectures, arch_kind, size, signer, team_id, source)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    id, &app.name, &app.bundle_id, &app.version, &app.build, &app.path,
+                    app.architectures.join(";"), &app.arch_kind, app.size, &app.signer, &app.team_id, &app.source
+                ],
+            ).map_err(|e| format!("无法保存快照应用: {}", e))?;
+        }
+        tx.commit().map_err(|e| format!("无法提交快照: {}", e))?;
+        let _ = conn.execute("CHECKPOINT", []);
+
+        Ok(InventorySnapshot {
+            id,
+            hostname: inventory.hostname,
+            created_at: inventory.created_at,
+            app_count: inventory.apps.len() as u32,
+        })
+    }
+
+    /// 列出已保存的快照（最新的在前）
+    pub fn list_snapshots(&self) -> Vec<InventorySnapshot> {
+        let Some(conn) = self.get_connection() else {
+            return Vec::new();
+        };
+        let mut result = Vec::new();
+        if let Ok(mut stmt) = conn.prepare(
+            "SELECT s.id, s.hostname, s.created_at, COUNT(a.snapshot_id)
+             FROM inventory_snapshots s LEFT JOIN inventory_apps a ON a.snapshot_id = s.id
+             GROUP BY s.id, s.hostname, s.created_at
+             ORDER BY s.created_at DESC"
+        ) {
+            if let Ok(rows) = stmt.query_map([], |row| {
+                let count: i64 = row.get(3)?;
+                Ok(InventorySnapshot {
+                    id: row.get(0)?,
+                    hostname: row.get(1)?,
+                    created_at: row.get(2)?,
+                    app_count: count as u32,
+                })
+            }) {
+                result.extend(rows.flatten());
+            }
+        }
+        result
+    }
+
+    /// 读取快照中的清单
+    pub fn get_snapshot(&self, id: i64) -> Result<Inventory, String> {
+        let conn = self.get_connection().ok_or_else(|| "没有已保存的快照".to_string())?;
+        let (hostname, created_at): (String, u64) = conn.query_row(
+            "SELECT hostname, created_at FROM inventory_snapshots WHERE id = ?",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).map_err(|_| format!("快照 {} 不存在", id))?;
+
+        let mut apps = Vec::new();
+        let mut stmt = conn.prepare(
+            "SELECT name, bundle_id, version, build, path, architectures, arch_kind, size, signer, team_id, source
+             FROM inventory_apps WHERE snapshot_id = ? ORDER BY lower(name)"
+        ).map_err(|e| format!("无法读取快照: {}", e))?;
+        let rows = stmt.query_map(params![id], |row| {
+            let architectures: String = row.get(5)?;
+            Ok(InventoryEntry {
+                name: row.get(0)?,
+                bundle_id: row.get(1)?,
+                version: row.get(2)?,
+                build: row.get(3)?,
+                path: row.get(4)?,
+                architectures: architectures.split(';').filter(|s| !s.is_empty()).map(|s| s.to_string()).collect(),
+                arch_kind: row.get(6)?,
+                size: row.get(7)?,
+                signer: row.get(8)?,
+                team_id: row.get(9)?,
+                source: row.get(10)?,
+            })
+        }).map_err(|e| format!("无法读取快照: {}", e))?;
+        apps.extend(rows.flatten());
+
+        Ok(Inventory { hostname, created_at, apps })
+    }
+
+    /// 对比指定天数前的快照与本机当前清单
+    ///
+    /// 使用不晚于该时间点的最新快照；都比该时间点新时使用最早的快照。
+    pub fn diff_since(&self, days: u32) -> Result<InventoryDiff, String> {
+        let snapshots = self.list_snapshots();
+        let cutoff = now_secs().saturating_sub(days as u64 * 86_400);
+        let baseline = snapshots.iter()
+            .find(|s| s.created_at <= cutoff)
+            .or_else(|| snapshots.last())
+            .ok_or_else(|| "没有可对比的快照".to_string())?;
+
+        let before = self.get_snapshot(baseline.id)?;
+        let current = self.build_inventory()?;
+        Ok(diff_inventory(&before, &current))
+    }
+
+    /// 删除快照
+    pub fn delete_snapshot(&self, id: i64) -> Result<bool, String> {
+        let Some(conn) = self.get_connection() else {
+            return Ok(true);
+        };
+        conn.execute("DELETE FROM inventory_apps WHERE snapshot_id = ?", params![id])
+            .map_err(|e| format!("无法删除快照: {}", e))?;
+        conn.execute("DELETE FROM inventory_snapshots WHERE id = ?", params![id])
+            .map_err(|e| format!("无法删除快照: {}", e))?;
+        let _ = conn.execute("CHECKPOINT", []);
+        Ok(true)
+    }
+}
@@ -0,0 +1,347 @@
+//! 应用多开启动配置服务实现
+//!
+//! 每个启动配置有独立的数据目录。Chromium/Electron 应用通过 --user-data-dir 隔离，
+//! Firefox 通过 -profile 隔离，其他应用改写 HOME 和 CFFIXED_USER_HOME；已知应用的隔离方式取自可多开应用目录。
+//! 配置保存在本地 JSON 文件中，启动的进程交给实例跟踪服务，按配置查询和停止。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::models::instance::LaunchedInstance;
+use crate::models::launch_profile::{LaunchCommand, LaunchProfile};
+use crate::services::duplicate_catalog_service::DuplicateCatalogService;
+use crate::services::instance_tracker_service::InstanceTrackerService;
+use crate::services::macho_service::MachOService;
+
+/// 支持的隔离方式
+const STRATEGIES: [&str; 4] = ["auto", "user_data_dir", "firefox_profile", "home"];
+
+/// 快速多开配置的名称
+const QUICK_PROFILE_NAME: &str = "快速多开";
+
+/// 当前时间（秒）
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 读取应用标识符
+fn read_bundle_id(app_path: &Path) -> String {
+    plist::Value::from_file(app_path.join("Contents/Info.plist"))
+        .ok()
+        .and_then(|v| v.as_dictionary()?.get("CFBundleIdentifier")?.as_string().map(|s| s.to_string()))
+        .unwrap_or_default()
+}
+
+/// 是否为 Electron 或 Chromium 内核应用（包含 Electron/Chromium 框架）
+fn is_chromium_based(app_path: &Path) -> bool {
+    fs::read_dir(app_path.join("Contents/Frameworks"))
+        .map(|entries| {
+            entries.flatten().any(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                name.ends_with(".framework") && (name.contains("Electron") || name.contains("Chrom"))
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// 按可多开应用目录和应用结构选择隔离方式
+pub fn resolve_strategy(app_path: &Path, bundle_id: &str) -> String {
+    if let Some(isolation) = DuplicateCatalogService::new().find(bundle_id).and_then(|e| e.isolation) {
+        return isolation;
+    }
+    if is_chromium_based(app_path) {
+        return "user_data_dir".to_string();
+    }
+    "home".to_string()
+}
+
+/// 应用多开启动配置服务
+pub struct LaunchProfileService {
+    store_path: PathBuf,
+    profiles_dir: PathBuf,
+}
+
+impl LaunchProfileService {
+    /// 创建新的启动配置服务实例
+    pub fn new() -> Self {
+        let base = dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("com.mole.app");
+        Self::with_paths(base.join("launch_profiles.json"), base.join("profiles"))
+    }
+
+    /// 使用指定的配置文件和数据目录根路径创建实例
+    pub fn with_paths(store_path: impl Into<PathBuf>, profiles_dir: impl Into<PathBuf>) -> Self {
+        LaunchProfileService {
+            store_path: store_path.into(),
+            profiles_dir: profiles_dir.into(),
+        }
+    }
+
+    /// 读取所有配置
+    fn load(&self) -> Vec<LaunchProfile> {
+        fs::read_to_string(&self.store_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// 保存所有配置
+    fn save(&self, profiles: &[LaunchProfile]) -> Result<(), String> {
+        if let Some(parent) = self.store_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("无法创建数据目录: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(profiles).map_err(|e| format!("无法序列化启动配置: {}", e))?;
+        fs::write(&self.store_path, json).map_err(|e| format!("无法保存启动配置: {}", e))
+    }
+
+    /// 列出配置，指定应用路径时只返回该应用的配置
+    pub fn list_profiles(&self, app_path: Option<&str>) -> Vec<LaunchProfile> {
+        self.load()
+            .into_iter()
+            .filter(|p| app_path.map(|path| p.app_path == path).unwrap_or(true))
+            .collect()
+    }
+
+    /// 获取单个配置
+    pub fn get_profile(&self, id: &str) -> Result<LaunchProfile, String> {
+        self.load()
+            .into_iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("启动配置 {} 不存在", id))
+    }
+
+    /// 为应用创建启动配置
+    pub fn create_profile(
+        &self,
+        app_path: &str,
+        name: &str,
+        strategy: Option<String>,
+        extra_args: Vec<String>,
+        env: BTreeMap<String, String>,
+    ) -> Result<LaunchProfile, String> {
+        let path = Path::new(app_path);
+        if !path.exists() {
+            return Err("应用不存在".to_string());
+        }
+        let strategy = strategy.unwrap_or_else(|| "auto".to_string());
+        if !STRATEGIES.contains(&strategy.as_str()) {
+            return Err(format!("不支持的隔离方式: {}", strategy));
+        }
+
+        let mut profiles = self.load();
+        if profiles.iter().any(|p| p.app_path == app_path && p.name == name) {
+            return Err(format!("配置 {} 已存在", name));
+        }
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let id = format!("{:x}", nanos);
+        let data_dir = self.profiles_dir.join(&id);
+        fs::create_dir_all(&data_dir).map_err(|e| format!("无法创建配置数据目录: {}", e))?;
+
+        let profile = LaunchProfile {
+            id,
+            name: name.to_string(),
+            app_path: app_path.to_string(),
+            bundle_id: read_bundle_id(path),
+            data_dir: data_dir.to_string_lossy().to_string(),
+            strategy,
+            extra_args,
+            env,
+            created_at: now_secs(),
+        };
+        profiles.push(profile.clone());
+        self.save(&profiles)?;
+        Ok(profile)
+    }
+
+    /// 删除配置，可选同时删除其数据目录
+    pub fn delete_profile(&self, id: &str, remove_data: bool) -> Result<bool, String> {
+        let mut profiles = self.load();
+        let Some(index) = profiles.iter().position(|p| p.id == id) else {
+            return Ok(false);
+        };
+        if !self.get_profile_instances(id).is_empty() {
+            return Err("配置仍有运行中的实例，请先停止".to_string());
+        }
+        let profile = profiles.remove(index);
+        self.save(&profiles)?;
+        if remove_data {
+            let _ = fs::remove_dir_all(&profile.data_dir);
+        }
+        Ok(true)
+    }
+
+    /// 获取应用的快速多开配置: 复用没有运行实例的快速配置，否则按应用选择隔离方式新建一个
+    pub fn quick_profile(&self, app_path: &str) -> Result<LaunchProfile, String> {
+        let profiles = self.list_profiles(Some(app_path));
+        let idle = profiles.iter()
+            .filter(|p| p.name.starts_with(QUICK_PROFILE_NAME))
+            .find(|p| self.get_profile_instances(&p.id).is_empty());
+        if let Some(profile) = idle {
+            return Ok(profile.clone());
+        }
+
+        let name = (1..)
+            .map(|n| if n == 1 { QUICK_PROFILE_NAME.to_string() } else { format!("{} {}", QUICK_PROFILE_NAME, n) })
+            .find(|name| !profiles.iter().any(|p| &p.name == name))
+            .unwrap_or_else(|| QUICK_PROFILE_NAME.to_string());
+        let path = Path::new(app_path);
+        let strategy = resolve_strategy(path, &read_bundle_id(path));
+        self.create_profile(app_path, &name, Some(strategy), Vec::new(), BTreeMap::new())
+    }
+
+    /// 生成配置对应的启动命令
+    pub fn build_command(&self, profile: &LaunchProfile) -> Result<LaunchCommand, String> {
+        let app_path = Path::new(&profile.app_path);
+        let executable = MachOService::new().main_executable(app_path)
+            .ok_or_else(|| "找不到应用的可执行文件".to_string())?;
+        let strategy = match profile.strategy.as_str() {
+            "auto" => resolve_strategy(app_path, &profile.bundle_id),
+            other => other.to_string(),
+        };
+
+        let mut args = Vec::new();
+        let mut env = BTreeMap::new();
+        match strategy.as_str() {
+            "user_data_dir" => args.push(format!("--user-data-dir={}", profile.data_dir)),
+            "firefox_profile" => {
+                args.extend(["-no-remote".to_string(), "-profile".to_string(), profile.data_dir.clone()]);
+            }
+            _ => {
+                env.insert("HOME".to_string(), profile.data_dir.clone());
+                env.insert("CFFIXED_USER_HOME".to_string(), profile.data_dir.clone());
+            }
+        }
+        args.extend(profile.extra_args.iter().cloned());
+        env.extend(profile.env.clone());
+
+        Ok(LaunchCommand {
+            executable: executable.to_string_lossy().to_string(),
+            strategy,
+            args,
+            env,
+        })
+    }
+
+    /// 以指定配置启动应用实例
+    pub fn launch_profile(&self, id: &str) -> Result<LaunchedInstance, String> {
+        let profile = self.get_profile(id)?;
+        let command = self.build_command(&profile)?;
+        fs::create_dir_all(&profile.data_dir).map_err(|e| format!("无法创建配置数据目录: {}", e))?;
+        InstanceTrackerService::new().launch(&profile.app_path, Some(profile.id), command)
+    }
+
+    /// 获取配置正在运行的实例
+    pub fn get_profile_instances(&self, id: &str) -> Vec<LaunchedInstance> {
+        InstanceTrackerService::new().profile_instances(id)
+    }
+
+    /// 停止配置的所有实例，返回停止的数量
+    pub fn stop_profile(&self, id: &str) -> u32 {
+        InstanceTrackerService::new().stop_profile(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-profile-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// 创建可执行文件为 sleep 脚本的应用，electron 为 true 时包含 Electron 框架
+    fn write_app(dir: &Path, bundle_id: &str, electron: bool) -> String {
+        let app = dir.join("Chat.app");
+        fs::create_dir_all(app.join("Contents/MacOS")).unwrap();
+        if electron {
+            fs::create_dir_all(app.join("Contents/Frameworks/Electron Framework.framework")).unwrap();
+        }
+        let mut info = plist::Dictionary::new();
+        info.insert("CFBundleIdentifier".to_string(), bundle_id.into());
+        info.insert("CFBundleExecutable".to_string(), "Chat".into());
+        plist::Value::Dictionary(info).to_file_xml(app.join("Contents/Info.plist")).unwrap();
+        let executable = app.join("Contents/MacOS/Chat");
+        fs::write(&executable, "#!/bin/sh\nsleep 30\n").unwrap();
+        fs::set_permissions(&executable, fs::Permissions::from_mode(0o755)).unwrap();
+        app.to_string_lossy().to_string()
+    }
+
+    fn service(dir: &Path) -> LaunchProfileService {
+        LaunchProfileService::with_paths(dir.join("launch_profiles.json"), dir.join("profiles"))
+    }
+
+    #[test]
+    fn resolves_strategy_from_catalog_and_frameworks() {
+        let dir = fixture_dir("strategy");
+        let app = write_app(&dir, "com.example.chat", true);
+        assert_eq!(resolve_strategy(Path::new(&app), "org.mozilla.firefox"), "firefox_profile");
+        assert_eq!(resolve_strategy(Path::new(&app), "com.example.chat"), "user_data_dir");
+        assert_eq!(resolve_strategy(&dir.join("Missing.app"), "com.example.chat"), "home");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn builds_isolated_commands() {
+        let dir = fixture_dir("command");
+        let app = write_app(&dir, "com.example.chat", true);
+        let service = service(&dir);
+        let mut env = BTreeMap::new();
+        env.insert("FOO".to_string(), "1".to_string());
+
+        let work = service.create_profile(&app, "Work", None, vec!["--x".to_string()], env).unwrap();
+        assert!(service.create_profile(&app, "Work", None, Vec::new(), BTreeMap::new()).is_err());
+        assert!(service.create_profile(&app, "Other", Some("bogus".to_string()), Vec::new(), BTreeMap::new()).is_err());
+        let command = service.build_command(&work).unwrap();
+        assert_eq!(command.strategy, "user_data_dir");
+        assert_eq!(command.args, vec![format!("--user-data-dir={}", work.data_dir), "--x".to_string()]);
+        assert_eq!(command.env.get("FOO").map(String::as_str), Some("1"));
+
+        let home = service.create_profile(&app, "Home", Some("home".to_string()), Vec::new(), BTreeMap::new()).unwrap();
+        let command = service.build_command(&home).unwrap();
+        assert_eq!(command.env.get("HOME"), Some(&home.data_dir));
+        assert_eq!(command.env.get("CFFIXED_USER_HOME"), Some(&home.data_dir));
+        assert!(command.args.is_empty());
+        assert_eq!(service.list_profiles(Some(&app)).len(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reuses_idle_quick_profile_and_adds_one_per_running_instance() {
+        let dir = fixture_dir("quick");
+        let app = write_app(&dir, "com.example.chat", false);
+        let service = service(&dir);
+
+        let first = service.quick_profile(&app).unwrap();
+        assert_eq!((first.name.as_str(), first.strategy.as_str()), (QUICK_PROFILE_NAME, "home"));
+        assert_eq!(service.quick_profile(&app).unwrap().id, first.id);
+
+        let instance = service.launch_profile(&first.id).unwrap();
+        assert!(instance.pid > 0);
+        assert_eq!(service.get_profile_instances(&first.id).len(), 1);
+        assert!(service.delete_profile(&first.id, true).is_err());
+        let second = service.quick_profile(&app).unwrap();
+        assert_ne!(second.id, first.id);
+        assert_ne!(second.data_dir, first.data_dir);
+        assert_eq!(second.name, format!("{} 2", QUICK_PROFILE_NAME));
+
+        assert_eq!(service.stop_profile(&first.id), 1);
+        assert!(service.get_profile_instances(&first.id).is_empty());
+        assert_eq!(service.quick_profile(&app).unwrap().id, first.id);
+        assert!(service.delete_profile(&first.id, true).unwrap());
+        assert!(!Path::new(&first.data_dir).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,355 @@
+//! 应用本地化资源分析服务实现
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+use crate::models::cleaner::CleanItem;
+use crate::models::localization::{AppLocalizations, LocalizationRemovalPlan, LocalizationReport, LprojInfo};
+use crate::services::bundle_rewrite_service::NESTED_CODE_EXTENSIONS;
+use crate::services::codesign_service::signature_base;
+
+/// 系统首选语言（进程内只读取一次）
+static PREFERRED_LANGUAGES: OnceLock<Vec<String>> = OnceLock::new();
+
+/// 旧式 lproj 目录名与语言代码的对应关系
+const LEGACY_LPROJ_NAMES: [(&str, &str); 8] = [
+    ("english", "en"),
+    ("french", "fr"),
+    ("german", "de"),
+    ("japanese", "ja"),
+    ("spanish", "es"),
+    ("italian", "it"),
+    ("dutch", "nl"),
+    ("chinese", "zh-hans"),
+];
+
+/// 将语言标签归一化为用于匹配的键（主语言，中文区分简繁）
+pub fn language_key(tag: &str) -> String {
+    let lower = tag.to_lowercase().replace('_', "-");
+    if let Some((_, code)) = LEGACY_LPROJ_NAMES.iter().find(|(name, _)| *name == lower) {
+        return code.to_string();
+    }
+
+    let primary = lower.split('-').next().unwrap_or_default().to_string();
+    if primary == "zh" {
+        let traditional = ["hant", "tw", "hk", "mo"].iter()
+            .any(|t| lower.split('-').skip(1).any(|part| part == *t));
+        return if traditional { "zh-hant".to_string() } else { "zh-hans".to_string() };
+    }
+    primary
+}
+
+/// 本地化资源分析服务
+pub struct LocalizationService {
+    preferred_keys: Vec<String>,
+    preferred_languages: Vec<String>,
+}
+
+impl LocalizationService {
+    /// 创建新的本地化分析服务实例（读取系统首选语言，首次读取后缓存）
+    pub fn new() -> Self {
+        Self::with_languages(PREFERRED_LANGUAGES.get_or_init(Self::get_preferred_languages).clone())
+    }
+
+    /// 使用指定首选语言创建实例
+    pub fn with_languages(preferred_languages: Vec<String>) -> Self {
+        let mut preferred_keys: Vec<String> = Vec::new();
+        for lang in &preferred_languages {
+            let key = language_key(lang);
+            if !preferred_keys.contains(&key) {
+                preferred_keys.push(key);
+            }
+        }
+        LocalizationService { preferred_keys, preferred_languages }
+    }
+
+    /// 获取系统首选语言列表
+    fn get_preferred_languages() -> Vec<String> {
+        let output = Command::new("defaults")
+            .args(["read", "-g", "AppleLanguages"])
+            .output();
+
+        // 输出格式: ( "zh-Hans-CN", "en-CN" )
+        let mut languages = Vec::new();
+        if let Ok(output) = output {
+            if output.status.success() {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                for line in stdout.lines() {
+                    let value = line.trim()
+                        .trim_end_matches(',')
+                        .trim_matches('"')
+                        .trim();
+                    if !value.is_empty() && value != "(" && value != ")" {
+                        languages.push(value.to_string());
+                    }
+                }
+            }
+        }
+
+        // 备用：使用 LANG 环境变量（如 zh_CN.UTF-8）
+        if languages.is_empty() {
+            if let Ok(lang) = std::env::var("LANG") {
+                let tag = lang.split('.').next().unwrap_or_default().to_string();
+                if !tag.is_empty() && tag != "C" && tag != "POSIX" {
+                    languages.push(tag);
+                }
+            }
+        }
+
+        if languages.is_empty() {
+            languages.push("en".to_string());
+        }
+        languages
+    }
+
+    /// 判断 lproj 语言是否在使用（开发语言是缺少本地化时的回退资源，始终保留）
+    fn is_language_used(&self, language: &str, development_region: &str) -> bool {
+        if language.eq_ignore_ascii_case("base") {
+            return true;
+        }
+        let key = language_key(language);
+        key == language_key(development_region) || self.preferred_keys.contains(&key)
+    }
+
+    /// 分析单个应用的本地化目录
+    pub fn analyze_app(&self, app_path: &str) -> AppLocalizations {
+        let path = Path::new(app_path);
+        let app_name = path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let development_region = read_development_region(path);
+        // 资源被 CodeResources 封存，删除已签名代码包（应用本身或嵌套的框架、应用）中的 lproj 会导致签名校验失败
+        let is_signed = signature_base(path).is_some();
+        let mut signed_bundles: HashMap<PathBuf, bool> = HashMap::new();
+        let mut localizations = Vec::new();
+        // lproj 内部不再包含需要统计的 lproj，不进入遍历；无法读取的条目跳过
+        let walker = WalkDir::new(path)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|e| e.path().parent().is_none_or(|p| p.extension().is_none_or(|ext| ext != "lproj")));
+        for entry in walker {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(language) = name.strip_suffix(".lproj") else {
+                continue;
+            };
+
+            let size = WalkDir::new(entry.path())
+                .into_iter()
+                .flatten()
+                .filter(|e| e.file_type().is_file())
+                .filter_map(|e| e.metadata().ok())
+                .map(|m| m.len())
+                .sum();
+            let bundle = enclosing_bundle(entry.path(), path);
+            let bundle_signed = *signed_bundles.entry(bundle.to_path_buf())
+                .or_insert_with(|| signature_base(bundle).is_some());
+
+            localizations.push(LprojInfo {
+                name: name.clone(),
+                language: language.to_string(),
+                path: entry.path().to_string_lossy().to_string(),
+                size,
+                is_used: self.is_language_used(language, &development_region),
+                removable: !is_signed && !bundle_signed,
+            });
+        }
+
+        localizations.sort_by(|a, b| b.size.cmp(&a.size));
+
+        let total_size = localizations.iter().map(|l| l.size).sum();
+        let unused_size = localizations.iter().filter(|l| !l.is_used).map(|l| l.size).sum();
+
+        AppLocalizations {
+            app_path: app_path.to_string(),
+            app_name,
+            localizations,
+            total_size,
+            unused_size,
+            is_signed,
+            removal_allowed: !is_signed,
+        }
+    }
+
+    /// 分析多个应用的本地化目录
+    pub fn analyze_apps(&self, app_paths: &[String]) -> LocalizationReport {
+        let apps: Vec<AppLocalizations> = app_paths.iter()
+            .map(|p| self.analyze_app(p))
+            .collect();
+        let total_unused_size = apps.iter().map(|a| a.unused_size).sum();
+
+        LocalizationReport {
+            preferred_languages: self.preferred_languages.clone(),
+            apps,
+            total_unused_size,
+        }
+    }
+
+    /// 生成未使用本地化的移除计划（跳过已签名应用和已签名嵌套代码包中的 lproj）
+    pub fn plan_removal(&self, app_paths: &[String]) -> LocalizationRemovalPlan {
+        let mut items = Vec::new();
+        let mut skipped_apps = Vec::new();
+
+        for app_path in app_paths {
+            let analysis = self.analyze_app(app_path);
+            let (removable, protected): (Vec<LprojInfo>, Vec<LprojInfo>) = analysis.localizations
+                .into_iter()
+                .filter(|l| !l.is_used && l.size > 0)
+                .partition(|l| l.removable);
+            if !protected.is_empty() {
+                skipped_apps.push(app_path.clone());
+            }
+            for lproj in removable {
+                items.push(CleanItem {
+                    type_: "localization".to_string(),
+                    path: lproj.path,
+                    size: lproj.size,
+                    description: format!("本地化: {} / {}", analysis.app_name, lproj.name),
+                });
+            }
+        }
+
+        items.sort_by(|a, b| b.size.cmp(&a.size));
+        let total_size = items.iter().map(|i| i.size).sum();
+
+        LocalizationRemovalPlan { items, total_size, skipped_apps }
+    }
+}
+
+/// lproj 所在的最近一层代码包（嵌套的应用、框架、插件等），没有嵌套代码包时为应用本身
+fn enclosing_bundle<'a>(lproj: &'a Path, app_path: &'a Path) -> &'a Path {
+    lproj.ancestors()
+        .skip(1)
+        .take_while(|p| *p != app_path)
+        .find(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| NESTED_CODE_EXTENSIONS.contains(&e))
+        })
+        .unwrap_or(app_path)
+}
+
+/// 读取应用的开发语言（CFBundleDevelopmentRegion），未设置时为 en
+fn read_development_region(app_path: &Path) -> String {
+    plist::Value::from_file(app_path.join("Contents/Info.plist"))
+        .ok()
+        .and_then(|v| {
+            v.as_dictionary()?
+                .get("CFBundleDevelopmentRegion")?
+                .as_string()
+                .map(|s| s.to_string())
+        })
+        .unwrap_or_else(|| "en".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-localization-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// 在 `resources` 目录下创建各语言的 lproj，每个 10 字节
+    fn write_lprojs(resources: &Path, languages: &[&str]) {
+        for language in languages {
+            let dir = resources.join(format!("{}.lproj", language));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("Localizable.strings"), [0u8; 10]).unwrap();
+        }
+    }
+
+    fn sign(base: &Path) {
+        fs::create_dir_all(base.join("_CodeSignature")).unwrap();
+        fs::write(base.join("_CodeSignature/CodeResources"), b"").unwrap();
+    }
+
+    #[test]
+    fn normalizes_language_keys() {
+        assert_eq!(language_key("zh-Hans-CN"), "zh-hans");
+        assert_eq!(language_key("zh_CN"), "zh-hans");
+        assert_eq!(language_key("zh_TW"), "zh-hant");
+        assert_eq!(language_key("English"), "en");
+        assert_eq!(language_key("en-GB"), "en");
+    }
+
+    #[test]
+    fn plans_unused_languages_of_unsigned_app() {
+        let dir = fixture_dir("unsigned");
+        let app = dir.join("A.app");
+        write_lprojs(&app.join("Contents/Resources"), &["en", "fr", "Base", "zh_CN"]);
+        let service = LocalizationService::with_languages(vec!["zh-Hans-CN".into(), "en-US".into()]);
+
+        let analysis = service.analyze_app(&app.to_string_lossy());
+        assert_eq!((analysis.total_size, analysis.unused_size), (40, 10));
+        assert!(analysis.removal_allowed);
+        let plan = service.plan_removal(&[app.to_string_lossy().to_string()]);
+        assert_eq!(plan.items.len(), 1);
+        assert!(plan.items[0].path.ends_with("fr.lproj"));
+        assert!(plan.skipped_apps.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_development_region() {
+        let dir = fixture_dir("development");
+        let app = dir.join("B.app");
+        fs::create_dir_all(app.join("Contents")).unwrap();
+        let mut info = plist::Dictionary::new();
+        info.insert("CFBundleDevelopmentRegion".to_string(), "German".into());
+        plist::Value::Dictionary(info).to_file_xml(app.join("Contents/Info.plist")).unwrap();
+        write_lprojs(&app.join("Contents/Resources"), &["de", "fr", "ja"]);
+
+        let analysis = LocalizationService::with_languages(vec!["ja".into()]).analyze_app(&app.to_string_lossy());
+        assert_eq!(analysis.unused_size, 10);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn protects_lprojs_in_signed_nested_bundles() {
+        let dir = fixture_dir("nested");
+        let app = dir.join("C.app");
+        write_lprojs(&app.join("Contents/Resources"), &["en", "fr"]);
+        let framework = app.join("Contents/Frameworks/Kit.framework");
+        write_lprojs(&framework.join("Versions/A/Resources"), &["de"]);
+        sign(&framework.join("Versions/A"));
+        std::os::unix::fs::symlink("A", framework.join("Versions/Current")).unwrap();
+        let helper = app.join("Contents/Frameworks/Helper.app");
+        write_lprojs(&helper.join("Contents/Resources"), &["ja"]);
+        sign(&helper.join("Contents"));
+        let service = LocalizationService::with_languages(vec!["en".into()]);
+
+        let analysis = service.analyze_app(&app.to_string_lossy());
+        assert!(!analysis.is_signed);
+        let removable = |name: &str| analysis.localizations.iter()
+            .find(|l| l.path.ends_with(name))
+            .unwrap()
+            .removable;
+        assert!(removable("Contents/Resources/fr.lproj"));
+        assert!(!removable("Kit.framework/Versions/A/Resources/de.lproj"));
+        assert!(!removable("Helper.app/Contents/Resources/ja.lproj"));
+
+        let plan = service.plan_removal(&[app.to_string_lossy().to_string()]);
+        assert_eq!(plan.items.len(), 1);
+        assert!(plan.items[0].path.ends_with("C.app/Contents/Resources/fr.lproj"));
+        assert_eq!(plan.skipped_apps, vec![app.to_string_lossy().to_string()]);
+
+        sign(&app.join("Contents"));
+        let analysis = service.analyze_app(&app.to_string_lossy());
+        assert!(analysis.is_signed && !analysis.removal_allowed);
+        assert!(analysis.localizations.iter().all(|l| !l.removable));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,488 @@
+//! Mach-O 文件解析服务
+//!
+//! 纯 Rust 读取 Mach-O 与 fat（通用二进制）头部，
+//! 获取应用主程序及内嵌框架的架构和最低系统版本。
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::models::macho::{AppArchInfo, FrameworkArch};
+
+const FAT_MAGIC: u32 = 0xcafe_babe;
+const FAT_MAGIC_64: u32 = 0xcafe_babf;
+const MH_MAGIC: u32 = 0xfeed_face;
+const MH_MAGIC_64: u32 = 0xfeed_facf;
+
+const CPU_ARCH_ABI64: u32 = 0x0100_0000;
+const CPU_ARCH_ABI64_32: u32 = 0x0200_0000;
+const CPU_TYPE_X86: u32 = 7;
+const CPU_TYPE_ARM: u32 = 12;
+const CPU_TYPE_POWERPC: u32 = 18;
+
+const LC_CODE_SIGNATURE: u32 = 0x1d;
+const LC_VERSION_MIN_MACOSX: u32 = 0x24;
+const LC_BUILD_VERSION: u32 = 0x32;
+
+/// 进程标志: 通过 Rosetta 转译运行
+const P_TRANSLATED: u32 = 0x0002_0000;
+
+/// fat 头部中允许的最大架构数（超出则视为 Java class 等其他格式）
+const MAX_FAT_ARCHS: u32 = 32;
+
+/// 代码签名超级块的最大读取长度
+const MAX_CODE_SIGNATURE_SIZE: u32 = 16 * 1024 * 1024;
+
+/// 单个架构切片
+#[derive(Debug, Clone)]
+pub struct MachOSlice {
+    /// CPU 类型
+    pub cpu_type: u32,
+    /// CPU 子类型
+    pub cpu_subtype: u32,
+    /// 切片在文件中的偏移
+    pub offset: u64,
+    /// 切片大小
+    pub size: u64,
+    /// 最低系统版本
+    pub min_os_version: Option<String>,
+    /// 代码签名数据位置（相对切片的偏移, 长度）
+    pub code_signature: Option<(u32, u32)>,
+}
+
+impl MachOSlice {
+    /// 架构名称
+    pub fn arch_name(&self) -> String {
+        arch_name(self.cpu_type, self.cpu_subtype)
+    }
+}
+
+/// Mach-O 文件
+#[derive(Debug, Clone)]
+pub struct MachOFile {
+    /// 是否为 fat（通用）二进制
+    pub is_fat: bool,
+    /// 架构切片
+    pub slices: Vec<MachOSlice>,
+}
+
+impl MachOFile {
+    /// 所有架构名称
+    pub fn architectures(&self) -> Vec<String> {
+        self.slices.iter().map(|s| s.arch_name()).collect()
+    }
+}
+
+/// 将 CPU 类型转换为架构名称
+pub fn arch_name(cpu_type: u32, cpu_subtype: u32) -> String {
+    let subtype = cpu_subtype & 0x00ff_ffff;
+    match cpu_type {
+        CPU_TYPE_X86 => "i386".to_string(),
+        t if t == CPU_TYPE_X86 | CPU_ARCH_ABI64 => {
+            if subtype == 8 { "x86_64h".to_string() } else { "x86_64".to_string() }
+        }
+        CPU_TYPE_ARM => match subtype {
+            9 => "armv7".to_string(),
+            11 => "armv7s".to_string(),
+            _ => "arm".to_string(),
+        },
+        t if t == CPU_TYPE_ARM | CPU_ARCH_ABI64 => {
+            if subtype == 2 { "arm64e".to_string() } else { "arm64".to_string() }
+        }
+        t if t == CPU_TYPE_ARM | CPU_ARCH_ABI64_32 => "arm64_32".to_string(),
+        CPU_TYPE_POWERPC => "ppc".to_string(),
+        t if t == CPU_TYPE_POWERPC | CPU_ARCH_ABI64 => "ppc64".to_string(),
+        _ => format!("unknown({:#x})", cpu_type),
+    }
+}
+
+/// 根据架构列表判断架构类型
+pub fn arch_kind(architectures: &[String]) -> String {
+    let has_arm = architectures.iter().any(|a| a.starts_with("arm64"));
+    let has_intel = architectures.iter().any(|a| a.starts_with("x86_64") || a == "i386");
+    match (has_arm, has_intel) {
+        (true, true) => "universal",
+        (true, false) => "apple_silicon",
+        (false, true) => "intel",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+/// 当前机器的原生架构名称
+pub fn native_arch() -> &'static str {
+    match std::env::consts::ARCH {
+        "aarch64" => "arm64",
+        "x86_64" => "x86_64",
+        other => other,
+    }
+}
+
+/// 解码 Mach-O 中的版本号（xxxx.yy.zz）
+fn decode_version(version: u32) -> String {
+    let major = version >> 16;
+    let minor = (version >> 8) & 0xff;
+    let patch = version & 0xff;
+    if patch == 0 {
+        format!("{}.{}", major, minor)
+    } else {
+        format!("{}.{}.{}", major, minor, patch)
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+    Some(if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+}
+
+fn read_u64(data: &[u8], offset: usize, big_endian: bool) -> Option<u64> {
+    let bytes: [u8; 8] = data.get(offset..offset.checked_add(8)?)?.try_into().ok()?;
+    Some(if big_endian { u64::from_be_bytes(bytes) } else { u64::from_le_bytes(bytes) })
+}
+
+/// 使用按偏移读取的回调解析 Mach-O 文件
+fn parse_with<F>(mut read_at: F, file_len: u64) -> Option<MachOFile>
+where
+    F: FnMut(u64, usize) -> Option<Vec<u8>>,
+{
+    let head = read_at(0, 8)?;
+    let magic = read_u32(&head, 0, true)?;
+
+    if magic == FAT_MAGIC || magic == FAT_MAGIC_64 {
+        let is_64 = magic == FAT_MAGIC_64;
+        let count = read_u32(&head, 4, true)?;
+        if count == 0 || count > MAX_FAT_ARCHS {
+            return None;
+        }
+        let entry_size = if is_64 { 32 } else { 20 };
+        let table = read_at(8, entry_size * count as usize)?;
+
+        let mut slices = Vec::new();
+        for i in 0..count as usize {
+            let base = i * entry_size;
+            let cpu_type = read_u32(&table, base, true)?;
+            let cpu_subtype = read_u32(&table, base + 4, true)?;
+            let (offset, size) = if is_64 {
+                (read_u64(&table, base + 8, true)?, read_u64(&table, base + 16, true)?)
+            } else {
+                (read_u32(&table, base + 8, true)? as u64, read_u32(&table, base + 12, true)? as u64)
+            };
+            if offset.checked_add(size)? > file_len {
+                return None;
+            }
+            let mut slice = MachOSlice {
+                cpu_type,
+                cpu_subtype,
+                offset,
+                size,
+                min_os_version: None,
+                code_signature: None,
+            };
+            parse_load_commands(&mut read_at, &mut slice);
+            slices.push(slice);
+        }
+        return Some(MachOFile { is_fat: true, slices });
+    }
+
+    let le_magic = read_u32(&head, 0, false)?;
+    if le_magic == MH_MAGIC || le_magic == MH_MAGIC_64 || magic == MH_MAGIC || magic == MH_MAGIC_64 {
+        let mut slice = MachOSlice {
+            cpu_type: 0,
+            cpu_subtype: 0,
+            offset: 0,
+            size: file_len,
+            min_os_version: None,
+            code_signature: None,
+        };
+        parse_load_commands(&mut read_at, &mut slice);
+        if slice.cpu_type == 0 {
+            return None;
+        }
+        return Some(MachOFile { is_fat: false, slices: vec![slice] });
+    }
+
+    None
+}
+
+/// 解析单个切片的 mach_header 和加载命令
+fn parse_load_commands<F>(read_at: &mut F, slice: &mut MachOSlice)
+where
+    F: FnMut(u64, usize) -> Option<Vec<u8>>,
+{
+    let Some(header) = read_at(slice.offset, 32) else {
+        return;
+    };
+    let Some(magic) = read_u32(&header, 0, false) else {
+        return;
+    };
+    let (big_endian, is_64) = match magic {
+        MH_MAGIC => (false, false),
+        MH_MAGIC_64 => (false, true),
+        m if m.swap_bytes() == MH_MAGIC => (true, false),
+        m if m.swap_bytes() == MH_MAGIC_64 => (true, true),
+        _ => return,
+    };
+
+    let (Some(cpu_type), Some(cpu_subtype), Some(ncmds), Some(sizeofcmds)) = (
+        read_u32(&header, 4, big_endian),
+        read_u32(&header, 8, big_endian),
+        read_u32(&header, 16, big_endian),
+        read_u32(&header, 20, big_endian),
+    ) else {
+        return;
+    };
+    slice.cpu_type = cpu_type;
+    slice.cpu_subtype = cpu_subtype;
+
+    let header_size = if is_64 { 32 } else { 28 };
+    let Some(commands) = read_at(slice.offset + header_size, sizeofcmds as usize) else {
+        return;
+    };
+
+    let mut cursor = 0usize;
+    for _ in 0..ncmds {
+        let (Some(cmd), Some(cmdsize)) = (read_u32(&commands, cursor, big_endian), read_u32(&commands, cursor + 4, big_endian)) else {
+            break;
+        };
+        match cmd {
+            LC_VERSION_MIN_MACOSX => {
+                if let Some(version) = read_u32(&commands, cursor + 8, big_endian) {
+                    slice.min_os_version = Some(decode_version(version));
+                }
+            }
+            LC_BUILD_VERSION => {
+                if let Some(minos) = read_u32(&commands, cursor + 12, big_endian) {
+                    slice.min_os_version = Some(decode_version(minos));
+                }
+            }
+            LC_CODE_SIGNATURE => {
+                if let (Some(dataoff), Some(datasize)) = (read_u32(&commands, cursor + 8, big_endian), read_u32(&commands, cursor + 12, big_endian)) {
+                    slice.code_signature = Some((dataoff, datasize));
+                }
+            }
+            _ => {}
+        }
+        if cmdsize < 8 {
+            break;
+        }
+        cursor += cmdsize as usize;
+    }
+}
+
+/// 从内存数据解析 Mach-O
+pub fn parse_macho_bytes(data: &[u8]) -> Option<MachOFile> {
+    parse_with(
+        |offset, len| {
+            let start = usize::try_from(offset).ok()?;
+            data.get(start..start.checked_add(len)?).map(|s| s.to_vec())
+        },
+        data.len() as u64,
+    )
+}
+
+/// 读取磁盘上的 Mach-O 文件（只读取头部和加载命令）
+pub fn read_macho(path: &Path) -> Option<MachOFile> {
+    let mut file = File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+    if file_len < 8 {
+        return None;
+    }
+    parse_with(
+        |offset, len| {
+            if offset.checked_add(len as u64)? > file_len {
+                return None;
+            }
+            let mut buf = vec![0u8; len];
+            file.seek(SeekFrom::Start(offset)).ok()?;
+            file.read_exact(&mut buf).ok()?;
+            Some(buf)
+        },
+        file_len,
+    )
+}
+
+/// 读取 Mach-O 中的代码签名超级块（fat 文件优先取本机架构切片）
+pub fn read_code_signature(path: &Path) -> Option<Vec<u8>> {
+    let macho = read_macho(path)?;
+    let native = native_arch();
+    let slice = macho.slices.iter()
+        .filter(|s| s.code_signature.is_some())
+        .find(|s| s.arch_name() == native)
+        .or_else(|| macho.slices.iter().find(|s| s.code_signature.is_some()))?;
+    let (dataoff, datasize) = slice.code_signature?;
+    if datasize > MAX_CODE_SIGNATURE_SIZE {
+        return None;
+    }
+
+    // 签名数据必须位于切片和文件范围内，避免按损坏的长度分配或越界读取
+    let mut file = File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+    let start = slice.offset.checked_add(u64::from(dataoff))?;
+    let end = start.checked_add(u64::from(datasize))?;
+    if u64::from(dataoff) + u64::from(datasize) > slice.size || end > file_len {
+        return None;
+    }
+    let mut buf = vec![0u8; datasize as usize];
+    file.seek(SeekFrom::Start(start)).ok()?;
+    file.read_exact(&mut buf).ok()?;
+    Some(buf)
+}
+
+/// Mach-O 解析服务
+pub struct MachOService;
+
+impl MachOService {
+    /// 创建新的 Mach-O 解析服务实例
+    pub fn new() -> Self {
+        MachOService
+    }
+
+    /// 获取应用主可执行文件路径
+    pub fn main_executable(&self, app_path: &Path) -> Option<PathBuf> {
+        let macos_dir = app_path.join("Contents/MacOS");
+        let executable = plist::Value::from_file(app_path.join("Contents/Info.plist"))
+            .ok()
+            .and_then(|v| {
+                v.as_dictionary()?
+                    .get("CFBundleExecutable")?
+                    .as_string()
+                    .map(|s| s.to_string())
+            });
+
+        if let Some(name) = executable {
+            let path = macos_dir.join(name);
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+
+        // 备用：Contents/MacOS 中第一个非隐藏文件
+        fs::read_dir(&macos_dir).ok()?
+            .flatten()
+            .map(|e| e.path())
+            .find(|p| {
+                p.is_file() && !p.file_name()
+                    .map(|n| n.to_string_lossy().starts_with('.'))
+                    .unwrap_or(true)
+            })
+    }
+
+    /// 快速获取应用主程序的架构和最低系统版本
+    pub fn get_executable_arch(&self, app_path: &Path) -> (Vec<String>, String) {
+        let Some(macho) = self.main_executable(app_path).and_then(|p| read_macho(&p)) else {
+            return (Vec::new(), String::new());
+        };
+        let min_os = macho.slices.iter()
+            .filter_map(|s| s.min_os_version.clone())
+            .next()
+            .unwrap_or_default();
+        (macho.architectures(), min_os)
+    }
+
+    /// 获取应用完整架构信息（包含内嵌框架与转译状态）
+    pub fn get_app_arch_info(&self, app_path: &str) -> Result<AppArchInfo, String> {
+        let path = Path::new(app_path);
+        if !path.exists() {
+            return Err("应用不存在".to_string());
+        }
+
+        let executable = self.main_executable(path);
+        let (architectures, min_os_version) = self.get_executable_arch(path);
+        let frameworks = self.get_framework_archs(path);
+
+        let translated_pids: Vec<u32> = match &executable {
+            Some(exe) => {
+                let exe = exe.to_string_lossy();
+                get_translated_processes()
+                    .into_iter()
+                    .filter(|(_, p)| *p == exe)
+                    .map(|(pid, _)| pid)
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        Ok(AppArchInfo {
+            app_path: app_path.to_string(),
+            executable_path: executable.map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+            arch_kind: arch_kind(&architectures),
+            architectures,
+            min_os_version,
+            frameworks,
+            is_translated: !translated_pids.is_empty(),
+            translated_pids,
+        })
+    }
+
+    /// 获取 Contents/Frameworks 中框架与动态库的架构
+    fn get_framework_archs(&self, app_path: &Path) -> Vec<FrameworkArch> {
+        let mut frameworks = Vec::new();
+        let Ok(entries) = fs::read_dir(app_path.join("Contents/Frameworks")) else {
+            return frameworks;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            let binary = if name.ends_with(".framework") {
+                let stem = name.trim_end_matches(".framework");
+                let direct = path.join(stem);
+                if direct.exists() {
+                    direct
+                } else {
+                    path.join("Versions/Current").join(stem)
+                }
+            } else if name.ends_with(".dylib") {
+                path.clone()
+            } else {
+                continue;
+            };
+
+            if let Some(macho) = read_macho(&binary) {
+                frameworks.push(FrameworkArch {
+                    name,
+                    path: binary.to_string_lossy().to_string(),
+                    architectures: macho.architectures(),
+                });
+            }
+        }
+
+        frameworks.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        frameworks
+    }
+}
+
+/// 获取正在通过 Rosetta 转译运行的进程（pid, 可执行文件路径）
+pub fn get_translated_processes() -> Vec<(u32, String)> {
+    if !cfg!(target_os = "macos") {
+        return Vec::new();
+    }
+
+    let output = Command::new("ps")
+        .args(["-axo", "pid=,flags=,comm="])
+        .output();
+
+    let mut processes = Vec::new();
+    if let Ok(output) = output {
+        if output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            for line in stdout.lines() {
+                // 路径中可能包含空格，只拆分前两列
+                let Some((pid, rest)) = line.trim_start().split_once(char::is_whitespace) else {
+                    continue;
+                };
+                let Some((flags, comm)) = rest.trim_start().split_once(char::is_whitespace) else {
+                    continue;
+                };
+                let (Ok(pid), Ok(flags)) = (pid.parse::<u32>(), u32::from_str_radix(flags, 16)) else {
+                    continue;
+                };
+                if flags & P_TRANSLATED != 0 {
+                    processes.push((pid, comm.trim().to_string()));
+                }
+            }
+        }
+    }
+    processes
+}
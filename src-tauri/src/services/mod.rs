@@ -5,11 +5,33 @@
 
 pub mod system_service;
 pub mod process_service;
+pub mod sampler_service;
 pub mod disk_service;
 pub mod cleaner_service;
 pub mod app_service;
 pub mod settings_service;
 pub mod search_history_service;
+pub mod receipt_service;
+pub mod macho_service;
+pub mod thin_service;
+pub mod localization_service;
+pub mod bundle_size_service;
+pub mod runtime_service;
+pub mod codesign_service;
+pub mod entitlement_service;
+pub mod association_service;
+pub mod usage_service;
+pub mod update_service;
+pub mod inventory_service;
+pub mod app_source_service;
+pub mod homebrew_service;
+pub mod developer_storage_service;
+pub mod duplicate_service;
+pub mod duplicate_catalog_service;
+pub mod bundle_rewrite_service;
+pub mod icon_badge_service;
+pub mod launch_profile_service;
+pub mod instance_tracker_service;
 
 // 为了避免未使用导入警告，我们只在需要的地方使用这些模块
 // pub use system_service::*;
@@ -1,7 +1,174 @@
 //! 进程服务实现
 
-use sysinfo::{System, Signal};
-use crate::models::process::{Process, ProcessList};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::fs;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use sysinfo::{Pid, System, Users};
+use crate::models::process::{AppProcessGroup, AppProcessGroups, Process, ProcessTree, ProcessTreeNode};
+use crate::services::app_service::AppService;
+use crate::services::sampler_service::latest_processes;
+use crate::services::usage_service::owning_app_bundle;
+
+/// 应用包信息缓存（应用包路径 -> Info.plist 修改时间、名称、标识符、图标）
+static APP_INFO_CACHE: Mutex<Option<HashMap<String, CachedBundleSummary>>> = Mutex::new(None);
+
+/// 缓存的应用包信息及读取时 Info.plist 的修改时间
+type CachedBundleSummary = (Option<SystemTime>, BundleSummary);
+
+/// 应用包的名称、标识符和图标
+#[derive(Clone)]
+struct BundleSummary {
+    name: String,
+    bundle_id: String,
+    icon: String,
+}
+
+/// 转换为进程信息
+pub fn to_process(system: &System, users: &Users, pid: Pid, process: &sysinfo::Process) -> Process {
+    let command_line = process.cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let user = process.user_id()
+        .and_then(|uid| users.get_user_by_id(uid))
+        .map(|u| u.name().to_string())
+        .unwrap_or_default();
+
+    Process {
+        pid: pid.as_u32(),
+        name: process.name().to_string_lossy().to_string(),
+        cpu_usage: process.cpu_usage() / 100.0,
+        memory_usage: if system.total_memory() > 0 {
+            process.memory() as f32 / system.total_memory() as f32
+        } else {
+            0.0
+        },
+        status: format!("{:?}", process.status()),
+        start_time: process.start_time(),
+        parent_pid: process.parent().map(|p| p.as_u32()),
+        user,
+        command_line,
+        exe_path: process.exe().map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+        cwd: process.cwd().map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+        thread_count: thread_count(pid, process),
+        memory: process.memory(),
+        virtual_memory: process.virtual_memory(),
+    }
+}
+
+/// 读取进程线程数，macOS 通过 proc_pidinfo 读取任务信息
+#[cfg(target_os = "macos")]
+fn thread_count(pid: Pid, _process: &sysinfo::Process) -> Option<u32> {
+    let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let read = unsafe {
+        libc::proc_pidinfo(
+            pid.as_u32() as libc::c_int,
+            libc::PROC_PIDTASKINFO,
+            0,
+            &mut info as *mut libc::proc_taskinfo as *mut libc::c_void,
+            size,
+        )
+    };
+    if read == size {
+        Some(info.pti_threadnum as u32)
+    } else {
+        None
+    }
+}
+
+/// 读取进程线程数，Linux 读取 /proc/<pid>/status 的 Threads 字段
+#[cfg(target_os = "linux")]
+fn thread_count(pid: Pid, _process: &sysinfo::Process) -> Option<u32> {
+    fs::read_to_string(format!("/proc/{}/status", pid.as_u32()))
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("Threads:"))
+        .and_then(|count| count.trim().parse().ok())
+}
+
+/// 其他平台不读取线程数（sysinfo 需要逐个进程列出任务，采样开销过大）
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn thread_count(_pid: Pid, _process: &sysinfo::Process) -> Option<u32> {
+    None
+}
+
+/// 按父子关系把进程组织成树，父进程不存在（或指向自身）的进程以及成环的进程作为顶层
+pub fn build_process_tree(processes: Vec<Process>) -> ProcessTree {
+    let pids: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+    let mut children: HashMap<u32, Vec<Process>> = HashMap::new();
+    let mut roots = Vec::new();
+    for process in processes {
+        match process.parent_pid {
+            Some(parent) if parent != process.pid && pids.contains(&parent) => {
+                children.entry(parent).or_default().push(process);
+            }
+            _ => roots.push(process),
+        }
+    }
+
+    let mut roots: Vec<ProcessTreeNode> = roots.into_iter()
+        .map(|p| build_node(p, &mut children))
+        .collect();
+    // 父进程关系成环（如 PID 被复用）的进程从顶层不可达，把剩余的进程提升为顶层
+    while let Some(parent) = children.keys().min().copied() {
+        let orphans = children.remove(&parent).unwrap_or_default();
+        roots.extend(orphans.into_iter().map(|p| build_node(p, &mut children)));
+    }
+    roots.sort_by(|a, b| b.subtree_memory.cmp(&a.subtree_memory));
+    ProcessTree { roots }
+}
+
+/// 递归生成节点并计算子树合计（已取出的子进程不会再次出现，避免循环）
+fn build_node(process: Process, children: &mut HashMap<u32, Vec<Process>>) -> ProcessTreeNode {
+    let mut nodes: Vec<ProcessTreeNode> = children.remove(&process.pid)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|child| build_node(child, children))
+        .collect();
+    nodes.sort_by(|a, b| b.subtree_memory.cmp(&a.subtree_memory));
+
+    ProcessTreeNode {
+        subtree_count: 1 + nodes.iter().map(|n| n.subtree_count).sum::<u32>(),
+        subtree_cpu_usage: process.cpu_usage + nodes.iter().map(|n| n.subtree_cpu_usage).sum::<f32>(),
+        subtree_memory: process.memory + nodes.iter().map(|n| n.subtree_memory).sum::<u64>(),
+        process,
+        children: nodes,
+    }
+}
+
+/// 读取应用包信息（通过现有的应用信息读取逻辑，Info.plist 未修改时使用缓存）
+fn bundle_summary(app_path: &Path) -> BundleSummary {
+    let key = app_path.to_string_lossy().to_string();
+    let modified = fs::metadata(app_path.join("Contents/Info.plist"))
+        .and_then(|m| m.modified())
+        .ok();
+    let mut cache = APP_INFO_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(HashMap::new);
+    if let Some((cached_modified, summary)) = cache.get(&key) {
+        if *cached_modified == modified {
+            return summary.clone();
+        }
+    }
+
+    let summary = match AppService::new().get_app_info_with_icon(app_path) {
+        Some(info) => BundleSummary {
+            name: info.name,
+            bundle_id: info.identifier,
+            icon: info.icon_path,
+        },
+        None => BundleSummary {
+            name: app_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+            bundle_id: String::new(),
+            icon: String::new(),
+        },
+    };
+    cache.insert(key, (modified, summary.clone()));
+    summary
+}
 
 /// 进程服务
 pub struct ProcessService;
@@ -12,46 +179,140 @@ impl ProcessService {
         ProcessService
     }
 
-    /// 获取进程列表
-    pub fn get_process_list(&self) -> ProcessList {
-        let mut system = System::new_all();
-        system.refresh_all();
-
-        let mut processes = Vec::new();
-
-        for (pid, process) in system.processes() {
-            processes.push(Process {
-                pid: pid.as_u32(),
-                name: process.name().to_string_lossy().to_string(),
-                cpu_usage: process.cpu_usage() / 100.0,
-                memory_usage: if system.total_memory() > 0 {
-                    process.memory() as f32 / system.total_memory() as f32
-                } else {
-                    0.0
-                },
-                status: format!("{:?}", process.status()),
-                start_time: process.start_time(),
+    /// 结束进程（按最新一次采样确认进程存在）
+    pub fn kill_process(&self, pid: u32) -> bool {
+        if !latest_processes().iter().any(|p| p.pid == pid) {
+            println!("Process with pid {} not found", pid);
+            return false;
+        }
+        let pid = pid as libc::pid_t;
+        // 先尝试 SIGTERM (优雅结束)，失败时尝试 SIGKILL (强制结束)
+        unsafe { libc::kill(pid, libc::SIGTERM) == 0 || libc::kill(pid, libc::SIGKILL) == 0 }
+    }
+
+    /// 按所属应用包汇总进程（辅助程序、渲染进程、XPC 服务和 Frameworks 内的进程归属到最外层应用）
+    pub fn group_by_app(&self, processes: &[Process]) -> AppProcessGroups {
+        let mut groups: HashMap<String, AppProcessGroup> = HashMap::new();
+        let mut other_process_count = 0;
+        let mut other_cpu_usage = 0.0;
+        let mut other_memory = 0;
+
+        for process in processes {
+            let Some(bundle) = owning_app_bundle(Path::new(&process.exe_path)) else {
+                other_process_count += 1;
+                other_cpu_usage += process.cpu_usage;
+                other_memory += process.memory;
+                continue;
+            };
+            let key = bundle.to_string_lossy().to_string();
+            let group = groups.entry(key.clone()).or_insert_with(|| {
+                let summary = bundle_summary(&bundle);
+                AppProcessGroup {
+                    app_path: key,
+                    app_name: summary.name,
+                    bundle_id: summary.bundle_id,
+                    icon: summary.icon,
+                    pids: Vec::new(),
+                    process_count: 0,
+                    thread_count: 0,
+                    cpu_usage: 0.0,
+                    memory_usage: 0.0,
+                    memory: 0,
+                }
             });
+            group.pids.push(process.pid);
+            group.process_count += 1;
+            group.thread_count += process.thread_count.unwrap_or(0);
+            group.cpu_usage += process.cpu_usage;
+            group.memory_usage += process.memory_usage;
+            group.memory += process.memory;
         }
 
-        ProcessList { processes }
+        let mut apps: Vec<AppProcessGroup> = groups.into_values().collect();
+        apps.sort_by(|a, b| b.memory.cmp(&a.memory));
+        AppProcessGroups {
+            apps,
+            other_process_count,
+            other_cpu_usage,
+            other_memory,
+        }
     }
 
-    /// 结束进程
-    pub fn kill_process(&self, pid: u32) -> bool {
-        let mut system = System::new_all();
-        system.refresh_all();
+    /// 获取进程所属应用的图标，不属于任何应用包时为空
+    pub fn get_process_icon(&self, process: &Process) -> String {
+        owning_app_bundle(Path::new(&process.exe_path))
+            .map(|bundle| bundle_summary(&bundle).icon)
+            .unwrap_or_default()
+    }
+}
 
-        if let Some(process) = system.process(sysinfo::Pid::from_u32(pid)) {
-            // 先尝试 SIGTERM (优雅结束)
-            if process.kill_with(Signal::Term).unwrap_or(false) {
-                return true;
-            }
-            // 如果失败，尝试 SIGKILL (强制结束)
-            process.kill_with(Signal::Kill).unwrap_or(false)
-        } else {
-            println!("Process with pid {} not found", pid);
-            false
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, parent_pid: Option<u32>, memory: u64) -> Process {
+        Process {
+            pid,
+            name: format!("p{}", pid),
+            cpu_usage: 0.0,
+            memory_usage: 0.0,
+            status: "Run".to_string(),
+            start_time: 0,
+            parent_pid,
+            user: String::new(),
+            command_line: String::new(),
+            exe_path: String::new(),
+            cwd: String::new(),
+            thread_count: None,
+            memory,
+            virtual_memory: memory,
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn builds_tree_with_subtree_totals() {
+        let tree = build_process_tree(vec![
+            process(1, None, 10),
+            process(2, Some(1), 20),
+            process(3, Some(2), 30),
+            process(4, Some(1), 5),
+            process(5, Some(99), 1),
+            process(6, Some(6), 2),
+        ]);
+
+        let pids: Vec<u32> = tree.roots.iter().map(|n| n.process.pid).collect();
+        assert_eq!(pids, vec![1, 6, 5]);
+        let root = &tree.roots[0];
+        assert_eq!((root.subtree_count, root.subtree_memory), (4, 65));
+        assert_eq!(root.children.iter().map(|n| n.process.pid).collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(root.children[0].children[0].process.pid, 3);
+    }
+
+    #[test]
+    fn promotes_parent_cycles_to_roots() {
+        let tree = build_process_tree(vec![
+            process(1, None, 10),
+            process(7, Some(8), 1),
+            process(8, Some(7), 2),
+            process(9, Some(8), 3),
+        ]);
+
+        let total: u32 = tree.roots.iter().map(|n| n.subtree_count).sum();
+        assert_eq!(total, 4);
+        let cycle = tree.roots.iter().find(|n| n.process.pid != 1).unwrap();
+        assert_eq!((cycle.process.pid, cycle.subtree_count), (8, 3));
+        let mut children: Vec<u32> = cycle.children.iter().map(|n| n.process.pid).collect();
+        children.sort();
+        assert_eq!(children, vec![7, 9]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reads_thread_count_from_proc_status() {
+        let pid = Pid::from_u32(std::process::id());
+        let mut system = System::new();
+        system.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+        let process = system.process(pid).unwrap();
+        assert!(thread_count(pid, process).unwrap() >= 1);
+    }
+}
@@ -0,0 +1,563 @@
+//! 安装包回执服务实现
+//!
+//! 读取 `/var/db/receipts` 下的 `*.plist` 与 `*.bom` 文件，
+//! 列出通过 `.pkg` 安装的软件及其释放的所有文件。
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::models::receipt::{PkgReceipt, PkgReceiptList, ReceiptFile};
+
+/// 系统回执目录
+const RECEIPTS_DIR: &str = "/var/db/receipts";
+
+/// 路径解析的最大层级（防止损坏的 BOM 造成死循环）
+const MAX_PATH_DEPTH: usize = 256;
+
+/// 已解析回执的缓存: plist 路径 -> (plist 修改时间, BOM 修改时间, 回执)
+type ReceiptCache = HashMap<PathBuf, (Option<SystemTime>, Option<SystemTime>, Arc<PkgReceipt>)>;
+
+/// 回执缓存（BOM 解析开销较大，文件未变化时复用）
+static RECEIPT_CACHE: Mutex<Option<ReceiptCache>> = Mutex::new(None);
+
+/// BOM 中的路径记录
+#[derive(Debug, Clone)]
+pub struct BomEntry {
+    /// 相对路径（不含开头的 "./"）
+    pub path: String,
+    /// 文件类型: 1 文件, 2 目录, 3 链接, 4 设备
+    pub kind: u8,
+    /// 权限位
+    pub mode: u16,
+    /// 文件大小(bytes)
+    pub size: u64,
+}
+
+/// BOM 存储文件
+struct BomStore<'a> {
+    data: &'a [u8],
+    blocks: Vec<(u32, u32)>,
+    vars: HashMap<String, u32>,
+}
+
+impl<'a> BomStore<'a> {
+    /// 解析 BOMStore 头部、块索引表和变量表
+    fn parse(data: &'a [u8]) -> Result<Self, String> {
+        if data.len() < 32 || &data[0..8] != b"BOMStore" {
+            return Err("不是有效的 BOM 文件".to_string());
+        }
+
+        let index_offset = read_u32(data, 16).ok_or("BOM 头部损坏")? as usize;
+        let vars_offset = read_u32(data, 24).ok_or("BOM 头部损坏")? as usize;
+
+        // 块索引表: count, 然后是 (address, length) 数组
+        let block_count = read_u32(data, index_offset).ok_or("BOM 索引表损坏")? as usize;
+        let mut blocks = Vec::with_capacity(block_count.min(1 << 20));
+        for i in 0..block_count {
+            let entry = index_offset + 4 + i * 8;
+            match (read_u32(data, entry), read_u32(data, entry + 4)) {
+                (Some(address), Some(length)) => blocks.push((address, length)),
+                _ => return Err("BOM 索引表损坏".to_string()),
+            }
+        }
+
+        // 变量表: count, 然后是 (block index, name length, name)
+        let var_count = read_u32(data, vars_offset).ok_or("BOM 变量表损坏")?;
+        let mut vars = HashMap::new();
+        let mut cursor = vars_offset + 4;
+        for _ in 0..var_count {
+            let index = read_u32(data, cursor).ok_or("BOM 变量表损坏")?;
+            let name_len = *data.get(cursor + 4).ok_or("BOM 变量表损坏")? as usize;
+            let name = data.get(cursor + 5..cursor + 5 + name_len).ok_or("BOM 变量表损坏")?;
+            vars.insert(String::from_utf8_lossy(name).to_string(), index);
+            cursor += 5 + name_len;
+        }
+
+        Ok(BomStore { data, blocks, vars })
+    }
+
+    /// 读取指定序号的数据块
+    fn block(&self, index: u32) -> Option<&'a [u8]> {
+        let (address, length) = *self.blocks.get(index as usize)?;
+        self.data.get(address as usize..(address as usize).checked_add(length as usize)?)
+    }
+
+    /// 读取 "Paths" 树中的所有路径
+    fn paths(&self) -> Result<Vec<BomEntry>, String> {
+        let tree_index = *self.vars.get("Paths").ok_or("BOM 中没有 Paths 变量")?;
+        let tree = self.block(tree_index).ok_or("BOM Paths 块损坏")?;
+        if tree.len() < 12 || &tree[0..4] != b"tree" {
+            return Err("BOM Paths 块损坏".to_string());
+        }
+
+        // 沿第一个子节点下降到最左侧的叶子
+        let mut node_index = read_u32(tree, 8).ok_or("BOM Paths 块损坏")?;
+        let mut visited = HashSet::new();
+        loop {
+            let node = self.block(node_index).ok_or("BOM 路径节点损坏")?;
+            let is_leaf = read_u16(node, 0).ok_or("BOM 路径节点损坏")? != 0;
+            if is_leaf {
+                break;
+            }
+            if !visited.insert(node_index) {
+                return Err("BOM 路径树存在循环".to_string());
+            }
+            node_index = read_u32(node, 12).ok_or("BOM 路径节点损坏")?;
+        }
+
+        // id -> (父 id, 名称, 类型, 权限, 大小)
+        let mut records: HashMap<u32, (u32, String, u8, u16, u64)> = HashMap::new();
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
+
+        // 沿 forward 指针遍历所有叶子
+        while node_index != 0 && visited.insert(node_index) {
+            let node = self.block(node_index).ok_or("BOM 路径节点损坏")?;
+            let count = read_u16(node, 2).ok_or("BOM 路径节点损坏")? as usize;
+            let forward = read_u32(node, 4).ok_or("BOM 路径节点损坏")?;
+
+            for i in 0..count {
+                let entry = 12 + i * 8;
+                let (Some(info_index), Some(file_index)) = (read_u32(node, entry), read_u32(node, entry + 4)) else {
+                    break;
+                };
+                let (Some(info1), Some(file)) = (self.block(info_index), self.block(file_index)) else {
+                    continue;
+                };
+                let (Some(id), Some(info2_index)) = (read_u32(info1, 0), read_u32(info1, 4)) else {
+                    continue;
+                };
+                let Some(parent) = read_u32(file, 0) else {
+                    continue;
+                };
+                let name_bytes = &file[4.min(file.len())..];
+                let name_end = name_bytes.iter().position(|&b| b == 0).unwrap_or(name_bytes.len());
+                let name = String::from_utf8_lossy(&name_bytes[..name_end]).to_string();
+
+                let (kind, mode, size) = self.block(info2_index)
+                    .map(|info2| (
+                        info2.first().copied().unwrap_or(0),
+                        read_u16(info2, 4).unwrap_or(0),
+                        read_u32(info2, 18).unwrap_or(0) as u64,
+                    ))
+                    .unwrap_or((0, 0, 0));
+
+                records.insert(id, (parent, name, kind, mode, size));
+                order.push(id);
+            }
+
+            node_index = forward;
+        }
+
+        let mut entries = Vec::with_capacity(order.len());
+        for id in order {
+            let Some((_, _, kind, mode, size)) = records.get(&id) else {
+                continue;
+            };
+            let Some(path) = resolve_path(&records, id) else {
+                continue;
+            };
+            entries.push(BomEntry { path, kind: *kind, mode: *mode, size: *size });
+        }
+
+        Ok(entries)
+    }
+}
+
+/// 根据父节点链拼出相对路径
+fn resolve_path(records: &HashMap<u32, (u32, String, u8, u16, u64)>, id: u32) -> Option<String> {
+    let mut parts = Vec::new();
+    let mut current = id;
+    while current != 0 {
+        if parts.len() > MAX_PATH_DEPTH {
+            return None;
+        }
+        let (parent, name, ..) = records.get(&current)?;
+        parts.push(name.as_str());
+        current = *parent;
+    }
+    parts.reverse();
+
+    let path = parts.into_iter()
+        .filter(|p| !p.is_empty() && *p != ".")
+        .collect::<Vec<_>>()
+        .join("/");
+    Some(path.trim_start_matches("./").to_string())
+}
+
+/// 读取大端 u32
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// 读取大端 u16
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+/// 解析 BOM 文件内容
+pub fn parse_bom(data: &[u8]) -> Result<Vec<BomEntry>, String> {
+    BomStore::parse(data)?.paths()
+}
+
+/// 安装包回执服务
+pub struct ReceiptService {
+    receipts_dir: PathBuf,
+}
+
+impl ReceiptService {
+    /// 创建新的安装包回执服务实例
+    pub fn new() -> Self {
+        Self::with_receipts_dir(RECEIPTS_DIR)
+    }
+
+    /// 使用指定回执目录创建实例
+    pub fn with_receipts_dir(receipts_dir: impl Into<PathBuf>) -> Self {
+        ReceiptService { receipts_dir: receipts_dir.into() }
+    }
+
+    /// 获取所有安装包回执
+    pub fn get_receipts(&self, include_files: bool) -> Result<PkgReceiptList, String> {
+        let entries = fs::read_dir(&self.receipts_dir)
+            .map_err(|e| format!("无法读取回执目录: {}", e))?;
+
+        let mut receipts = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|e| e == "plist").unwrap_or(false) {
+                if let Some(receipt) = self.read_receipt(&path) {
+                    let mut receipt = receipt.as_ref().clone();
+                    if !include_files {
+                        receipt.files.clear();
+                    }
+                    receipts.push(receipt);
+                }
+            }
+        }
+
+        receipts.sort_by(|a, b| a.package_id.cmp(&b.package_id));
+        Ok(PkgReceiptList { receipts })
+    }
+
+    /// 获取单个安装包回执（包含完整文件列表）
+    pub fn get_receipt(&self, package_id: &str) -> Result<PkgReceipt, String> {
+        if package_id.is_empty() || package_id.contains('/') || package_id.contains("..") {
+            return Err("无效的安装包标识符".to_string());
+        }
+        let plist_path = self.receipts_dir.join(format!("{}.plist", package_id));
+        if !plist_path.exists() {
+            return Err("安装包回执不存在".to_string());
+        }
+        self.read_receipt(&plist_path)
+            .map(|receipt| receipt.as_ref().clone())
+            .ok_or_else(|| "无法解析安装包回执".to_string())
+    }
+
+    /// 查找安装了指定应用的回执（跳过 Apple 系统安装包）
+    pub fn find_receipts_for_app(&self, app_path: &str) -> Vec<PkgReceipt> {
+        let app_path = app_path.trim_end_matches('/');
+        self.third_party_receipts()
+            .into_iter()
+            .filter(|receipt| receipt.apps.iter().any(|a| a == app_path))
+            .map(|receipt| receipt.as_ref().clone())
+            .collect()
+    }
+
+    /// 一次读取所有回执，生成应用路径到安装包标识符的对应关系（跳过 Apple 系统安装包）
+    pub fn receipts_by_app(&self) -> HashMap<String, Vec<String>> {
+        let mut by_app: HashMap<String, Vec<String>> = HashMap::new();
+        for receipt in self.third_party_receipts() {
+            for app in &receipt.apps {
+                by_app.entry(app.clone()).or_default().push(receipt.package_id.clone());
+            }
+        }
+        by_app
+    }
+
+    /// 读取除 Apple 系统安装包外的所有回执
+    fn third_party_receipts(&self) -> Vec<Arc<PkgReceipt>> {
+        let Ok(entries) = fs::read_dir(&self.receipts_dir) else {
+            return Vec::new();
+        };
+
+        entries.flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let is_plist = path.extension().map(|e| e == "plist").unwrap_or(false);
+                let is_system = path.file_name()
+                    .map(|n| n.to_string_lossy().starts_with("com.apple.pkg."))
+                    .unwrap_or(false);
+                is_plist && !is_system
+            })
+            .filter_map(|path| self.read_receipt(&path))
+            .collect()
+    }
+
+    /// 读取回执，plist 和 BOM 都未修改时使用缓存
+    fn read_receipt(&self, plist_path: &Path) -> Option<Arc<PkgReceipt>> {
+        let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+        let plist_modified = modified(plist_path);
+        let bom_modified = modified(&plist_path.with_extension("bom"));
+
+        let mut cache = RECEIPT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let cache = cache.get_or_insert_with(HashMap::new);
+        if let Some((cached_plist, cached_bom, receipt)) = cache.get(plist_path) {
+            if *cached_plist == plist_modified && *cached_bom == bom_modified {
+                return Some(Arc::clone(receipt));
+            }
+        }
+
+        let receipt = Arc::new(self.parse_receipt(plist_path)?);
+        cache.insert(plist_path.to_path_buf(), (plist_modified, bom_modified, Arc::clone(&receipt)));
+        Some(receipt)
+    }
+
+    /// 解析回执 plist 及对应的 BOM
+    fn parse_receipt(&self, plist_path: &Path) -> Option<PkgReceipt> {
+        let value = plist::Value::from_file(plist_path).ok()?;
+        let dict = value.as_dictionary()?;
+
+        let package_id = dict.get("PackageIdentifier")
+            .and_then(|v| v.as_string())
+            .map(|s| s.to_string())
+            .or_else(|| plist_path.file_stem().map(|s| s.to_string_lossy().to_string()))?;
+        let version = dict.get("PackageVersion")
+            .and_then(|v| v.as_string())
+            .unwrap_or_default()
+            .to_string();
+        let install_date = dict.get("InstallDate")
+            .and_then(|v| v.as_date())
+            .and_then(|d| std::time::SystemTime::from(d).duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let install_prefix = dict.get("InstallPrefixPath")
+            .and_then(|v| v.as_string())
+            .unwrap_or("/")
+            .to_string();
+
+        let bom_path = plist_path.with_extension("bom");
+        let bom_entries = fs::read(&bom_path)
+            .ok()
+            .and_then(|data| parse_bom(&data).ok())
+            .unwrap_or_default();
+
+        let prefix = Path::new("/").join(install_prefix.trim_start_matches('/'));
+        let mut files = Vec::with_capacity(bom_entries.len());
+        let mut apps: Vec<String> = Vec::new();
+        let mut file_count = 0u64;
+        let mut total_size = 0u64;
+
+        for entry in bom_entries {
+            if entry.path.is_empty() {
+                continue;
+            }
+            let full_path = prefix.join(&entry.path).to_string_lossy().to_string();
+
+            if let Some(app) = enclosing_app_bundle(&full_path) {
+                if !apps.contains(&app) {
+                    apps.push(app);
+                }
+            }
+
+            let file_type = match entry.kind {
+                1 => "file",
+                2 => "directory",
+                3 => "symlink",
+                4 => "device",
+                _ => "unknown",
+            };
+            if entry.kind != 2 {
+                file_count += 1;
+                total_size += entry.size;
+            }
+
+            files.push(ReceiptFile {
+                path: full_path,
+                size: entry.size,
+                mode: entry.mode as u32,
+                file_type: file_type.to_string(),
+            });
+        }
+
+        Some(PkgReceipt {
+            package_id,
+            version,
+            install_date,
+            install_prefix,
+            bom_path: bom_path.to_string_lossy().to_string(),
+            file_count,
+            total_size,
+            apps,
+            files,
+        })
+    }
+}
+
+/// 返回路径所在的最外层 .app 包路径
+fn enclosing_app_bundle(path: &str) -> Option<String> {
+    let end = path.find(".app/").map(|i| i + 4)
+        .or_else(|| path.ends_with(".app").then_some(path.len()))?;
+    Some(path[..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BOM 路径记录: (id, 父 id, 名称, 类型, 大小)
+    type FixtureEntry<'a> = (u32, u32, &'a str, u8, u32);
+
+    /// 按 BOMStore 格式构造只有一个叶子节点的 Paths 树
+    fn build_bom(entries: &[FixtureEntry]) -> Vec<u8> {
+        let mut blocks: Vec<Vec<u8>> = vec![Vec::new()];
+        let mut pairs = Vec::new();
+        for (id, parent, name, kind, size) in entries {
+            let mut info2 = vec![0u8; 22];
+            info2[0] = *kind;
+            info2[4..6].copy_from_slice(&0o644u16.to_be_bytes());
+            info2[18..22].copy_from_slice(&size.to_be_bytes());
+            blocks.push(info2);
+            let info2_index = blocks.len() as u32 - 1;
+
+            let mut info1 = id.to_be_bytes().to_vec();
+            info1.extend_from_slice(&info2_index.to_be_bytes());
+            blocks.push(info1);
+            let info1_index = blocks.len() as u32 - 1;
+
+            let mut file = parent.to_be_bytes().to_vec();
+            file.extend_from_slice(name.as_bytes());
+            file.push(0);
+            blocks.push(file);
+            pairs.push((info1_index, blocks.len() as u32 - 1));
+        }
+
+        let mut leaf = Vec::new();
+        leaf.extend_from_slice(&1u16.to_be_bytes());
+        leaf.extend_from_slice(&(pairs.len() as u16).to_be_bytes());
+        leaf.extend_from_slice(&[0u8; 8]);
+        for (info1, file) in &pairs {
+            leaf.extend_from_slice(&info1.to_be_bytes());
+            leaf.extend_from_slice(&file.to_be_bytes());
+        }
+        blocks.push(leaf);
+        let leaf_index = blocks.len() as u32 - 1;
+
+        let mut tree = b"tree".to_vec();
+        tree.extend_from_slice(&1u32.to_be_bytes());
+        tree.extend_from_slice(&leaf_index.to_be_bytes());
+        tree.extend_from_slice(&4096u32.to_be_bytes());
+        tree.extend_from_slice(&(pairs.len() as u32).to_be_bytes());
+        tree.push(0);
+        blocks.push(tree);
+        let tree_index = blocks.len() as u32 - 1;
+
+        let mut data = vec![0u8; 32];
+        let mut index = Vec::new();
+        index.extend_from_slice(&(blocks.len() as u32).to_be_bytes());
+        for block in &blocks {
+            let address = if block.is_empty() { 0 } else { data.len() as u32 };
+            index.extend_from_slice(&address.to_be_bytes());
+            index.extend_from_slice(&(block.len() as u32).to_be_bytes());
+            data.extend_from_slice(block);
+        }
+        let index_offset = data.len() as u32;
+        data.extend_from_slice(&index);
+        let vars_offset = data.len() as u32;
+        data.extend_from_slice(&1u32.to_be_bytes());
+        data.extend_from_slice(&tree_index.to_be_bytes());
+        data.push(5);
+        data.extend_from_slice(b"Paths");
+
+        data[0..8].copy_from_slice(b"BOMStore");
+        data[8..12].copy_from_slice(&1u32.to_be_bytes());
+        data[12..16].copy_from_slice(&(blocks.len() as u32).to_be_bytes());
+        data[16..20].copy_from_slice(&index_offset.to_be_bytes());
+        data[20..24].copy_from_slice(&(index.len() as u32).to_be_bytes());
+        data[24..28].copy_from_slice(&vars_offset.to_be_bytes());
+        let vars_length = data.len() as u32 - vars_offset;
+        data[28..32].copy_from_slice(&vars_length.to_be_bytes());
+        data
+    }
+
+    const FIXTURE: [FixtureEntry<'static>; 6] = [
+        (1, 0, ".", 2, 0),
+        (2, 1, "Applications", 2, 0),
+        (3, 2, "Foo.app", 2, 0),
+        (4, 3, "Contents", 2, 0),
+        (5, 4, "Info.plist", 1, 42),
+        (6, 4, "PkgInfo", 1, 8),
+    ];
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-receipt-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let mut dict = plist::Dictionary::new();
+        dict.insert("PackageIdentifier".into(), "com.example.foo".into());
+        dict.insert("PackageVersion".into(), "1.2".into());
+        dict.insert("InstallPrefixPath".into(), "/".into());
+        plist::Value::Dictionary(dict).to_file_xml(dir.join("com.example.foo.plist")).unwrap();
+        fs::write(dir.join("com.example.foo.bom"), build_bom(&FIXTURE)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parses_bom_paths() {
+        let entries = parse_bom(&build_bom(&FIXTURE)).unwrap();
+        let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec![
+            "",
+            "Applications",
+            "Applications/Foo.app",
+            "Applications/Foo.app/Contents",
+            "Applications/Foo.app/Contents/Info.plist",
+            "Applications/Foo.app/Contents/PkgInfo",
+        ]);
+        assert_eq!((entries[4].kind, entries[4].mode, entries[4].size), (1, 0o644, 42));
+    }
+
+    #[test]
+    fn rejects_invalid_bom() {
+        assert!(parse_bom(b"not a bom file at all, just some bytes").is_err());
+        let mut data = build_bom(&FIXTURE);
+        data.truncate(40);
+        assert!(parse_bom(&data).is_err());
+    }
+
+    #[test]
+    fn reads_receipts_from_fixture_dir() {
+        let dir = fixture_dir("read");
+        let service = ReceiptService::with_receipts_dir(&dir);
+
+        let receipt = service.get_receipt("com.example.foo").unwrap();
+        assert_eq!(receipt.version, "1.2");
+        assert_eq!(receipt.apps, vec!["/Applications/Foo.app".to_string()]);
+        assert_eq!((receipt.file_count, receipt.total_size), (2, 50));
+
+        let found = service.find_receipts_for_app("/Applications/Foo.app/");
+        assert_eq!(found.len(), 1);
+        assert!(service.find_receipts_for_app("/Applications/Bar.app").is_empty());
+
+        let by_app = service.receipts_by_app();
+        assert_eq!(by_app["/Applications/Foo.app"], vec!["com.example.foo".to_string()]);
+
+        let list = service.get_receipts(false).unwrap();
+        assert!(list.receipts[0].files.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_package_ids_with_paths() {
+        let dir = fixture_dir("reject");
+        let service = ReceiptService::with_receipts_dir(dir.join("sub"));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        assert!(service.get_receipt("../com.example.foo").is_err());
+        assert!(service.get_receipt("a/b").is_err());
+        assert!(service.get_receipt("").is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,264 @@
+//! 内嵌运行时检测服务实现
+//!
+//! 识别已安装应用中各自打包的 Electron、Chromium、CEF、NW.js 和 Qt 框架，
+//! 统计重复运行时占用的空间，并根据内置版本表标记过期的 Electron。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+use crate::models::runtime::{EmbeddedRuntime, RuntimeGroup, RuntimeReport};
+use crate::services::app_service::AppService;
+
+/// 框架名称与运行时类型的对应关系
+const RUNTIME_FRAMEWORKS: [(&str, &str); 8] = [
+    ("Electron Framework.framework", "electron"),
+    ("Chromium Embedded Framework.framework", "cef"),
+    ("nwjs Framework.framework", "nwjs"),
+    ("Chromium Framework.framework", "chromium"),
+    ("Google Chrome Framework.framework", "chromium"),
+    ("Microsoft Edge Framework.framework", "chromium"),
+    ("Brave Browser Framework.framework", "chromium"),
+    ("QtCore.framework", "qt"),
+];
+
+/// Electron 各主版本的首个稳定版发布日期
+///
+/// Electron 同时维护最新的三个主版本，主版本 N 在 N+3 发布时停止安全更新。
+/// 低于表中最小版本的均视为过期，新于表中最大版本的按 8 周发布周期推算发布日期。
+const ELECTRON_RELEASES: [(u32, &str); 20] = [
+    (20, "2022-08-02"),
+    (21, "2022-09-27"),
+    (22, "2022-11-29"),
+    (23, "2023-02-07"),
+    (24, "2023-04-04"),
+    (25, "2023-05-30"),
+    (26, "2023-08-15"),
+    (27, "2023-10-10"),
+    (28, "2023-12-05"),
+    (29, "2024-02-20"),
+    (30, "2024-04-16"),
+    (31, "2024-06-11"),
+    (32, "2024-08-20"),
+    (33, "2024-10-15"),
+    (34, "2025-01-14"),
+    (35, "2025-03-04"),
+    (36, "2025-04-29"),
+    (37, "2025-06-24"),
+    (38, "2025-09-02"),
+    (39, "2025-10-28"),
+];
+
+/// 同时受支持的 Electron 主版本数量
+const ELECTRON_SUPPORTED_MAJORS: u32 = 3;
+
+/// Electron 主版本发布周期（天）
+const ELECTRON_RELEASE_CADENCE_DAYS: i64 = 56;
+
+/// 将 YYYY-MM-DD 转换为 unix 天数
+fn parse_date(date: &str) -> Option<i64> {
+    // Howard Hinnant 的 days_from_civil 算法
+    let mut parts = date.split('-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
+
+/// 将 unix 天数转换为 YYYY-MM-DD
+fn format_date(days_since_epoch: i64) -> String {
+    // Howard Hinnant 的 civil_from_days 算法
+    let z = days_since_epoch + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// 今天的日期（YYYY-MM-DD）
+fn today() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    format_date(secs / 86_400)
+}
+
+/// Electron 主版本的发布日期，表中没有的较新版本按发布周期推算
+fn electron_release_date(major: u32) -> Option<String> {
+    if let Some((_, date)) = ELECTRON_RELEASES.iter().find(|(m, _)| *m == major) {
+        return Some(date.to_string());
+    }
+    let (newest, newest_date) = ELECTRON_RELEASES[ELECTRON_RELEASES.len() - 1];
+    let ahead = i64::from(major.checked_sub(newest)?);
+    Some(format_date(parse_date(newest_date)? + ahead * ELECTRON_RELEASE_CADENCE_DAYS))
+}
+
+/// 判断 Electron 版本是否已停止安全更新，返回说明
+pub fn electron_advisory(version: &str, today: &str) -> Option<String> {
+    let major: u32 = version.split('.').next()?.trim_start_matches('v').parse().ok()?;
+    let (oldest, _) = ELECTRON_RELEASES[0];
+
+    if major < oldest {
+        return Some(format!("Electron {} 已停止安全更新", major));
+    }
+
+    // 主版本 N 在 N+3 发布时停止支持
+    let end_of_life = electron_release_date(major + ELECTRON_SUPPORTED_MAJORS)?;
+    if end_of_life.as_str() <= today {
+        Some(format!("Electron {} 已于 {} 停止安全更新", major, end_of_life))
+    } else {
+        None
+    }
+}
+
+/// 内嵌运行时检测服务
+pub struct RuntimeService;
+
+impl RuntimeService {
+    /// 创建新的内嵌运行时检测服务实例
+    pub fn new() -> Self {
+        RuntimeService
+    }
+
+    /// 分析所有已安装应用中的内嵌运行时
+    pub fn analyze_installed_apps(&self) -> Result<RuntimeReport, String> {
+        let installed = AppService::new().get_installed_apps()?;
+        let app_paths: Vec<String> = installed.apps.into_iter().map(|a| a.path).collect();
+        Ok(self.analyze_apps(&app_paths))
+    }
+
+    /// 分析指定应用中的内嵌运行时
+    pub fn analyze_apps(&self, app_paths: &[String]) -> RuntimeReport {
+        let today = today();
+        let mut runtimes: Vec<EmbeddedRuntime> = app_paths.iter()
+            .flat_map(|p| self.detect_runtimes(Path::new(p), &today))
+            .collect();
+        runtimes.sort_by(|a, b| b.size.cmp(&a.size));
+
+        let mut grouped: BTreeMap<String, Vec<&EmbeddedRuntime>> = BTreeMap::new();
+        for runtime in &runtimes {
+            grouped.entry(runtime.runtime.clone()).or_default().push(runtime);
+        }
+
+        let mut groups: Vec<RuntimeGroup> = grouped.into_iter()
+            .map(|(runtime, copies)| {
+                let total_size: u64 = copies.iter().map(|r| r.size).sum();
+                let largest = copies.iter().map(|r| r.size).max().unwrap_or(0);
+                let mut versions: Vec<String> = copies.iter().map(|r| r.version.clone()).collect();
+                versions.sort();
+                versions.dedup();
+                RuntimeGroup {
+                    runtime,
+                    copies: copies.len() as u32,
+                    total_size,
+                    duplicated_size: total_size - largest,
+                    versions,
+                }
+            })
+            .collect();
+        groups.sort_by(|a, b| b.duplicated_size.cmp(&a.duplicated_size));
+
+        let total_duplicated_size = groups.iter().map(|g| g.duplicated_size).sum();
+        let outdated_count = runtimes.iter().filter(|r| r.is_outdated).count() as u32;
+
+        RuntimeReport { runtimes, groups, total_duplicated_size, outdated_count }
+    }
+
+    /// 检测单个应用中的内嵌运行时
+    fn detect_runtimes(&self, app_path: &Path, today: &str) -> Vec<EmbeddedRuntime> {
+        let frameworks_dir = app_path.join("Contents/Frameworks");
+        let app_name = app_path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let mut runtimes = Vec::new();
+        for (framework_name, runtime) in RUNTIME_FRAMEWORKS {
+            let framework_path = frameworks_dir.join(framework_name);
+            if !framework_path.is_dir() {
+                continue;
+            }
+
+            let version = read_framework_version(&framework_path).unwrap_or_default();
+            let size = if runtime == "qt" {
+                // Qt 拆分为多个 Qt*.framework，合并统计
+                qt_frameworks_size(&frameworks_dir)
+            } else {
+                dir_size(&framework_path)
+            };
+            let advisory = if runtime == "electron" {
+                electron_advisory(&version, today)
+            } else {
+                None
+            };
+
+            runtimes.push(EmbeddedRuntime {
+                app_path: app_path.to_string_lossy().to_string(),
+                app_name: app_name.clone(),
+                runtime: runtime.to_string(),
+                framework_name: framework_name.to_string(),
+                framework_path: framework_path.to_string_lossy().to_string(),
+                version,
+                size,
+                is_outdated: advisory.is_some(),
+                advisory,
+            });
+        }
+        runtimes
+    }
+}
+
+/// 读取框架 Info.plist 中的版本号
+fn read_framework_version(framework_path: &Path) -> Option<String> {
+    let candidates = [
+        framework_path.join("Resources/Info.plist"),
+        framework_path.join("Versions/Current/Resources/Info.plist"),
+        framework_path.join("Versions/A/Resources/Info.plist"),
+        framework_path.join("Versions/5/Resources/Info.plist"),
+    ];
+    let plist = candidates.iter().find(|p| p.exists())?;
+    let value = plist::Value::from_file(plist).ok()?;
+    let dict = value.as_dictionary()?;
+    // Electron 把自身版本写在 CFBundleVersion 中
+    dict.get("CFBundleVersion")
+        .or_else(|| dict.get("CFBundleShortVersionString"))
+        .and_then(|v| v.as_string())
+        .map(|s| s.to_string())
+}
+
+/// 计算目录大小（不跟随符号链接）
+fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// 计算所有 Qt*.framework 的总大小
+fn qt_frameworks_size(frameworks_dir: &Path) -> u64 {
+    fs::read_dir(frameworks_dir)
+        .map(|entries| {
+            entries.flatten()
+                .filter(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    name.starts_with("Qt") && name.ends_with(".framework")
+                })
+                .map(|e| dir_size(&e.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
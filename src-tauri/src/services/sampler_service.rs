@@ -0,0 +1,184 @@
+//! 系统后台采样服务实现
+//!
+//! 采样线程长期持有同一个 System，按设置中的自动刷新间隔刷新进程、CPU、内存和网络，
+//! sysinfo 以上一次刷新为基准计算 CPU 使用率，网速由前后两次采样的流量差计算。
+//! 进程列表、CPU、内存和网络命令直接读取最新一次采样结果，
+//! 使用记录和实例跟踪也通过 `latest_processes` 读取同一份进程列表。
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{Networks, ProcessRefreshKind, ProcessesToUpdate, System, Uid, UpdateKind, Users, MINIMUM_CPU_UPDATE_INTERVAL};
+use crate::models::process::Process;
+use crate::models::system::{CpuInfo, MemoryInfo, NetworkSpeed};
+use crate::services::process_service::to_process;
+use crate::services::system_service::SystemService;
+
+/// 最新一次采样结果（采样器启动后设置）
+static LATEST_SAMPLE: Mutex<Option<Arc<Mutex<SystemSample>>>> = Mutex::new(None);
+
+/// 最新一次采样的进程列表（采样器未启动时为空）
+pub fn latest_processes() -> Vec<Process> {
+    let latest = LATEST_SAMPLE.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    latest
+        .map(|sample| sample.lock().unwrap_or_else(|e| e.into_inner()).processes.clone())
+        .unwrap_or_default()
+}
+
+/// 一次采样结果
+#[derive(Debug, Clone)]
+pub struct SystemSample {
+    /// 进程列表（不含线程）
+    pub processes: Vec<Process>,
+    /// CPU 信息
+    pub cpu: CpuInfo,
+    /// 内存信息
+    pub memory: MemoryInfo,
+    /// 网络流量和网速（不含公网 IP 和 WiFi 信息）
+    pub network: NetworkSpeed,
+}
+
+/// 采样线程持有的状态
+struct SamplerState {
+    system: System,
+    networks: Networks,
+    users: Users,
+    /// 刷新用户列表后仍找不到的用户 ID，避免每次采样都重新刷新
+    unknown_uids: HashSet<Uid>,
+    /// 上一次采样的网络流量合计和时间
+    last_traffic: Option<(u64, u64, Instant)>,
+}
+
+impl SamplerState {
+    fn new() -> Self {
+        SamplerState {
+            system: System::new(),
+            networks: Networks::new_with_refreshed_list(),
+            users: Users::new_with_refreshed_list(),
+            unknown_uids: HashSet::new(),
+            last_traffic: None,
+        }
+    }
+
+    /// 刷新并生成采样结果
+    fn sample(&mut self) -> SystemSample {
+        self.system.refresh_cpu_all();
+        self.system.refresh_memory();
+        // 命令行、路径、用户等不常变化的字段只在首次出现时读取
+        let refresh_kind = ProcessRefreshKind::nothing()
+            .with_cpu()
+            .with_memory()
+            .with_cmd(UpdateKind::OnlyIfNotSet)
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_cwd(UpdateKind::OnlyIfNotSet)
+            .with_user(UpdateKind::OnlyIfNotSet);
+        self.system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+        self.networks.refresh(true);
+        self.refresh_users_if_needed();
+
+        // Linux 下线程也会作为进程列出，跳过
+        let processes = self.system.processes()
+            .iter()
+            .filter(|(_, process)| process.thread_kind().is_none())
+            .map(|(pid, process)| to_process(&self.system, &self.users, *pid, process))
+            .collect();
+
+        let mut network = SystemService::network_traffic(&self.networks);
+        let now = Instant::now();
+        if let Some((prev_rx, prev_tx, prev_time)) = self.last_traffic {
+            let elapsed = now.duration_since(prev_time).as_secs_f64();
+            if elapsed > 0.0 {
+                network.download_speed = (network.total_received.saturating_sub(prev_rx) as f64 / elapsed) as u64;
+                network.upload_speed = (network.total_transmitted.saturating_sub(prev_tx) as f64 / elapsed) as u64;
+            }
+        }
+        self.last_traffic = Some((network.total_received, network.total_transmitted, now));
+
+        SystemSample {
+            processes,
+            cpu: SystemService::cpu_info(&self.system),
+            memory: SystemService::memory_info(&self.system),
+            network,
+        }
+    }
+
+    /// 出现新的未知用户 ID 时才刷新用户列表
+    fn refresh_users_if_needed(&mut self) {
+        let has_new_uid = self.system.processes()
+            .values()
+            .filter_map(|process| process.user_id())
+            .any(|uid| self.users.get_user_by_id(uid).is_none() && !self.unknown_uids.contains(uid));
+        if !has_new_uid {
+            return;
+        }
+        self.users.refresh();
+        self.unknown_uids = self.system.processes()
+            .values()
+            .filter_map(|process| process.user_id())
+            .filter(|uid| self.users.get_user_by_id(uid).is_none())
+            .cloned()
+            .collect();
+    }
+}
+
+/// 后台采样器（作为 Tauri 状态注册，整个应用生命周期内只有一个）
+pub struct SystemSampler {
+    latest: Arc<Mutex<SystemSample>>,
+    interval_ms: Arc<AtomicU64>,
+}
+
+impl SystemSampler {
+    /// 立即采样一次，然后启动后台线程按间隔（毫秒）持续采样
+    pub fn start(interval_ms: u64) -> Self {
+        let mut state = SamplerState::new();
+        let latest = Arc::new(Mutex::new(state.sample()));
+        *LATEST_SAMPLE.lock().unwrap_or_else(|e| e.into_inner()) = Some(Arc::clone(&latest));
+        let interval_ms = Arc::new(AtomicU64::new(Self::clamp_interval(interval_ms)));
+
+        let thread_latest = Arc::clone(&latest);
+        let thread_interval = Arc::clone(&interval_ms);
+        thread::spawn(move || {
+            // 首次采样的 CPU 使用率为 0，尽快进行第二次采样
+            thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+            loop {
+                let sample = state.sample();
+                *thread_latest.lock().unwrap_or_else(|e| e.into_inner()) = sample;
+                thread::sleep(Duration::from_millis(thread_interval.load(Ordering::Relaxed)));
+            }
+        });
+
+        SystemSampler { latest, interval_ms }
+    }
+
+    /// 采样间隔不能小于 sysinfo 计算 CPU 使用率所需的最小间隔
+    fn clamp_interval(interval_ms: u64) -> u64 {
+        interval_ms.max(MINIMUM_CPU_UPDATE_INTERVAL.as_millis() as u64)
+    }
+
+    /// 修改采样间隔（毫秒），下一轮采样生效
+    pub fn set_interval(&self, interval_ms: u64) {
+        self.interval_ms.store(Self::clamp_interval(interval_ms), Ordering::Relaxed);
+    }
+
+    /// 最新的进程列表
+    pub fn processes(&self) -> Vec<Process> {
+        self.latest.lock().unwrap_or_else(|e| e.into_inner()).processes.clone()
+    }
+
+    /// 最新的 CPU 信息
+    pub fn cpu(&self) -> CpuInfo {
+        self.latest.lock().unwrap_or_else(|e| e.into_inner()).cpu.clone()
+    }
+
+    /// 最新的内存信息
+    pub fn memory(&self) -> MemoryInfo {
+        self.latest.lock().unwrap_or_else(|e| e.into_inner()).memory.clone()
+    }
+
+    /// 最新的网络流量和网速
+    pub fn network(&self) -> NetworkSpeed {
+        self.latest.lock().unwrap_or_else(|e| e.into_inner()).network.clone()
+    }
+}
@@ -5,16 +5,10 @@ use std::process::Command;
 use std::sync::Mutex;
 use crate::models::system::{SystemInfo, CpuInfo, MemoryInfo, Disk, DiskInfo, BatteryInfo, NetworkInterface, NetworkSpeed, GpuInfo};
 
-/// 网络状态缓存（用于计算网速）
-static NETWORK_CACHE: Mutex<Option<(u64, u64, std::time::Instant)>> = Mutex::new(None);
-
 /// 公网 IP 缓存（IP, 国家代码, 获取时间）- 缓存 60 秒
 static PUBLIC_IP_CACHE: Mutex<Option<(String, String, std::time::Instant)>> = Mutex::new(None);
 const PUBLIC_IP_CACHE_DURATION: u64 = 60; // 缓存有效期 60 秒
 
-/// CPU System 对象缓存（保持状态以计算使用率）
-static CPU_SYSTEM: Mutex<Option<System>> = Mutex::new(None);
-
 /// GPU 名称缓存
 static GPU_NAME_CACHE: Mutex<Option<String>> = Mutex::new(None);
 
@@ -41,42 +35,23 @@ impl SystemService {
         }
     }
 
-    /// 获取CPU信息
-    pub fn get_cpu_info(&self) -> CpuInfo {
-        // 使用缓存的 System 对象来计算 CPU 使用率
-        let mut system_guard = CPU_SYSTEM.lock().unwrap();
-        
-        if system_guard.is_none() {
-            let mut sys = System::new_all();
-            sys.refresh_all();
-            *system_guard = Some(sys);
-        }
-        
-        let system = system_guard.as_mut().unwrap();
-        
-        // 等待一小段时间后再刷新，以获取准确的 CPU 使用率
-        std::thread::sleep(std::time::Duration::from_millis(200));
-        system.refresh_cpu_all();
-        
-        let cpu = system.cpus().first().unwrap();
-        let cpu_usage = system.global_cpu_usage();
+    /// 根据已刷新的 System 生成 CPU 信息（使用率需要两次间隔刷新才有意义）
+    pub fn cpu_info(system: &System) -> CpuInfo {
+        let cpu = system.cpus().first();
         
         CpuInfo {
-            name: cpu.name().to_string(),
-            vendor_id: cpu.vendor_id().to_string(),
-            brand: cpu.brand().to_string(),
-            frequency: cpu.frequency(),
-            cpu_usage: cpu_usage / 100.0,
+            name: cpu.map(|c| c.name().to_string()).unwrap_or_default(),
+            vendor_id: cpu.map(|c| c.vendor_id().to_string()).unwrap_or_default(),
+            brand: cpu.map(|c| c.brand().to_string()).unwrap_or_default(),
+            frequency: cpu.map(|c| c.frequency()).unwrap_or(0),
+            cpu_usage: system.global_cpu_usage() / 100.0,
             physical_cores: sysinfo::System::physical_core_count().unwrap_or(0),
             logical_cores: system.cpus().len(),
         }
     }
 
-    /// 获取内存信息
-    pub fn get_memory_info(&self) -> MemoryInfo {
-        let mut system = System::new_all();
-        system.refresh_all();
-
+    /// 根据已刷新的 System 生成内存信息
+    pub fn memory_info(system: &System) -> MemoryInfo {
         let total_mem = system.total_memory();
         let used_mem = system.used_memory();
         let free_mem = system.free_memory();
@@ -442,17 +417,15 @@ impl SystemService {
         info
     }
 
-    /// 获取网络速度信息
-    pub fn get_network_speed(&self) -> NetworkSpeed {
-        let networks = Networks::new_with_refreshed_list();
-        
+    /// 根据已刷新的网络接口生成流量信息（网速由调用方按前后两次采样计算，公网 IP 和 WiFi 信息为空）
+    pub fn network_traffic(networks: &Networks) -> NetworkSpeed {
         let mut total_received: u64 = 0;
         let mut total_transmitted: u64 = 0;
         let mut interfaces = Vec::new();
         let mut local_ip = String::new();
         let mut local_ipv6 = String::new();
 
-        for (name, data) in &networks {
+        for (name, data) in networks {
             let received = data.total_received();
             let transmitted = data.total_transmitted();
             
@@ -498,49 +471,35 @@ impl SystemService {
             });
         }
 
-        // 计算网速
-        let (download_speed, upload_speed) = {
-            let mut cache = NETWORK_CACHE.lock().unwrap();
-            let now = std::time::Instant::now();
-            
-            if let Some((prev_rx, prev_tx, prev_time)) = cache.take() {
-                let elapsed = now.duration_since(prev_time).as_secs_f64();
-                if elapsed > 0.0 {
-                    let dl = ((total_received.saturating_sub(prev_rx)) as f64 / elapsed) as u64;
-                    let ul = ((total_transmitted.saturating_sub(prev_tx)) as f64 / elapsed) as u64;
-                    *cache = Some((total_received, total_transmitted, now));
-                    (dl, ul)
-                } else {
-                    *cache = Some((total_received, total_transmitted, now));
-                    (0, 0)
-                }
-            } else {
-                *cache = Some((total_received, total_transmitted, now));
-                (0, 0)
-            }
-        };
-
-        // 获取公网 IP 和国家代码
-        let (public_ip, country_code) = self.get_public_ip();
-        
-        // 获取 WiFi SSID 和网络类型
-        let (wifi_ssid, network_type) = self.get_wifi_info();
-
         NetworkSpeed {
-            download_speed,
-            upload_speed,
+            download_speed: 0,
+            upload_speed: 0,
             total_received,
             total_transmitted,
             local_ip,
             local_ipv6,
-            public_ip,
-            country_code,
-            wifi_ssid,
-            network_type,
+            public_ip: String::new(),
+            country_code: String::new(),
+            wifi_ssid: String::new(),
+            network_type: String::new(),
             interfaces,
         }
     }
 
+    /// 补充公网 IP、国家代码、WiFi SSID 和网络类型
+    pub fn add_network_details(&self, speed: &mut NetworkSpeed) {
+        // 获取公网 IP 和国家代码
+        let (public_ip, country_code) = self.get_public_ip();
+        
+        // 获取 WiFi SSID 和网络类型
+        let (wifi_ssid, network_type) = self.get_wifi_info();
+
+        speed.public_ip = public_ip;
+        speed.country_code = country_code;
+        speed.wifi_ssid = wifi_ssid;
+        speed.network_type = network_type;
+    }
+
     /// 获取接口类型
     fn get_interface_type(name: &str) -> String {
         let lower = name.to_lowercase();
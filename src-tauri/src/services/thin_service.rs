@@ -0,0 +1,653 @@
+//! 通用二进制瘦身服务实现
+//!
+//! 从用户选择的应用中移除非原生架构切片，瘦身前备份原始二进制以便还原。
+//! 需要重新签名的应用会改写嵌套代码和签名目录，这类应用整体备份应用包，还原时整体替换。
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+use crate::models::thin::{AppThinPlan, ThinBackup, ThinBinary, ThinPlan, ThinResult};
+use crate::services::bundle_rewrite_service::{copy_bundle, AdHocCodeSigner, CodeSigner};
+use crate::services::macho_service::{self, MachOFile, MachOSlice};
+
+/// 备份清单文件名
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// 整体备份的应用包目录名
+const BUNDLE_DIR: &str = "bundle";
+
+/// 从 fat 二进制中选出要保留的切片
+fn select_slice<'a>(macho: &'a MachOFile, target_arch: &str) -> Option<&'a MachOSlice> {
+    macho.slices.iter()
+        .find(|s| s.arch_name() == target_arch)
+        .or_else(|| macho.slices.iter().find(|s| s.arch_name().starts_with(target_arch)))
+}
+
+/// 提取 fat 二进制中指定架构的切片，返回瘦身后的文件内容
+pub fn thin_macho_bytes(data: &[u8], target_arch: &str) -> Option<Vec<u8>> {
+    let macho = macho_service::parse_macho_bytes(data)?;
+    if !macho.is_fat || macho.slices.len() < 2 {
+        return None;
+    }
+    let slice = select_slice(&macho, target_arch)?;
+    let start = usize::try_from(slice.offset).ok()?;
+    let end = start.checked_add(usize::try_from(slice.size).ok()?)?;
+    data.get(start..end).map(|s| s.to_vec())
+}
+
+/// 通用二进制瘦身服务
+pub struct ThinService {
+    backup_root: PathBuf,
+    signer: Box<dyn CodeSigner>,
+}
+
+impl ThinService {
+    /// 创建新的瘦身服务实例
+    pub fn new() -> Self {
+        let backup_root = dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("com.mole.app")
+            .join("thin_backups");
+        Self::with_backup_root(backup_root)
+    }
+
+    /// 使用指定备份目录创建实例
+    pub fn with_backup_root(backup_root: impl Into<PathBuf>) -> Self {
+        ThinService {
+            backup_root: backup_root.into(),
+            signer: Box::new(AdHocCodeSigner),
+        }
+    }
+
+    /// 预览瘦身计划（不修改任何文件）
+    ///
+    /// 已签名的应用瘦身后签名失效，只有 `allow_resign` 为 true 时才允许瘦身并在之后临时签名
+    pub fn plan_thinning(&self, app_paths: &[String], target_arch: Option<&str>, allow_resign: bool) -> ThinPlan {
+        let target_arch = target_arch.unwrap_or_else(|| macho_service::native_arch()).to_string();
+
+        let apps: Vec<AppThinPlan> = app_paths.iter()
+            .map(|p| self.plan_app(Path::new(p), &target_arch, allow_resign))
+            .collect();
+        let total_reclaim_bytes = apps.iter()
+            .filter(|a| a.refused_reason.is_none())
+            .map(|a| a.reclaim_bytes)
+            .sum();
+
+        ThinPlan { target_arch, apps, total_reclaim_bytes }
+    }
+
+    /// 生成单个应用的瘦身计划
+    fn plan_app(&self, app_path: &Path, target_arch: &str, allow_resign: bool) -> AppThinPlan {
+        let app_name = app_path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+        let identifier = read_bundle_identifier(app_path).unwrap_or_default();
+
+        let mut plan = AppThinPlan {
+            app_path: app_path.to_string_lossy().to_string(),
+            app_name,
+            identifier,
+            binaries: Vec::new(),
+            reclaim_bytes: 0,
+            needs_resign: false,
+            refused_reason: None,
+        };
+
+        if !app_path.join("Contents/Info.plist").exists() {
+            plan.refused_reason = Some("不是有效的应用包".to_string());
+            return plan;
+        }
+        if app_path.starts_with("/System") || plan.identifier.starts_with("com.apple.") {
+            plan.refused_reason = Some("系统应用受签名保护，不允许瘦身".to_string());
+            return plan;
+        }
+
+        let mut has_signature = app_path.join("Contents/_CodeSignature").exists();
+
+        for entry in WalkDir::new(app_path).follow_links(false).into_iter().flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Some(macho) = macho_service::read_macho(entry.path()) else {
+                continue;
+            };
+            if !macho.is_fat || macho.slices.len() < 2 {
+                continue;
+            }
+            let Some(slice) = select_slice(&macho, target_arch) else {
+                continue;
+            };
+
+            has_signature |= macho.slices.iter().any(|s| s.code_signature.is_some());
+            let original_size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            plan.binaries.push(ThinBinary {
+                path: entry.path().to_string_lossy().to_string(),
+                architectures: macho.architectures(),
+                keep_arch: slice.arch_name(),
+                original_size,
+                thinned_size: slice.size,
+            });
+        }
+
+        plan.reclaim_bytes = plan.binaries.iter()
+            .map(|b| b.original_size.saturating_sub(b.thinned_size))
+            .sum();
+        plan.needs_resign = has_signature && !plan.binaries.is_empty();
+
+        if plan.binaries.is_empty() {
+            plan.refused_reason = Some(format!("没有包含 {} 的通用二进制可瘦身", target_arch));
+        } else if plan.needs_resign && !allow_resign {
+            plan.refused_reason = Some("应用已签名，瘦身会使签名失效，需允许临时重新签名".to_string());
+        }
+
+        plan
+    }
+
+    /// 执行瘦身（先备份原始二进制，已签名的应用瘦身后临时重新签名）
+    pub fn thin_apps(&self, app_paths: &[String], target_arch: Option<&str>, allow_resign: bool) -> Vec<ThinResult> {
+        let plan = self.plan_thinning(app_paths, target_arch, allow_resign);
+        plan.apps.iter()
+            .map(|app| self.thin_app(app, &plan.target_arch))
+            .collect()
+    }
+
+    /// 对单个应用执行瘦身
+    fn thin_app(&self, plan: &AppThinPlan, target_arch: &str) -> ThinResult {
+        let mut result = ThinResult {
+            app_path: plan.app_path.clone(),
+            success: false,
+            message: String::new(),
+            backup_id: String::new(),
+            reclaimed_bytes: 0,
+            thinned_files: Vec::new(),
+            failed_items: Vec::new(),
+            needs_resign: plan.needs_resign,
+        };
+
+        if let Some(reason) = &plan.refused_reason {
+            result.message = reason.clone();
+            return result;
+        }
+
+        let app_path = Path::new(&plan.app_path);
+        let backup = match self.create_backup(plan, target_arch) {
+            Ok(backup) => backup,
+            Err(e) => {
+                result.message = e;
+                return result;
+            }
+        };
+        result.backup_id = backup.id.clone();
+
+        for binary in &plan.binaries {
+            let path = Path::new(&binary.path);
+            match self.thin_file(path, target_arch) {
+                Ok(saved) => {
+                    result.reclaimed_bytes += saved;
+                    result.thinned_files.push(binary.path.clone());
+                }
+                Err(e) => result.failed_items.push(format!("{}: {}", binary.path, e)),
+            }
+        }
+
+        // 签名失败时还原整个应用包，避免留下签名不完整、无法启动的应用
+        if plan.needs_resign && !result.thinned_files.is_empty() {
+            if let Err(e) = self.signer.sign(app_path) {
+                result.reclaimed_bytes = 0;
+                result.thinned_files.clear();
+                result.message = match self.restore_backup(&backup.id) {
+                    Ok(restored) if restored.success => format!("重新签名失败，已还原原始应用: {}", e),
+                    Ok(restored) => format!("重新签名失败: {}; 还原失败（备份 {}）: {}", e, backup.id, restored.message),
+                    Err(restore_error) => format!("重新签名失败: {}; 还原失败（备份 {}）: {}", e, backup.id, restore_error),
+                };
+                return result;
+            }
+            result.needs_resign = false;
+        }
+
+        result.success = !result.thinned_files.is_empty();
+        result.message = if result.failed_items.is_empty() {
+            format!("{} 瘦身完成", app_path.file_stem().unwrap_or_default().to_string_lossy())
+        } else {
+            format!("部分文件瘦身失败: {:?}", result.failed_items)
+        };
+        result
+    }
+
+    /// 将单个 fat 二进制替换为目标架构切片，返回节省的字节数
+    fn thin_file(&self, path: &Path, target_arch: &str) -> Result<u64, String> {
+        let data = fs::read(path).map_err(|e| format!("读取失败: {}", e))?;
+        let thinned = thin_macho_bytes(&data, target_arch)
+            .ok_or_else(|| "不包含目标架构切片".to_string())?;
+        let permissions = fs::metadata(path).map_err(|e| e.to_string())?.permissions();
+
+        // 先写入临时文件再替换，避免写入中断导致二进制损坏
+        let tmp_path = path.with_extension("mole-thin");
+        fs::write(&tmp_path, &thinned).map_err(|e| format!("写入失败: {}", e))?;
+        fs::set_permissions(&tmp_path, permissions).map_err(|e| e.to_string())?;
+        if let Err(e) = fs::rename(&tmp_path, path) {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(format!("替换失败: {}", e));
+        }
+
+        Ok((data.len() - thinned.len()) as u64)
+    }
+
+    /// 创建瘦身备份：需要重新签名的应用备份整个应用包，其余只备份计划中的二进制文件
+    fn create_backup(&self, plan: &AppThinPlan, target_arch: &str) -> Result<ThinBackup, String> {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let (id, backup_dir) = self.create_backup_dir(&plan.app_name, created_at)?;
+
+        let app_path = Path::new(&plan.app_path);
+        let mut files = Vec::new();
+        for binary in &plan.binaries {
+            let relative = Path::new(&binary.path).strip_prefix(app_path).map_err(|e| e.to_string())?;
+            files.push(relative.to_string_lossy().to_string());
+        }
+
+        let total_size = if plan.needs_resign {
+            let bundle_dir = backup_dir.join(BUNDLE_DIR);
+            copy_bundle(app_path, &bundle_dir).map_err(|e| format!("备份失败: {}", e))?;
+            WalkDir::new(&bundle_dir)
+                .into_iter()
+                .flatten()
+                .filter(|e| e.file_type().is_file())
+                .filter_map(|e| e.metadata().ok())
+                .map(|m| m.len())
+                .sum()
+        } else {
+            self.backup_files(app_path, &files, &backup_dir.join("files"))?
+        };
+
+        let backup = ThinBackup {
+            id,
+            app_path: plan.app_path.clone(),
+            target_arch: target_arch.to_string(),
+            created_at,
+            files,
+            total_size,
+            full_bundle: plan.needs_resign,
+        };
+        let manifest = serde_json::to_string_pretty(&backup)
+            .map_err(|e| format!("无法序列化备份清单: {}", e))?;
+        fs::write(backup_dir.join(MANIFEST_FILE), manifest)
+            .map_err(|e| format!("无法保存备份清单: {}", e))?;
+
+        Ok(backup)
+    }
+
+    /// 备份应用包内的指定文件，返回备份总大小
+    fn backup_files(&self, app_path: &Path, files: &[String], files_dir: &Path) -> Result<u64, String> {
+        let mut total_size = 0u64;
+        for relative in files {
+            let dest = files_dir.join(relative);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("无法创建备份目录: {}", e))?;
+            }
+            total_size += fs::copy(app_path.join(relative), &dest).map_err(|e| format!("备份失败: {}", e))?;
+        }
+        Ok(total_size)
+    }
+
+    /// 创建新的备份目录，同一秒内的多次备份以序号区分
+    fn create_backup_dir(&self, app_name: &str, created_at: u64) -> Result<(String, PathBuf), String> {
+        fs::create_dir_all(&self.backup_root).map_err(|e| format!("无法创建备份目录: {}", e))?;
+        let base = format!("{}-{}", app_name.replace(' ', "_"), created_at);
+        for n in 0u32.. {
+            let id = if n == 0 { base.clone() } else { format!("{}-{}", base, n) };
+            let dir = self.backup_root.join(&id);
+            match fs::create_dir(&dir) {
+                Ok(()) => return Ok((id, dir)),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(format!("无法创建备份目录: {}", e)),
+            }
+        }
+        Err("无法创建备份目录".to_string())
+    }
+
+    /// 获取所有瘦身备份
+    pub fn list_backups(&self) -> Vec<ThinBackup> {
+        let mut backups: Vec<ThinBackup> = fs::read_dir(&self.backup_root)
+            .map(|entries| {
+                entries.flatten()
+                    .filter_map(|e| fs::read_to_string(e.path().join(MANIFEST_FILE)).ok())
+                    .filter_map(|content| serde_json::from_str(&content).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        backups
+    }
+
+    /// 从备份还原原始二进制（整体备份的应用包整体替换）
+    pub fn restore_backup(&self, backup_id: &str) -> Result<ThinResult, String> {
+        let backup = self.read_backup(backup_id)?;
+        let app_path = Path::new(&backup.app_path);
+        if !app_path.exists() {
+            return Err("应用不存在".to_string());
+        }
+        if backup.full_bundle {
+            return self.restore_bundle(&backup);
+        }
+
+        let files_dir = self.backup_root.join(backup_id).join("files");
+        let mut restored = Vec::new();
+        let mut failed_items = Vec::new();
+
+        for relative in &backup.files {
+            let source = files_dir.join(relative);
+            let dest = app_path.join(relative);
+            match fs::copy(&source, &dest) {
+                Ok(_) => restored.push(dest.to_string_lossy().to_string()),
+                Err(e) => failed_items.push(format!("{}: {}", relative, e)),
+            }
+        }
+
+        Ok(ThinResult {
+            app_path: backup.app_path.clone(),
+            success: failed_items.is_empty(),
+            message: if failed_items.is_empty() {
+                "已还原原始二进制".to_string()
+            } else {
+                format!("部分文件还原失败: {:?}", failed_items)
+            },
+            backup_id: backup.id.clone(),
+            reclaimed_bytes: 0,
+            thinned_files: restored,
+            failed_items,
+            needs_resign: false,
+        })
+    }
+
+    /// 用备份的应用包替换当前应用包（先复制到同目录的临时位置，再交换）
+    fn restore_bundle(&self, backup: &ThinBackup) -> Result<ThinResult, String> {
+        let app_path = Path::new(&backup.app_path);
+        let file_name = app_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let staging = app_path.with_file_name(format!(".{}.mole-restore", file_name));
+        let previous = app_path.with_file_name(format!(".{}.mole-thinned", file_name));
+        let _ = fs::remove_dir_all(&staging);
+        let _ = fs::remove_dir_all(&previous);
+
+        let source = self.backup_root.join(&backup.id).join(BUNDLE_DIR);
+        if let Err(e) = copy_bundle(&source, &staging) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(format!("还原失败: {}", e));
+        }
+        if let Err(e) = fs::rename(app_path, &previous) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(format!("无法替换应用: {}", e));
+        }
+        if let Err(e) = fs::rename(&staging, app_path) {
+            let _ = fs::remove_dir_all(&staging);
+            if let Err(rollback) = fs::rename(&previous, app_path) {
+                return Err(format!(
+                    "无法替换应用: {}; 当前应用保留在 {}: {}",
+                    e, previous.to_string_lossy(), rollback
+                ));
+            }
+            return Err(format!("无法替换应用: {}", e));
+        }
+        let _ = fs::remove_dir_all(&previous);
+
+        Ok(ThinResult {
+            app_path: backup.app_path.clone(),
+            success: true,
+            message: "已还原原始应用".to_string(),
+            backup_id: backup.id.clone(),
+            reclaimed_bytes: 0,
+            thinned_files: backup.files.iter()
+                .map(|relative| app_path.join(relative).to_string_lossy().to_string())
+                .collect(),
+            failed_items: Vec::new(),
+            needs_resign: false,
+        })
+    }
+
+    /// 删除瘦身备份
+    pub fn delete_backup(&self, backup_id: &str) -> Result<bool, String> {
+        // 确认是有效备份，避免误删备份目录外的路径
+        self.read_backup(backup_id)?;
+        fs::remove_dir_all(self.backup_root.join(backup_id))
+            .map(|_| true)
+            .map_err(|e| format!("无法删除备份: {}", e))
+    }
+
+    /// 读取备份清单
+    fn read_backup(&self, backup_id: &str) -> Result<ThinBackup, String> {
+        if backup_id.is_empty() || backup_id.contains('/') || backup_id.contains("..") {
+            return Err("无效的备份 ID".to_string());
+        }
+        let content = fs::read_to_string(self.backup_root.join(backup_id).join(MANIFEST_FILE))
+            .map_err(|_| "备份不存在".to_string())?;
+        serde_json::from_str(&content).map_err(|e| format!("备份清单损坏: {}", e))
+    }
+}
+
+/// 读取应用包的 CFBundleIdentifier
+fn read_bundle_identifier(app_path: &Path) -> Option<String> {
+    plist::Value::from_file(app_path.join("Contents/Info.plist"))
+        .ok()?
+        .as_dictionary()?
+        .get("CFBundleIdentifier")?
+        .as_string()
+        .map(|s| s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    const X86_64: u32 = 0x0100_0007;
+    const ARM64: u32 = 0x0100_000c;
+
+    /// 记录调用的签名器：改写签名目录，`fail` 为 true 时改写一半后失败
+    struct FakeSigner {
+        calls: Arc<Mutex<Vec<PathBuf>>>,
+        fail: bool,
+    }
+
+    impl CodeSigner for FakeSigner {
+        fn sign(&self, bundle: &Path) -> Result<(), String> {
+            self.calls.lock().unwrap().push(bundle.to_path_buf());
+            let signature = bundle.join("Contents/_CodeSignature");
+            fs::write(signature.join("CodeResources"), b"resigned").unwrap();
+            if self.fail {
+                return Err("fake signer failed".to_string());
+            }
+            fs::write(signature.join("CodeRequirements"), b"resigned").unwrap();
+            Ok(())
+        }
+    }
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-thin-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn service(dir: &Path, fail: bool) -> (ThinService, Arc<Mutex<Vec<PathBuf>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let service = ThinService {
+            backup_root: dir.join("backups"),
+            signer: Box::new(FakeSigner { calls: Arc::clone(&calls), fail }),
+        };
+        (service, calls)
+    }
+
+    /// 4096 字节的单架构 Mach-O
+    fn thin(cpu_type: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        for v in [0xfeedfacfu32, cpu_type, 0, 2, 0, 0, 0, 0] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        data.resize(4096, cpu_type as u8);
+        data
+    }
+
+    /// x86_64 和 arm64 两个切片的 fat 二进制（12288 字节）
+    fn fat() -> Vec<u8> {
+        let mut data = Vec::new();
+        for v in [0xcafebabeu32, 2] {
+            data.extend_from_slice(&v.to_be_bytes());
+        }
+        for (cpu_type, offset) in [(X86_64, 4096u32), (ARM64, 8192)] {
+            for v in [cpu_type, 3, offset, 4096, 12] {
+                data.extend_from_slice(&v.to_be_bytes());
+            }
+        }
+        data.resize(4096, 0);
+        data.extend_from_slice(&thin(X86_64));
+        data.extend_from_slice(&thin(ARM64));
+        data
+    }
+
+    /// 构造包含 fat 主程序、fat 框架和单架构辅助程序的应用，`signed` 时带签名目录
+    fn build_app(dir: &Path, name: &str, identifier: &str, signed: bool) -> PathBuf {
+        let app = dir.join(format!("{}.app", name));
+        fs::create_dir_all(app.join("Contents/MacOS")).unwrap();
+        fs::create_dir_all(app.join("Contents/Frameworks/Kit.framework/Versions/A")).unwrap();
+        let mut info = plist::Dictionary::new();
+        info.insert("CFBundleIdentifier".to_string(), identifier.into());
+        plist::Value::Dictionary(info).to_file_xml(app.join("Contents/Info.plist")).unwrap();
+        fs::write(app.join("Contents/MacOS").join(name), fat()).unwrap();
+        fs::write(app.join("Contents/MacOS/helper"), thin(ARM64)).unwrap();
+        fs::write(app.join("Contents/Frameworks/Kit.framework/Versions/A/Kit"), fat()).unwrap();
+        if signed {
+            fs::create_dir_all(app.join("Contents/_CodeSignature")).unwrap();
+            fs::write(app.join("Contents/_CodeSignature/CodeResources"), b"original").unwrap();
+        }
+        app
+    }
+
+    fn paths(app: &Path) -> Vec<String> {
+        vec![app.to_string_lossy().to_string()]
+    }
+
+    #[test]
+    fn extracts_target_slice_from_fat_binary() {
+        assert_eq!(thin_macho_bytes(&fat(), "arm64"), Some(thin(ARM64)));
+        assert_eq!(thin_macho_bytes(&fat(), "x86_64"), Some(thin(X86_64)));
+        assert_eq!(thin_macho_bytes(&fat(), "ppc"), None);
+        assert_eq!(thin_macho_bytes(&thin(ARM64), "arm64"), None);
+    }
+
+    #[test]
+    fn plans_fat_binaries_and_reclaimed_bytes() {
+        let dir = fixture_dir("plan");
+        let app = build_app(&dir, "Chat", "com.example.chat", false);
+        let (service, _) = service(&dir, false);
+        let plan = service.plan_thinning(&paths(&app), Some("arm64"), false);
+
+        assert_eq!(plan.target_arch, "arm64");
+        let app_plan = &plan.apps[0];
+        assert!(app_plan.refused_reason.is_none(), "{:?}", app_plan.refused_reason);
+        assert!(!app_plan.needs_resign);
+        let mut binaries: Vec<&str> = app_plan.binaries.iter()
+            .map(|b| b.path.strip_prefix(&*app.to_string_lossy()).unwrap())
+            .collect();
+        binaries.sort();
+        assert_eq!(binaries, vec!["/Contents/Frameworks/Kit.framework/Versions/A/Kit", "/Contents/MacOS/Chat"]);
+        assert!(app_plan.binaries.iter().all(|b| b.keep_arch == "arm64" && b.original_size == 12288 && b.thinned_size == 4096));
+        assert_eq!(app_plan.reclaim_bytes, 2 * 8192);
+        assert_eq!(plan.total_reclaim_bytes, 2 * 8192);
+
+        let plan = service.plan_thinning(&paths(&app), Some("ppc"), false);
+        assert!(plan.apps[0].refused_reason.is_some());
+        assert_eq!(plan.total_reclaim_bytes, 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_system_apps_and_signed_apps_without_opt_in() {
+        let dir = fixture_dir("refuse");
+        let system = build_app(&dir, "Safari", "com.apple.Safari", true);
+        let signed = build_app(&dir, "Chat", "com.example.chat", true);
+        let (service, _) = service(&dir, false);
+
+        let plan = service.plan_thinning(&paths(&system), Some("arm64"), true);
+        assert!(plan.apps[0].refused_reason.is_some());
+        assert_eq!(plan.total_reclaim_bytes, 0);
+
+        let plan = service.plan_thinning(&paths(&signed), Some("arm64"), false);
+        assert!(plan.apps[0].needs_resign);
+        assert!(plan.apps[0].refused_reason.is_some());
+        assert_eq!(plan.total_reclaim_bytes, 0);
+        let result = service.thin_apps(&paths(&signed), Some("arm64"), false);
+        assert!(!result[0].success);
+        assert_eq!(fs::read(signed.join("Contents/MacOS/Chat")).unwrap(), fat());
+
+        let plan = service.plan_thinning(&paths(&signed), Some("arm64"), true);
+        assert!(plan.apps[0].refused_reason.is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn thins_and_restores_unsigned_binaries() {
+        let dir = fixture_dir("unsigned");
+        let app = build_app(&dir, "Chat", "com.example.chat", false);
+        let (service, calls) = service(&dir, false);
+        let result = service.thin_apps(&paths(&app), Some("arm64"), false);
+
+        assert!(result[0].success, "{:?}", result);
+        assert_eq!(result[0].reclaimed_bytes, 2 * 8192);
+        assert!(calls.lock().unwrap().is_empty());
+        assert_eq!(fs::read(app.join("Contents/MacOS/Chat")).unwrap(), thin(ARM64));
+
+        let backups = service.list_backups();
+        assert_eq!(backups.len(), 1);
+        assert!(!backups[0].full_bundle);
+        assert_eq!(backups[0].total_size, 2 * 12288);
+        let restored = service.restore_backup(&backups[0].id).unwrap();
+        assert!(restored.success, "{:?}", restored);
+        assert_eq!(fs::read(app.join("Contents/MacOS/Chat")).unwrap(), fat());
+        assert_eq!(fs::read(app.join("Contents/Frameworks/Kit.framework/Versions/A/Kit")).unwrap(), fat());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn restores_whole_bundle_including_signatures() {
+        let dir = fixture_dir("signed");
+        let app = build_app(&dir, "Chat", "com.example.chat", true);
+        let (service, calls) = service(&dir, false);
+        let result = service.thin_apps(&paths(&app), Some("arm64"), true);
+
+        assert!(result[0].success, "{:?}", result);
+        assert!(!result[0].needs_resign);
+        assert_eq!(*calls.lock().unwrap(), vec![app.clone()]);
+        assert_eq!(fs::read(app.join("Contents/_CodeSignature/CodeResources")).unwrap(), b"resigned");
+
+        let backup = &service.list_backups()[0];
+        assert!(backup.full_bundle);
+        service.restore_backup(&backup.id).unwrap();
+        assert_eq!(fs::read(app.join("Contents/MacOS/Chat")).unwrap(), fat());
+        assert_eq!(fs::read(app.join("Contents/_CodeSignature/CodeResources")).unwrap(), b"original");
+        assert!(!app.join("Contents/_CodeSignature/CodeRequirements").exists());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2, "临时目录未清理");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn restores_original_bundle_when_resign_fails() {
+        let dir = fixture_dir("sign-fail");
+        let app = build_app(&dir, "Chat", "com.example.chat", true);
+        let (service, _) = service(&dir, true);
+        let first = service.thin_apps(&paths(&app), Some("arm64"), true);
+
+        assert!(!first[0].success);
+        assert!(first[0].message.contains("已还原原始应用"), "{}", first[0].message);
+        assert_eq!(first[0].reclaimed_bytes, 0);
+        assert_eq!(fs::read(app.join("Contents/MacOS/Chat")).unwrap(), fat());
+        assert_eq!(fs::read(app.join("Contents/_CodeSignature/CodeResources")).unwrap(), b"original");
+
+        let second = service.thin_apps(&paths(&app), Some("arm64"), true);
+        assert_ne!(first[0].backup_id, second[0].backup_id);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
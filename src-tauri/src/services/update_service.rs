@@ -0,0 +1,619 @@
+//! 应用更新检查服务实现
+//!
+//! 读取应用 Info.plist 中的 SUFeedURL，下载并解析 Sparkle appcast，
+//! 按 Sparkle 的版本比较规则判断是否有新版本。appcast 在内存中缓存一段时间，
+//! HTTP 请求通过 `HttpClient` 完成，默认使用系统 curl。
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use crate::models::updates::{AppUpdateInfo, AppcastDelta, AppcastItem, UpdateCheckReport};
+use crate::services::app_service::app_bundle_paths;
+
+/// appcast 缓存: feed 地址 -> (获取时间, 版本列表)
+type AppcastCache = HashMap<String, (Instant, Vec<AppcastItem>)>;
+static APPCAST_CACHE: Mutex<Option<AppcastCache>> = Mutex::new(None);
+
+/// appcast 缓存有效期
+const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// 同时检查的应用数量（无法访问的 feed 各自等待超时，不能逐个串行）
+const MAX_PARALLEL_CHECKS: usize = 8;
+
+/// HTTP 客户端
+pub trait HttpClient: Send + Sync {
+    /// 获取地址对应的响应正文
+    fn get(&self, url: &str) -> Result<String, String>;
+}
+
+/// 使用系统 curl 的 HTTP 客户端
+pub struct CurlHttpClient {
+    timeout_secs: u32,
+}
+
+impl CurlHttpClient {
+    /// 创建新的 curl 客户端
+    pub fn new(timeout_secs: u32) -> Self {
+        CurlHttpClient { timeout_secs }
+    }
+}
+
+impl HttpClient for CurlHttpClient {
+    fn get(&self, url: &str) -> Result<String, String> {
+        let output = Command::new("curl")
+            .args(["-sSL", "--fail", "--proto", "=http,https", "--proto-redir", "=http,https"])
+            .args(["--max-time", &self.timeout_secs.to_string(), "--", url])
+            .output()
+            .map_err(|e| format!("无法执行 curl: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("下载失败: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/// 版本号片段类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PartType {
+    Number,
+    Separator,
+    String,
+}
+
+fn part_type(c: char) -> PartType {
+    if c.is_ascii_digit() {
+        PartType::Number
+    } else if c == '.' {
+        PartType::Separator
+    } else {
+        PartType::String
+    }
+}
+
+/// 按字符类型拆分版本号（每个分隔符单独成为一段）
+fn split_version(version: &str) -> Vec<(PartType, String)> {
+    let mut parts: Vec<(PartType, String)> = Vec::new();
+    for c in version.trim().chars() {
+        let kind = part_type(c);
+        match parts.last_mut() {
+            Some((last_kind, text)) if *last_kind == kind && kind != PartType::Separator => text.push(c),
+            _ => parts.push((kind, c.to_string())),
+        }
+    }
+    parts
+}
+
+/// 按 Sparkle（SUStandardVersionComparator）的规则比较版本号
+///
+/// 数字按数值比较；数字比字符串新（1.0 > 1.0b1）；
+/// 前缀相同时，多出的部分是字符串则较短者更新，否则较长者更新。
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts_a = split_version(a);
+    let parts_b = split_version(b);
+
+    for ((type_a, text_a), (type_b, text_b)) in parts_a.iter().zip(parts_b.iter()) {
+        let ordering = if type_a == type_b {
+            match type_a {
+                PartType::Number => {
+                    let num_a = text_a.trim_start_matches('0');
+                    let num_b = text_b.trim_start_matches('0');
+                    num_a.len().cmp(&num_b.len()).then_with(|| num_a.cmp(num_b))
+                }
+                PartType::String => text_a.cmp(text_b),
+                PartType::Separator => Ordering::Equal,
+            }
+        } else if *type_b == PartType::String {
+            Ordering::Greater
+        } else if *type_a == PartType::String {
+            Ordering::Less
+        } else if *type_a == PartType::Number {
+            // 一边是数字、一边是分隔符，分隔符无效
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    match parts_a.len().cmp(&parts_b.len()) {
+        Ordering::Equal => Ordering::Equal,
+        Ordering::Greater => {
+            if parts_a[parts_b.len()].0 == PartType::String {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+        Ordering::Less => {
+            if parts_b[parts_a.len()].0 == PartType::String {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        }
+    }
+}
+
+/// 读取元素属性
+fn attribute(element: &BytesStart, name: &str) -> Option<String> {
+    element.attributes()
+        .flatten()
+        .find(|a| a.key.as_ref() == name.as_bytes())
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.trim().to_string())
+}
+
+/// 新建空版本
+fn empty_item() -> AppcastItem {
+    AppcastItem {
+        title: String::new(),
+        version: String::new(),
+        short_version: String::new(),
+        pub_date: String::new(),
+        minimum_system_version: None,
+        release_notes_url: None,
+        description: None,
+        download_url: String::new(),
+        length: 0,
+        critical: false,
+        deltas: Vec::new(),
+    }
+}
+
+/// 处理 enclosure 元素（完整包或 sparkle:deltas 中的增量包）
+fn apply_enclosure(item: &mut AppcastItem, element: &BytesStart, in_deltas: bool) {
+    let url = attribute(element, "url").unwrap_or_default();
+    let length = attribute(element, "length").and_then(|l| l.parse().ok()).unwrap_or(0);
+
+    if in_deltas {
+        if let Some(from_version) = attribute(element, "sparkle:deltaFrom") {
+            item.deltas.push(AppcastDelta { from_version, url, length });
+        }
+        return;
+    }
+
+    item.download_url = url;
+    item.length = length;
+    // 旧版 appcast 把版本号写在 enclosure 属性上
+    if item.version.is_empty() {
+        item.version = attribute(element, "sparkle:version").unwrap_or_default();
+    }
+    if item.short_version.is_empty() {
+        item.short_version = attribute(element, "sparkle:shortVersionString").unwrap_or_default();
+    }
+}
+
+/// 保存 item 内元素的文本
+fn apply_text(item: &mut AppcastItem, element: &str, text: String) {
+    match element {
+        "title" => item.title = text,
+        "pubDate" => item.pub_date = text,
+        "description" => item.description = Some(text),
+        "sparkle:version" => item.version = text,
+        "sparkle:shortVersionString" => item.short_version = text,
+        "sparkle:minimumSystemVersion" => item.minimum_system_version = Some(text),
+        "sparkle:releaseNotesLink" => item.release_notes_url = Some(text),
+        _ => {}
+    }
+}
+
+/// 解析 Sparkle appcast XML
+pub fn parse_appcast(xml: &str) -> Result<Vec<AppcastItem>, String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut items = Vec::new();
+    let mut current: Option<AppcastItem> = None;
+    let mut element = String::new();
+    let mut in_deltas = false;
+
+    loop {
+        let event = reader.read_event()
+            .map_err(|e| format!("appcast 解析失败 (位置 {}): {}", reader.buffer_position(), e))?;
+        match event {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                match name.as_str() {
+                    "item" => current = Some(empty_item()),
+                    "sparkle:deltas" => in_deltas = true,
+                    "sparkle:criticalUpdate" => {
+                        if let Some(item) = current.as_mut() {
+                            item.critical = true;
+                        }
+                    }
+                    "enclosure" => {
+                        if let Some(item) = current.as_mut() {
+                            apply_enclosure(item, &e, in_deltas);
+                        }
+                    }
+                    _ => {}
+                }
+                element = name;
+            }
+            Event::Empty(e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if let Some(item) = current.as_mut() {
+                    match name.as_str() {
+                        "enclosure" => apply_enclosure(item, &e, in_deltas),
+                        "sparkle:criticalUpdate" => item.critical = true,
+                        _ => {}
+                    }
+                }
+            }
+            Event::Text(t) => {
+                if let Some(item) = current.as_mut() {
+                    let text = t.unescape().map(|s| s.to_string()).unwrap_or_default();
+                    apply_text(item, &element, text);
+                }
+            }
+            Event::CData(c) => {
+                if let Some(item) = current.as_mut() {
+                    apply_text(item, &element, String::from_utf8_lossy(&c).to_string());
+                }
+            }
+            Event::End(e) => {
+                match e.name().as_ref() {
+                    b"item" => {
+                        if let Some(item) = current.take() {
+                            if !item.version.is_empty() || !item.short_version.is_empty() {
+                                items.push(item);
+                            }
+                        }
+                    }
+                    b"sparkle:deltas" => in_deltas = false,
+                    _ => {}
+                }
+                element.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(items)
+}
+
+/// 当前系统版本
+fn current_os_version() -> Option<String> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    let output = Command::new("sw_vers").arg("-productVersion").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 是否为 http 或 https 地址（其他协议可能读取本地文件或被当作命令行选项）
+fn is_http_url(url: &str) -> bool {
+    let lower = url.to_ascii_lowercase();
+    lower.starts_with("https://") || lower.starts_with("http://")
+}
+
+/// 应用更新检查服务
+pub struct UpdateService {
+    client: Box<dyn HttpClient>,
+    os_version: Option<String>,
+}
+
+impl UpdateService {
+    /// 创建新的更新检查服务实例（使用 curl）
+    pub fn new() -> Self {
+        Self::with_client(Box::new(CurlHttpClient::new(15)), current_os_version())
+    }
+
+    /// 使用指定 HTTP 客户端和系统版本创建实例
+    pub fn with_client(client: Box<dyn HttpClient>, os_version: Option<String>) -> Self {
+        UpdateService { client, os_version }
+    }
+
+    /// 清除 appcast 缓存
+    pub fn clear_cache(&self) {
+        if let Ok(mut cache) = APPCAST_CACHE.lock() {
+            *cache = None;
+        }
+    }
+
+    /// 获取 appcast（优先使用未过期的缓存）
+    pub fn fetch_appcast(&self, feed_url: &str) -> Result<Vec<AppcastItem>, String> {
+        if let Ok(cache) = APPCAST_CACHE.lock() {
+            if let Some((fetched_at, items)) = cache.as_ref().and_then(|c| c.get(feed_url)) {
+                if fetched_at.elapsed() < CACHE_TTL {
+                    return Ok(items.clone());
+                }
+            }
+        }
+
+        if !is_http_url(feed_url) {
+            return Err(format!("不支持的 appcast 地址: {}", feed_url));
+        }
+        let xml = self.client.get(feed_url)?;
+        let items = parse_appcast(&xml)?;
+
+        if let Ok(mut cache) = APPCAST_CACHE.lock() {
+            cache.get_or_insert_with(HashMap::new)
+                .insert(feed_url.to_string(), (Instant::now(), items.clone()));
+        }
+        Ok(items)
+    }
+
+    /// 检查所有声明了 SUFeedURL 的已安装应用
+    pub fn check_installed_apps(&self, force: bool) -> Result<UpdateCheckReport, String> {
+        if force {
+            self.clear_cache();
+        }
+        let apps = self.check_apps(&app_bundle_paths());
+
+        let outdated_count = apps.iter().filter(|a| a.update_available).count() as u32;
+        let failed_count = apps.iter().filter(|a| a.error.is_some()).count() as u32;
+        Ok(UpdateCheckReport { apps, outdated_count, failed_count })
+    }
+
+    /// 分批并行检查多个应用，跳过没有 SUFeedURL 的应用（结果保持输入顺序）
+    fn check_apps(&self, app_paths: &[PathBuf]) -> Vec<AppUpdateInfo> {
+        let mut apps = Vec::new();
+        for chunk in app_paths.chunks(MAX_PARALLEL_CHECKS) {
+            thread::scope(|scope| {
+                let handles: Vec<_> = chunk.iter()
+                    .map(|path| scope.spawn(move || self.check_app(&path.to_string_lossy()).ok()))
+                    .collect();
+                apps.extend(handles.into_iter().filter_map(|h| h.join().ok().flatten()));
+            });
+        }
+        apps
+    }
+
+    /// 检查单个应用，没有 SUFeedURL 时返回错误
+    pub fn check_app(&self, app_path: &str) -> Result<AppUpdateInfo, String> {
+        let path = Path::new(app_path);
+        let value = plist::Value::from_file(path.join("Contents/Info.plist"))
+            .map_err(|e| format!("无法读取 Info.plist: {}", e))?;
+        let info = value.as_dictionary().ok_or_else(|| "Info.plist 格式错误".to_string())?;
+        let info_string = |key: &str| info.get(key).and_then(|v| v.as_string()).map(|s| s.trim().to_string());
+
+        let feed_url = info_string("SUFeedURL")
+            .filter(|u| !u.is_empty())
+            .ok_or_else(|| "应用未声明 SUFeedURL".to_string())?;
+
+        let mut result = AppUpdateInfo {
+            app_path: app_path.to_string(),
+            app_name: info_string("CFBundleDisplayName")
+                .or_else(|| info_string("CFBundleName"))
+                .or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+                .unwrap_or_else(|| "Unknown".to_string()),
+            bundle_id: info_string("CFBundleIdentifier").unwrap_or_default(),
+            feed_url: feed_url.clone(),
+            current_version: info_string("CFBundleShortVersionString").unwrap_or_default(),
+            current_build: info_string("CFBundleVersion").unwrap_or_default(),
+            latest: None,
+            update_available: false,
+            compatible: true,
+            applicable_delta: None,
+            error: None,
+        };
+
+        let items = match self.fetch_appcast(&feed_url) {
+            Ok(items) => items,
+            Err(e) => {
+                result.error = Some(e);
+                return Ok(result);
+            }
+        };
+
+        // 优先选择兼容当前系统的最新版本
+        let is_compatible = |item: &AppcastItem| match (&item.minimum_system_version, &self.os_version) {
+            (Some(minimum), Some(os)) => compare_versions(os, minimum) != Ordering::Less,
+            _ => true,
+        };
+        let by_version = |a: &&AppcastItem, b: &&AppcastItem| compare_versions(item_version(a), item_version(b));
+        let latest = items.iter().filter(|i| is_compatible(i)).max_by(by_version)
+            .or_else(|| items.iter().max_by(by_version));
+
+        if let Some(latest) = latest {
+            // sparkle:version 对应 CFBundleVersion，只有显示版本时与 CFBundleShortVersionString 比较
+            let newer = if !latest.version.is_empty() && !result.current_build.is_empty() {
+                compare_versions(&latest.version, &result.current_build) == Ordering::Greater
+            } else {
+                compare_versions(&latest.short_version, &result.current_version) == Ordering::Greater
+            };
+            result.update_available = newer;
+            result.compatible = is_compatible(latest);
+            result.applicable_delta = latest.deltas.iter()
+                .find(|d| d.from_version == result.current_build)
+                .cloned();
+            result.latest = Some(latest.clone());
+        }
+
+        Ok(result)
+    }
+}
+
+/// 用于排序的版本号（优先构建版本）
+fn item_version(item: &AppcastItem) -> &str {
+    if item.version.is_empty() {
+        &item.short_version
+    } else {
+        &item.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+    use std::sync::Arc;
+
+    /// 返回固定 appcast 的客户端，记录请求次数并可模拟网络延迟
+    struct StubClient {
+        body: String,
+        delay: Duration,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl HttpClient for StubClient {
+        fn get(&self, _url: &str) -> Result<String, String> {
+            self.requests.fetch_add(1, AtomicOrdering::SeqCst);
+            thread::sleep(self.delay);
+            Ok(self.body.clone())
+        }
+    }
+
+    fn stub_service(body: &str, delay: Duration, os_version: Option<&str>) -> (UpdateService, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let client = StubClient { body: body.to_string(), delay, requests: Arc::clone(&requests) };
+        (UpdateService::with_client(Box::new(client), os_version.map(|v| v.to_string())), requests)
+    }
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mole-update-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// 写入声明 SUFeedURL 的应用（feed 地址按测试区分，避免共享缓存互相影响）
+    fn write_app(dir: &Path, name: &str, feed_url: Option<&str>, short_version: &str, build: &str) -> PathBuf {
+        let app = dir.join(format!("{}.app", name));
+        fs::create_dir_all(app.join("Contents")).unwrap();
+        let mut info = plist::Dictionary::new();
+        info.insert("CFBundleName".to_string(), name.into());
+        info.insert("CFBundleShortVersionString".to_string(), short_version.into());
+        info.insert("CFBundleVersion".to_string(), build.into());
+        if let Some(feed_url) = feed_url {
+            info.insert("SUFeedURL".to_string(), feed_url.into());
+        }
+        plist::Value::Dictionary(info).to_file_xml(app.join("Contents/Info.plist")).unwrap();
+        app
+    }
+
+    const APPCAST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<rss version="2.0" xmlns:sparkle="http://www.andymatuschak.org/xml-namespaces/sparkle">
+  <channel>
+    <item>
+      <title>Version 2.0</title>
+      <sparkle:version>200</sparkle:version>
+      <sparkle:shortVersionString>2.0</sparkle:shortVersionString>
+      <sparkle:minimumSystemVersion>99.0</sparkle:minimumSystemVersion>
+      <enclosure url="https://example.com/2.0.zip" length="20"/>
+    </item>
+    <item>
+      <title>Version 1.5</title>
+      <description><![CDATA[<b>Fixes</b> & improvements]]></description>
+      <sparkle:releaseNotesLink>https://example.com/1.5.html</sparkle:releaseNotesLink>
+      <sparkle:criticalUpdate/>
+      <enclosure url="https://example.com/1.5.zip" length="15" sparkle:version="150" sparkle:shortVersionString="1.5"/>
+      <sparkle:deltas>
+        <enclosure url="https://example.com/100-150.delta" sparkle:deltaFrom="100" length="3"/>
+        <enclosure url="https://example.com/120-150.delta" sparkle:deltaFrom="120" length="2"/>
+      </sparkle:deltas>
+    </item>
+    <item>
+      <title>No version</title>
+      <enclosure url="https://example.com/unknown.zip" length="1"/>
+    </item>
+  </channel>
+</rss>"#;
+
+    #[test]
+    fn compares_versions_like_sparkle() {
+        assert_eq!(compare_versions("1.0", "1.0b1"), Ordering::Greater);
+        assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.1", "1.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0a1", "1.0b1"), Ordering::Less);
+        assert_eq!(compare_versions("1.0b1", "1.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.0", "2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.09", "1.9"), Ordering::Equal);
+        assert_eq!(compare_versions("10", "9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn parses_items_deltas_cdata_and_legacy_enclosures() {
+        let items = parse_appcast(APPCAST).unwrap();
+        assert_eq!(items.len(), 2);
+
+        let latest = &items[0];
+        assert_eq!((latest.version.as_str(), latest.short_version.as_str()), ("200", "2.0"));
+        assert_eq!(latest.minimum_system_version.as_deref(), Some("99.0"));
+        assert_eq!((latest.download_url.as_str(), latest.length), ("https://example.com/2.0.zip", 20));
+        assert!(!latest.critical);
+
+        let legacy = &items[1];
+        assert_eq!((legacy.version.as_str(), legacy.short_version.as_str()), ("150", "1.5"));
+        assert_eq!(legacy.description.as_deref(), Some("<b>Fixes</b> & improvements"));
+        assert_eq!(legacy.release_notes_url.as_deref(), Some("https://example.com/1.5.html"));
+        assert!(legacy.critical);
+        assert_eq!(legacy.download_url, "https://example.com/1.5.zip");
+        let deltas: Vec<(&str, u64)> = legacy.deltas.iter().map(|d| (d.from_version.as_str(), d.length)).collect();
+        assert_eq!(deltas, vec![("100", 3), ("120", 2)]);
+
+        assert!(parse_appcast("<rss><channel><item></channel></rss>").is_err());
+    }
+
+    #[test]
+    fn picks_latest_compatible_version_and_delta() {
+        let dir = fixture_dir("check");
+        let app = write_app(&dir, "Chat", Some("https://example.com/check/appcast.xml"), "1.0", "100");
+        let (service, requests) = stub_service(APPCAST, Duration::ZERO, Some("14.0"));
+
+        let result = service.check_app(&app.to_string_lossy()).unwrap();
+        assert!(result.update_available);
+        assert!(result.compatible);
+        assert_eq!(result.latest.as_ref().unwrap().version, "150");
+        assert_eq!(result.applicable_delta.as_ref().unwrap().url, "https://example.com/100-150.delta");
+
+        // 系统满足要求时选择 2.0；空响应的客户端不会被调用，appcast 来自缓存
+        let (newer_os, newer_requests) = stub_service("", Duration::ZERO, Some("99.1"));
+        let result = newer_os.check_app(&app.to_string_lossy()).unwrap();
+        assert_eq!(result.latest.unwrap().version, "200");
+        assert!(result.applicable_delta.is_none());
+        assert_eq!(requests.load(AtomicOrdering::SeqCst), 1);
+        assert_eq!(newer_requests.load(AtomicOrdering::SeqCst), 0);
+
+        let current = write_app(&dir, "Current", Some("https://example.com/check/appcast.xml"), "1.5", "150");
+        assert!(!service.check_app(&current.to_string_lossy()).unwrap().update_available);
+        let no_feed = write_app(&dir, "Plain", None, "1.0", "1");
+        assert!(service.check_app(&no_feed.to_string_lossy()).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_non_http_feeds() {
+        let (service, requests) = stub_service(APPCAST, Duration::ZERO, None);
+        for url in ["file:///etc/passwd", "-o/tmp/x", "ftp://example.com/a.xml", " https://example.com"] {
+            assert!(service.fetch_appcast(url).is_err(), "{}", url);
+        }
+        assert_eq!(requests.load(AtomicOrdering::SeqCst), 0);
+    }
+
+    #[test]
+    fn checks_apps_in_parallel() {
+        let dir = fixture_dir("parallel");
+        let apps: Vec<PathBuf> = (0..MAX_PARALLEL_CHECKS * 2)
+            .map(|i| {
+                let feed = format!("https://example.com/parallel/{}.xml", i);
+                write_app(&dir, &format!("App{}", i), Some(&feed), "1.0", "100")
+            })
+            .chain(std::iter::once(write_app(&dir, "Plain", None, "1.0", "1")))
+            .collect();
+        let (service, requests) = stub_service(APPCAST, Duration::from_millis(200), None);
+
+        let started = Instant::now();
+        let results = service.check_apps(&apps);
+        assert!(started.elapsed() < Duration::from_millis(200 * 4), "{:?}", started.elapsed());
+        assert_eq!(requests.load(AtomicOrdering::SeqCst), MAX_PARALLEL_CHECKS * 2);
+        let names: Vec<String> = results.iter().map(|r| r.app_name.clone()).collect();
+        let expected: Vec<String> = (0..MAX_PARALLEL_CHECKS * 2).map(|i| format!("App{}", i)).collect();
+        assert_eq!(names, expected);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
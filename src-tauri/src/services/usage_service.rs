@@ -0,0 +1,318 @@
+//! 应用使用记录服务实现 - 使用 DuckDB
+//!
+//! 后台线程定期读取系统采样器的进程列表，把主程序正在运行的应用包记为"正在使用"，
+//! 并根据进程启动时间累计启动次数。首次遇到的应用用主程序的访问时间作为初始值。
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use duckdb::{Connection, params};
+use crate::models::app::AppInfo;
+use crate::models::process::Process;
+use crate::models::usage::AppUsage;
+use crate::services::app_service::AppService;
+use crate::services::macho_service::MachOService;
+use crate::services::sampler_service::latest_processes;
+
+/// 采样线程是否已启动
+static SAMPLER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// 串行化数据库访问（同一进程内多个连接同时打开会互相锁定）
+static DB_LOCK: Mutex<()> = Mutex::new(());
+
+/// 当前时间（秒）
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 可执行文件所属的最外层应用包（辅助程序归属到主应用）
+pub fn owning_app_bundle(executable: &Path) -> Option<PathBuf> {
+    executable.ancestors()
+        .filter(|p| p.extension().map(|e| e == "app").unwrap_or(false))
+        .last()
+        .map(|p| p.to_path_buf())
+}
+
+/// 正在运行的应用包及其主程序的启动时间
+///
+/// 只统计可执行文件为应用包主程序（Contents/MacOS/<CFBundleExecutable>）的进程，
+/// 常驻的登录项和辅助进程不会让应用一直显示为"正在使用"；同一应用多个实例取最早的启动时间
+fn running_main_executables<F>(processes: &[Process], mut main_executable: F) -> HashMap<String, u64>
+where
+    F: FnMut(&Path) -> Option<PathBuf>,
+{
+    let mut main_executables: HashMap<PathBuf, Option<PathBuf>> = HashMap::new();
+    let mut running: HashMap<String, u64> = HashMap::new();
+    for process in processes {
+        let exe_path = Path::new(&process.exe_path);
+        let Some(bundle) = owning_app_bundle(exe_path) else {
+            continue;
+        };
+        let main = main_executables.entry(bundle.clone())
+            .or_insert_with(|| main_executable(&bundle));
+        if main.as_deref() != Some(exe_path) {
+            continue;
+        }
+        running.entry(bundle.to_string_lossy().to_string())
+            .and_modify(|started| *started = (*started).min(process.start_time))
+            .or_insert(process.start_time);
+    }
+    running
+}
+
+/// 启动后台采样线程（重复调用只启动一次）
+pub fn start_sampler(interval: Duration) {
+    if SAMPLER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    thread::spawn(move || {
+        let service = UsageService::new();
+        if let Ok(installed) = AppService::new().get_installed_apps() {
+            service.seed_from_access_times(&installed.apps);
+        }
+        let macho = MachOService::new();
+        loop {
+            let running = running_main_executables(&latest_processes(), |bundle| macho.main_executable(bundle));
+            service.record_sample(&running);
+            thread::sleep(interval);
+        }
+    });
+}
+
+/// 应用使用记录服务
+pub struct UsageService {
+    db_path: PathBuf,
+}
+
+impl UsageService {
+    /// 创建新的使用记录服务实例（不自动创建数据库）
+    pub fn new() -> Self {
+        let db_path = dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("com.mole.app")
+            .join("app_usage.db");
+        Self::with_db_path(db_path)
+    }
+
+    /// 使用指定数据库文件创建实例
+    pub fn with_db_path(db_path: impl Into<PathBuf>) -> Self {
+        UsageService { db_path: db_path.into() }
+    }
+
+    /// 获取数据库连接（仅当数据库存在时）
+    fn get_connection(&self) -> Option<Connection> {
+        if !self.db_path.exists() {
+            return None;
+        }
+        Connection::open(&self.db_path).ok()
+    }
+
+    /// 获取或创建数据库连接，并确保表存在
+    fn get_or_create_connection(&self) -> Option<Connection> {
+        if let Some(parent) = self.db_path.parent() {
+            if fs::create_dir_all(parent).is_err() {
+                return None;
+            }
+        }
+        let conn = Connection::open(&self.db_path).ok()?;
+        let create_table = conn.execute(
+            "CREATE TABLE IF NOT EXISTS app_usage (
+                app_path VARCHAR PRIMARY KEY,
+                last_used UBIGINT,
+                last_launch UBIGINT,
+                launch_count UINTEGER NOT NULL DEFAULT 0,
+                source VARCHAR NOT NULL
+            )",
+            [],
+        );
+        if create_table.is_err() {
+            println!("[AppUsage] Failed to create table: {:?}", create_table);
+            return None;
+        }
+        Some(conn)
+    }
+
+    /// 读取所有使用记录
+    fn load_all(&self, conn: &Connection) -> HashMap<String, AppUsage> {
+        let mut result = HashMap::new();
+        if let Ok(mut stmt) = conn.prepare(
+            "SELECT app_path, last_used, last_launch, launch_count, source FROM app_usage"
+        ) {
+            if let Ok(rows) = stmt.query_map([], |row| {
+                Ok(AppUsage {
+                    app_path: row.get(0)?,
+                    last_used: row.get(1)?,
+                    last_launch: row.get(2)?,
+                    launch_count: row.get(3)?,
+                    source: row.get(4)?,
+                })
+            }) {
+                for usage in rows.flatten() {
+                    result.insert(usage.app_path.clone(), usage);
+                }
+            }
+        }
+        result
+    }
+
+    /// 写入一条使用记录
+    fn save(&self, conn: &Connection, usage: &AppUsage) {
+        let _ = conn.execute(
+            "INSERT OR REPLACE INTO app_usage (app_path, last_used, last_launch, launch_count, source)
+             VALUES (?, ?, ?, ?, ?)",
+            params![&usage.app_path, usage.last_used, usage.last_launch, usage.launch_count, &usage.source],
+        );
+    }
+
+    /// 记录一次采样: 正在运行的应用及其最新进程启动时间
+    pub fn record_sample(&self, running: &HashMap<String, u64>) {
+        if running.is_empty() {
+            return;
+        }
+        let _guard = DB_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(conn) = self.get_or_create_connection() else {
+            return;
+        };
+        let existing = self.load_all(&conn);
+        let now = now_secs();
+
+        for (app_path, started) in running {
+            let mut usage = existing.get(app_path).cloned().unwrap_or(AppUsage {
+                app_path: app_path.clone(),
+                last_used: None,
+                last_launch: None,
+                launch_count: 0,
+                source: "sampler".to_string(),
+            });
+            // 启动时间比上次记录新，说明应用被重新启动过
+            if usage.last_launch.map(|last| *started > last).unwrap_or(true) {
+                usage.launch_count += 1;
+                usage.last_launch = Some(*started);
+            }
+            usage.last_used = Some(now);
+            usage.source = "sampler".to_string();
+            self.save(&conn, &usage);
+        }
+
+        let _ = conn.execute("CHECKPOINT", []);
+    }
+
+    /// 用主程序访问时间为尚无记录的应用生成初始使用时间
+    pub fn seed_from_access_times(&self, apps: &[AppInfo]) {
+        let _guard = DB_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(conn) = self.get_or_create_connection() else {
+            return;
+        };
+        let existing = self.load_all(&conn);
+        let macho = MachOService::new();
+
+        for app in apps {
+            if existing.contains_key(&app.path) {
+                continue;
+            }
+            let accessed = macho.main_executable(Path::new(&app.path))
+                .and_then(|exe| fs::metadata(exe).ok())
+                .and_then(|m| m.accessed().ok())
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+            let Some(accessed) = accessed else {
+                continue;
+            };
+            self.save(&conn, &AppUsage {
+                app_path: app.path.clone(),
+                last_used: Some(accessed),
+                last_launch: None,
+                launch_count: 0,
+                source: "atime".to_string(),
+            });
+        }
+
+        let _ = conn.execute("CHECKPOINT", []);
+    }
+
+    /// 获取单个应用的使用记录
+    pub fn get_usage(&self, app_path: &str) -> Option<AppUsage> {
+        let _guard = DB_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let conn = self.get_connection()?;
+        self.load_all(&conn).remove(app_path)
+    }
+
+    /// 为应用列表填充最后使用时间和启动次数
+    pub fn apply_usage(&self, apps: &mut [AppInfo]) {
+        let _guard = DB_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(conn) = self.get_connection() else {
+            return;
+        };
+        let usage = self.load_all(&conn);
+        for app in apps.iter_mut() {
+            if let Some(record) = usage.get(&app.path) {
+                app.last_used = record.last_used;
+                app.launch_count = record.launch_count;
+            }
+        }
+    }
+
+    /// 筛选超过指定天数未使用的应用（使用时间未知的应用不包含在内）
+    pub fn filter_unused(&self, apps: Vec<AppInfo>, days: u32) -> Vec<AppInfo> {
+        let cutoff = now_secs().saturating_sub(days as u64 * 86_400);
+        let mut unused: Vec<AppInfo> = apps.into_iter()
+            .filter(|app| app.last_used.map(|t| t < cutoff).unwrap_or(false))
+            .collect();
+        unused.sort_by_key(|app| app.last_used);
+        unused
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, exe_path: &str, start_time: u64) -> Process {
+        Process {
+            pid,
+            name: String::new(),
+            cpu_usage: 0.0,
+            memory_usage: 0.0,
+            status: String::new(),
+            start_time,
+            parent_pid: None,
+            user: String::new(),
+            command_line: String::new(),
+            exe_path: exe_path.to_string(),
+            cwd: String::new(),
+            thread_count: None,
+            memory: 0,
+            virtual_memory: 0,
+        }
+    }
+
+    #[test]
+    fn counts_only_main_executables() {
+        let processes = vec![
+            process(1, "/Applications/Chat.app/Contents/Library/LoginItems/Launcher.app/Contents/MacOS/Launcher", 100),
+            process(2, "/Applications/Chat.app/Contents/Frameworks/Chat Helper.app/Contents/MacOS/Chat Helper", 150),
+            process(3, "/Applications/Chat.app/Contents/MacOS/Chat", 300),
+            process(4, "/Applications/Chat.app/Contents/MacOS/Chat", 200),
+            process(5, "/Applications/Sync.app/Contents/Library/LoginItems/Agent.app/Contents/MacOS/Agent", 50),
+            process(6, "/usr/sbin/syslogd", 10),
+        ];
+        let mut lookups = Vec::new();
+        let running = running_main_executables(&processes, |bundle| {
+            lookups.push(bundle.to_path_buf());
+            let name = bundle.file_stem()?.to_string_lossy().to_string();
+            Some(bundle.join("Contents/MacOS").join(name))
+        });
+
+        assert_eq!(running.len(), 1);
+        assert_eq!(running.get("/Applications/Chat.app"), Some(&200));
+        // 每个应用包只读取一次主程序
+        assert_eq!(lookups.len(), 2);
+    }
+}